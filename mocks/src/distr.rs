@@ -0,0 +1,118 @@
+use std::cell::Cell;
+
+/// A Distribution that returns a predefined Sequence of Values in order, completely ignoring the
+/// RNG it is sampled with.
+///
+/// This makes it easy to control the exact Rolls in a Test. Once all the Values have been
+/// returned, sampling again will panic.
+pub struct SequenceDistr {
+    values: Vec<usize>,
+    position: Cell<usize>,
+}
+
+impl SequenceDistr {
+    /// Creates a new Distribution, that will return the given Values in order
+    pub fn new(values: Vec<usize>) -> Self {
+        Self {
+            values,
+            position: Cell::new(0),
+        }
+    }
+}
+
+impl rand::distributions::Distribution<usize> for SequenceDistr {
+    fn sample<R: rand::Rng + ?Sized>(&self, _: &mut R) -> usize {
+        let position = self.position.get();
+        let value = *self
+            .values
+            .get(position)
+            .unwrap_or_else(|| panic!("SequenceDistr exhausted after {} rolls", position));
+
+        self.position.set(position + 1);
+        value
+    }
+}
+
+/// A simple Distribution that takes a Random Number and maps it onto the Range `min..=max`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RangeDistr {
+    /// The smallest Value that can be sampled
+    pub min: usize,
+    /// The largest Value that can be sampled
+    pub max: usize,
+}
+
+impl RangeDistr {
+    /// Creates a new Distribution for the Range `min..=max`
+    pub fn new(min: usize, max: usize) -> Self {
+        assert!(min <= max, "The Range {}..={} is empty", min, max);
+        Self { min, max }
+    }
+}
+
+impl rand::distributions::Distribution<usize> for RangeDistr {
+    fn sample<R: rand::Rng + ?Sized>(&self, rng: &mut R) -> usize {
+        let raw: usize = rng.gen();
+        (raw % (self.max - self.min + 1)) + self.min
+    }
+}
+
+/// A simple Distribution that takes a Random Number and maps it onto 1-6, like a normal Die.
+///
+/// This behaves exactly like a [`RangeDistr`] for the Range `1..=6`
+pub struct DumbDistr {}
+
+impl rand::distributions::Distribution<usize> for DumbDistr {
+    fn sample<R: rand::Rng + ?Sized>(&self, rng: &mut R) -> usize {
+        RangeDistr::new(1, 6).sample(rng)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::MockRand;
+    use rand::distributions::Distribution;
+
+    #[test]
+    fn returns_sequence() {
+        let distr = SequenceDistr::new(vec![6, 3, 1]);
+        let mut rng = MockRand::new(vec![0]);
+
+        let values: Vec<_> = (0..3).map(|_| distr.sample(&mut rng)).collect();
+        assert_eq!(vec![6, 3, 1], values);
+    }
+
+    #[test]
+    #[should_panic(expected = "SequenceDistr exhausted after 1 rolls")]
+    fn exhausted() {
+        let distr = SequenceDistr::new(vec![6]);
+        let mut rng = MockRand::new(vec![0]);
+
+        distr.sample(&mut rng);
+        distr.sample(&mut rng);
+    }
+
+    #[test]
+    fn small_range() {
+        let distr = RangeDistr::new(1, 3);
+        let mut rng = MockRand::new(vec![0, 1, 2, 3, 4, 5]);
+
+        let values: Vec<_> = (0..6).map(|_| distr.sample(&mut rng)).collect();
+        assert_eq!(vec![1, 2, 3, 1, 2, 3], values);
+    }
+
+    #[test]
+    fn dumb_distr_is_die() {
+        let distr = DumbDistr {};
+        let range = RangeDistr::new(1, 6);
+        let mut rng = MockRand::new((0..12).collect());
+        let mut range_rng = MockRand::new((0..12).collect());
+
+        let values: Vec<_> = (0..12).map(|_| distr.sample(&mut rng)).collect();
+        assert_eq!(vec![1, 2, 3, 4, 5, 6, 1, 2, 3, 4, 5, 6], values);
+
+        let range_values: Vec<_> = (0..12).map(|_| range.sample(&mut range_rng)).collect();
+        assert_eq!(values, range_values);
+    }
+}
@@ -1,11 +1,25 @@
 /// A Test RNG implementation, which returns predefined Values
+///
+/// By default the Values are repeated from the start, once all of them have been returned.
+/// Using [`MockRand::new_strict`] instead will panic once all the Values have been used up.
 pub struct MockRand {
     results: Vec<u64>,
+    draws: usize,
+    strict: bool,
 }
 
 impl rand::RngCore for MockRand {
     fn next_u64(&mut self) -> u64 {
-        self.results.remove(0)
+        if self.results.is_empty() {
+            panic!("MockRand has no Values to return");
+        }
+        if self.strict && self.draws >= self.results.len() {
+            panic!("MockRand exhausted after {} draws", self.draws);
+        }
+
+        let value = self.results[self.draws % self.results.len()];
+        self.draws += 1;
+        value
     }
 
     fn next_u32(&mut self) -> u32 {
@@ -20,8 +34,45 @@ impl rand::RngCore for MockRand {
 }
 
 impl MockRand {
-    /// Creates a new Instance
+    /// Creates a new Instance, which cycles through the given Values
     pub fn new(results: Vec<u64>) -> Self {
-        Self { results }
+        Self {
+            results,
+            draws: 0,
+            strict: false,
+        }
+    }
+
+    /// Creates a new Instance, which panics once all the given Values have been returned
+    pub fn new_strict(results: Vec<u64>) -> Self {
+        Self {
+            results,
+            draws: 0,
+            strict: true,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::RngCore;
+
+    #[test]
+    fn cycles_values() {
+        let mut rng = MockRand::new(vec![1, 2, 3]);
+
+        let values: Vec<_> = (0..7).map(|_| rng.next_u64()).collect();
+        assert_eq!(vec![1, 2, 3, 1, 2, 3, 1], values);
+    }
+
+    #[test]
+    #[should_panic(expected = "MockRand exhausted after 2 draws")]
+    fn strict_exhausted() {
+        let mut rng = MockRand::new_strict(vec![1, 2]);
+
+        assert_eq!(1, rng.next_u64());
+        assert_eq!(2, rng.next_u64());
+        rng.next_u64();
     }
 }
@@ -1,10 +1,34 @@
+use std::{
+    collections::VecDeque,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex,
+    },
+    task::Waker,
+};
+
 use futures::{Sink, Stream};
 
 /// Create a Test Socket/Stream + Sink
-#[derive(Debug)]
+///
+/// Once all the given Messages have been received, the Stream stays pending until another Message
+/// is pushed to it with [`MockSocket::push`].
+///
+/// Clones of the Socket share the same pending Messages and whether sending fails, so a Clone can be kept around to push
+/// Messages to a Socket that was already split or moved into a Task.
+#[derive(Debug, Clone)]
 pub struct MockSocket<C> {
-    msgs: Vec<C>,
+    pending: Arc<Mutex<Pending<C>>>,
     tx: tokio::sync::mpsc::UnboundedSender<C>,
+    failing: Arc<AtomicBool>,
+}
+
+/// The Messages that have not been received from a Socket yet
+#[derive(Debug)]
+struct Pending<C> {
+    msgs: VecDeque<C>,
+    /// The Waker of the Task waiting for the next Message
+    waker: Option<Waker>,
 }
 
 impl<C> Stream for MockSocket<C>
@@ -14,11 +38,17 @@ where
     type Item = Result<C, axum::Error>;
 
     fn poll_next(
-        mut self: std::pin::Pin<&mut Self>,
-        _: &mut std::task::Context<'_>,
+        self: std::pin::Pin<&mut Self>,
+        cx: &mut std::task::Context<'_>,
     ) -> std::task::Poll<Option<Self::Item>> {
-        let msg = self.msgs.remove(0);
-        std::task::Poll::Ready(Some(Ok(msg)))
+        let mut pending = self.pending.lock().unwrap();
+        match pending.msgs.pop_front() {
+            Some(msg) => std::task::Poll::Ready(Some(Ok(msg))),
+            None => {
+                pending.waker = Some(cx.waker().clone());
+                std::task::Poll::Pending
+            }
+        }
     }
 }
 
@@ -47,6 +77,10 @@ impl<C> Sink<C> for MockSocket<C> {
     }
 
     fn start_send(self: std::pin::Pin<&mut Self>, item: C) -> Result<(), Self::Error> {
+        if self.failing.load(Ordering::SeqCst) {
+            return Err("MockSocket is set to fail");
+        }
+
         let _ = self.tx.send(item);
         Ok(())
     }
@@ -55,6 +89,42 @@ impl<C> Sink<C> for MockSocket<C> {
 impl<C> MockSocket<C> {
     /// Create a new Test Socket
     pub fn new(tx: tokio::sync::mpsc::UnboundedSender<C>, msgs: Vec<C>) -> Self {
-        Self { tx, msgs }
+        Self {
+            tx,
+            pending: Self::pending(msgs),
+            failing: Arc::new(AtomicBool::new(false)),
+        }
+    }
+
+    /// Create a new Test Socket, where every attempt to send something fails
+    pub fn new_failing(tx: tokio::sync::mpsc::UnboundedSender<C>, msgs: Vec<C>) -> Self {
+        Self {
+            tx,
+            pending: Self::pending(msgs),
+            failing: Arc::new(AtomicBool::new(true)),
+        }
+    }
+
+    /// Lets every following attempt to send something through the Socket fail, like it was
+    /// created with [`MockSocket::new_failing`]
+    pub fn fail(&self) {
+        self.failing.store(true, Ordering::SeqCst);
+    }
+
+    /// Queues another Message to be received from the Socket and wakes up the Task waiting for it
+    pub fn push(&self, msg: C) {
+        let mut pending = self.pending.lock().unwrap();
+        pending.msgs.push_back(msg);
+
+        if let Some(waker) = pending.waker.take() {
+            waker.wake();
+        }
+    }
+
+    fn pending(msgs: Vec<C>) -> Arc<Mutex<Pending<C>>> {
+        Arc::new(Mutex::new(Pending {
+            msgs: msgs.into(),
+            waker: None,
+        }))
     }
 }
@@ -0,0 +1,88 @@
+use serde_derive::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+/// The Hash the first Entry of a [`RollAuditLog`] is chained to, which is exactly 64 zero
+/// Hex-Characters, the Length of a hex-encoded SHA-256 Digest, and not the Digest of anything
+pub const GENESIS_HASH: &str = "0000000000000000000000000000000000000000000000000000000000000000";
+
+/// A single Roll in the [`RollAuditLog`] of a Game
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RollAuditEntry {
+    /// The Number of Turns that were played before the Roll
+    pub turn: usize,
+    pub player: usize,
+    pub roll: usize,
+    /// The hex-encoded SHA-256 Digest over this Entry together with the Hash of the previous
+    /// Entry
+    pub hash: String,
+}
+
+impl RollAuditEntry {
+    /// Hashes the serialized Entry together with the Hash of the previous Entry
+    fn chained_hash(&self, prev: &str) -> String {
+        let content = serde_json::to_vec(&(prev, self.turn, self.player, self.roll))
+            .expect("Serializing an Audit Entry should always work as the Format is known");
+
+        hex::encode(Sha256::digest(content))
+    }
+}
+
+/// A Log of all the Rolls in a Game, where every Entry is chained to the previous one by its
+/// SHA-256 Hash, so changing, removing or reordering any Entry breaks the Chain.
+///
+/// The Chain only proves that the Rolls were not changed afterwards, if its [`RollAuditLog::head`]
+/// was published while the Game was running
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct RollAuditLog {
+    pub entries: Vec<RollAuditEntry>,
+}
+
+impl RollAuditLog {
+    /// Creates a new empty Log
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The Hash of the latest Entry, which is the [`GENESIS_HASH`] for an empty Log
+    pub fn head(&self) -> String {
+        self.entries
+            .last()
+            .map_or(GENESIS_HASH, |e| e.hash.as_str())
+            .to_string()
+    }
+
+    /// Appends the Roll of the Player in the given Turn to the Log
+    pub fn record(&mut self, turn: usize, player: usize, roll: usize) {
+        let mut entry = RollAuditEntry {
+            turn,
+            player,
+            roll,
+            hash: String::new(),
+        };
+        entry.hash = entry.chained_hash(&self.head());
+
+        self.entries.push(entry);
+    }
+
+    /// Checks that every Entry still matches its Hash.
+    ///
+    /// # Returns
+    /// * `Ok` if the whole Chain is intact
+    /// * `Err` the Index of the first Entry that does not match its Hash
+    pub fn verify(&self) -> Result<(), usize> {
+        let mut prev = GENESIS_HASH;
+        for (index, entry) in self.entries.iter().enumerate() {
+            if entry.chained_hash(prev) != entry.hash {
+                return Err(index);
+            }
+            prev = &entry.hash;
+        }
+
+        Ok(())
+    }
+
+    /// Removes all the Entries from the Log
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+}
@@ -0,0 +1,13 @@
+//! Writes the JSON Schema of the Protocol to the File given as the first Argument, which defaults
+//! to `protocol.schema.json`
+
+fn main() {
+    let path = std::env::args()
+        .nth(1)
+        .unwrap_or_else(|| "protocol.schema.json".to_string());
+
+    if let Err(e) = server::schema::write_protocol_schema(std::path::Path::new(&path)) {
+        eprintln!("Could not write the Schema to {:?}: {:?}", path, e);
+        std::process::exit(1);
+    }
+}
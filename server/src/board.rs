@@ -0,0 +1,57 @@
+/// The Number of Fields the shared Track of the default Board has for every Seat
+const FIELDS_PER_PLAYER: usize = 10;
+/// The Number of Players the default Board is laid out for
+const MIN_SEATS: usize = 4;
+/// The Number of Positions in the House of every Player on the default Board
+const HOUSE_SIZE: usize = 4;
+
+/// The Layout of a Board, which consists of a shared Track and a House for every Player
+#[derive(Debug, Clone, PartialEq)]
+pub struct Board {
+    /// The Number of Fields on the shared Track
+    pub fields: usize,
+    /// The Number of Positions in the House of every Player
+    pub house_size: usize,
+    /// The absolute Position of the Start-Field of every Player on the shared Track
+    pub player_starts: Vec<usize>,
+}
+
+impl Board {
+    /// The default Board for the given Number of Players, which always has Space for 4 Players
+    /// and only grows for more Players. The Start-Fields of the Players are spread evenly over the
+    /// Track, so in a Game with 2 Players they start opposite of each other
+    pub fn for_players(player_count: usize) -> Self {
+        let seats = player_count.max(MIN_SEATS);
+        let fields = seats * FIELDS_PER_PLAYER;
+        let offset = fields / player_count.max(1);
+
+        Self {
+            fields,
+            house_size: HOUSE_SIZE,
+            player_starts: (0..player_count).map(|i| i * offset).collect(),
+        }
+    }
+
+    /// The absolute Position on the shared Track for a Figure of the given Player, that has moved
+    /// the given Number of Fields from its Start-Field
+    pub fn absolute_position(&self, player: usize, moved: usize) -> usize {
+        (moved + self.player_starts[player]) % self.fields
+    }
+
+    /// The Number of Fields the given absolute Position is away from the Start-Field of the Player
+    pub fn relative_position(&self, player: usize, abs: usize) -> usize {
+        (abs + self.fields - self.player_starts[player] % self.fields) % self.fields
+    }
+
+    /// Whether the absolute Position is the Start-Field of any Player
+    pub fn is_start_field(&self, abs: usize) -> bool {
+        self.player_starts.contains(&abs)
+    }
+}
+
+impl Default for Board {
+    /// The default Board for 4 Players
+    fn default() -> Self {
+        Self::for_players(MIN_SEATS)
+    }
+}
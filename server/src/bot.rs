@@ -0,0 +1,413 @@
+use std::{
+    collections::VecDeque,
+    convert::Infallible,
+    pin::Pin,
+    task::{Context, Poll, Waker},
+};
+
+use axum::extract::ws::Message;
+use futures::{Sink, Stream};
+
+use crate::{Board, Figure, GameRequest, GameResponse};
+
+/// The Strategy a Bot uses to choose which Figure to move
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum Strategy {
+    /// Always moves the first Figure that can be moved
+    #[default]
+    FirstLegal,
+    /// Always advances the Figure that is closest to the House
+    FurthestFigure,
+    /// Prefers Moves that capture a Figure of another Player
+    Aggressive,
+    /// Prefers Moves that take a Figure off the shared Track or out of the Reach of other Players
+    Defensive,
+}
+
+impl Strategy {
+    /// Chooses one of the legal `options` to move by the given `value`, based on the Figures of all
+    /// the `players` on the `board`, where the Bot itself is the Player `me`.
+    ///
+    /// The `teams` contain the Team of every Player, as Teammates are neither captured nor feared
+    pub fn choose(
+        &self,
+        me: usize,
+        players: &[[Figure; 4]],
+        board: &Board,
+        teams: &[Option<usize>],
+        value: usize,
+        options: &[usize],
+    ) -> Option<usize> {
+        let board_size = board.fields;
+        let own = players.get(me)?;
+
+        let team_of = |player: usize| teams.get(player).copied().flatten();
+        let teammate = |player: usize| team_of(me).is_some_and(|t| team_of(player) == Some(t));
+
+        // The Fields on the shared Track, that are occupied by the Figures of the other Teams
+        let opponents: Vec<usize> = players
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| *i != me && !teammate(*i))
+            .flat_map(|(i, figures)| {
+                figures.iter().filter_map(move |f| match f {
+                    Figure::OnField { moved } if *moved < board_size => {
+                        Some(board.absolute_position(i, *moved))
+                    }
+                    _ => None,
+                })
+            })
+            .collect();
+
+        let on_track =
+            |moved: usize| (moved < board_size).then(|| board.absolute_position(me, moved));
+        let threatened = |pos: usize| {
+            opponents.iter().any(|o| {
+                let distance = (pos + board_size - o) % board_size;
+                (1..=6).contains(&distance)
+            })
+        };
+        let target = |figure: usize| match &own[figure] {
+            Figure::InStart => on_track(0),
+            Figure::OnField { moved } => on_track(moved + value),
+            Figure::InHouse { .. } => None,
+        };
+
+        match self {
+            Strategy::FirstLegal => options.first().copied(),
+            Strategy::FurthestFigure => options.iter().copied().max_by_key(|i| match &own[*i] {
+                Figure::InStart => 0,
+                Figure::OnField { moved } => moved + 1,
+                Figure::InHouse { pos } => board_size + pos + 1,
+            }),
+            Strategy::Aggressive => options
+                .iter()
+                .copied()
+                .find(|i| target(*i).is_some_and(|pos| opponents.contains(&pos)))
+                .or_else(|| options.first().copied()),
+            Strategy::Defensive => {
+                let current = |figure: usize| match &own[figure] {
+                    Figure::OnField { moved } => on_track(*moved),
+                    _ => None,
+                };
+
+                options
+                    .iter()
+                    .copied()
+                    .find(|i| current(*i).is_some() && target(*i).is_none())
+                    .or_else(|| {
+                        options.iter().copied().find(|i| {
+                            current(*i).is_some_and(threatened)
+                                && !target(*i).is_some_and(threatened)
+                        })
+                    })
+                    .or_else(|| options.first().copied())
+            }
+        }
+    }
+}
+
+/// A computer controlled Player, which behaves like the Socket of a real Player.
+///
+/// All the Responses sent to the Bot are answered by queueing the matching Requests, which are
+/// then received from the Bot like from any other Socket.
+#[derive(Debug, Default)]
+pub struct BotPlayer {
+    pending: VecDeque<Message>,
+    waker: Option<Waker>,
+    strategy: Strategy,
+    /// The Index of the Bot in the Game, once it has been indicated
+    me: Option<usize>,
+    /// The Layout of the Board the Game is played on
+    board: Board,
+    /// The Team of every Player, once they have been indicated
+    teams: Vec<Option<usize>>,
+    /// The Figures of all the Players from the last State
+    figures: Vec<[Figure; 4]>,
+    /// The Value of the last Roll
+    value: usize,
+    options: Vec<usize>,
+}
+
+impl BotPlayer {
+    /// Creates a new Bot for a Game on the given Board, which is immediately ready to start the
+    /// Game
+    pub fn new(board: Board) -> Self {
+        Self::with_strategy(Strategy::default(), board)
+    }
+
+    /// Creates a new Bot using the given Strategy for a Game on the given Board, which is
+    /// immediately ready to start the Game
+    pub fn with_strategy(strategy: Strategy, board: Board) -> Self {
+        let mut bot = Self::substitute(strategy, board);
+        bot.queue(&GameRequest::Ready);
+        bot
+    }
+
+    /// Creates a new Bot, that takes over the Seat of a Player in an already running Game on the
+    /// given Board and therefore does not get ready first
+    pub fn substitute(strategy: Strategy, board: Board) -> Self {
+        Self {
+            strategy,
+            board,
+            ..Default::default()
+        }
+    }
+
+    /// Queues the given Request to be received from the Bot
+    fn queue(&mut self, req: &GameRequest) {
+        let content = serde_json::to_string(req)
+            .expect("Serializing a Request should always work as the Format is known");
+        self.pending.push_back(Message::Text(content));
+
+        if let Some(waker) = self.waker.take() {
+            waker.wake();
+        }
+    }
+
+    /// Reacts to a Response sent to the Bot
+    fn respond(&mut self, resp: GameResponse) {
+        match resp {
+            GameResponse::Batch { responses } => {
+                for resp in responses {
+                    self.respond(resp);
+                }
+            }
+            GameResponse::Turn => {
+                self.queue(&GameRequest::Roll);
+            }
+            GameResponse::IndicatePlayer {
+                player, you, team, ..
+            } => {
+                if self.teams.len() <= player {
+                    self.teams.resize(player + 1, None);
+                }
+                self.teams[player] = team;
+                if you {
+                    self.me = Some(player);
+                }
+            }
+            GameResponse::State { players, .. } => {
+                self.figures = players.into_iter().map(|(_, figures)| figures).collect();
+            }
+            GameResponse::MoveOptions { value, figures } => {
+                self.value = value;
+                self.options = figures;
+                self.move_next();
+            }
+            GameResponse::ChooseAnotherFigure { .. } => {
+                self.move_next();
+            }
+            GameResponse::GameDone { .. } => {
+                self.queue(&GameRequest::Rematch);
+            }
+            _ => {}
+        };
+    }
+
+    /// Moves the Figure chosen by the Strategy out of the remaining Options.
+    ///
+    /// Without knowing its own Index, the Bot always falls back to the first Option.
+    fn move_next(&mut self) {
+        let choice = self
+            .me
+            .and_then(|me| {
+                self.strategy.choose(
+                    me,
+                    &self.figures,
+                    &self.board,
+                    &self.teams,
+                    self.value,
+                    &self.options,
+                )
+            })
+            .or_else(|| self.options.first().copied());
+
+        if let Some(figure) = choice {
+            self.options.retain(|o| *o != figure);
+            self.queue(&GameRequest::Move { figure });
+        }
+    }
+}
+
+impl Sink<Message> for BotPlayer {
+    type Error = Infallible;
+
+    fn poll_ready(self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn start_send(self: Pin<&mut Self>, item: Message) -> Result<(), Self::Error> {
+        if let Message::Text(content) = item {
+            match serde_json::from_str(&content) {
+                Ok(resp) => self.get_mut().respond(resp),
+                Err(e) => tracing::warn!("Bot received unknown Response: {:?}", e),
+            };
+        }
+
+        Ok(())
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn poll_close(self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+}
+
+impl Stream for BotPlayer {
+    type Item = Result<Message, axum::Error>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let bot = self.get_mut();
+        match bot.pending.pop_front() {
+            Some(msg) => Poll::Ready(Some(Ok(msg))),
+            None => {
+                bot.waker = Some(cx.waker().clone());
+                Poll::Pending
+            }
+        }
+    }
+}
+
+/// A Seat in a Game, which is either taken by a real Player connected over a Socket or by a Bot
+#[derive(Debug)]
+pub enum Seat<S> {
+    Remote(S),
+    Bot(BotPlayer),
+}
+
+impl<S> Sink<Message> for Seat<S>
+where
+    S: Sink<Message> + Unpin,
+{
+    type Error = S::Error;
+
+    fn poll_ready(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        match self.get_mut() {
+            Seat::Remote(socket) => Pin::new(socket).poll_ready(cx),
+            Seat::Bot(_) => Poll::Ready(Ok(())),
+        }
+    }
+
+    fn start_send(self: Pin<&mut Self>, item: Message) -> Result<(), Self::Error> {
+        match self.get_mut() {
+            Seat::Remote(socket) => Pin::new(socket).start_send(item),
+            Seat::Bot(bot) => {
+                let Ok(()) = Pin::new(bot).start_send(item);
+                Ok(())
+            }
+        }
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        match self.get_mut() {
+            Seat::Remote(socket) => Pin::new(socket).poll_flush(cx),
+            Seat::Bot(_) => Poll::Ready(Ok(())),
+        }
+    }
+
+    fn poll_close(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        match self.get_mut() {
+            Seat::Remote(socket) => Pin::new(socket).poll_close(cx),
+            Seat::Bot(_) => Poll::Ready(Ok(())),
+        }
+    }
+}
+
+impl<S> Stream for Seat<S>
+where
+    S: Stream<Item = Result<Message, axum::Error>> + Unpin,
+{
+    type Item = Result<Message, axum::Error>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        match self.get_mut() {
+            Seat::Remote(socket) => Pin::new(socket).poll_next(cx),
+            Seat::Bot(bot) => Pin::new(bot).poll_next(cx),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn figures() -> Vec<[Figure; 4]> {
+        vec![
+            [
+                Figure::OnField { moved: 5 },
+                Figure::OnField { moved: 30 },
+                Figure::OnField { moved: 38 },
+                Figure::InStart,
+            ],
+            [
+                Figure::OnField { moved: 28 },
+                Figure::OnField { moved: 5 },
+                Figure::InStart,
+                Figure::InStart,
+            ],
+        ]
+    }
+
+    /// Chooses for the first Player of a Game with 2 Players on the default Board
+    fn choose(strategy: Strategy, value: usize, options: &[usize]) -> Option<usize> {
+        strategy.choose(0, &figures(), &Board::for_players(2), &[], value, options)
+    }
+
+    #[test]
+    fn first_legal() {
+        assert_eq!(Some(0), choose(Strategy::FirstLegal, 3, &[0, 1, 2]));
+        assert_eq!(None, choose(Strategy::FirstLegal, 3, &[]));
+    }
+
+    #[test]
+    fn furthest_figure() {
+        assert_eq!(Some(2), choose(Strategy::FurthestFigure, 3, &[0, 1, 2]));
+        assert_eq!(Some(1), choose(Strategy::FurthestFigure, 3, &[0, 1]));
+    }
+
+    #[test]
+    fn aggressive() {
+        assert_eq!(Some(0), choose(Strategy::Aggressive, 3, &[1, 2, 0]));
+        assert_eq!(Some(1), choose(Strategy::Aggressive, 3, &[1, 2]));
+    }
+
+    #[test]
+    fn aggressive_spares_teammates() {
+        let teams = [Some(0), Some(0)];
+        assert_eq!(
+            Some(1),
+            Strategy::Aggressive.choose(
+                0,
+                &figures(),
+                &Board::for_players(2),
+                &teams,
+                3,
+                &[1, 2, 0]
+            )
+        );
+    }
+
+    #[test]
+    fn aggressive_on_custom_board() {
+        let board = Board {
+            fields: 40,
+            house_size: 4,
+            player_starts: vec![0, 5],
+        };
+        assert_eq!(
+            Some(1),
+            Strategy::Aggressive.choose(0, &figures(), &board, &[], 3, &[0, 1, 2])
+        );
+    }
+
+    #[test]
+    fn defensive() {
+        assert_eq!(Some(2), choose(Strategy::Defensive, 3, &[0, 1, 2]));
+        assert_eq!(Some(1), choose(Strategy::Defensive, 3, &[0, 1]));
+        assert_eq!(Some(0), choose(Strategy::Defensive, 1, &[0, 1]));
+    }
+}
@@ -0,0 +1,78 @@
+use std::{fmt::Debug, future::Future};
+
+use axum::extract::ws::Message;
+use futures::{Sink, Stream};
+use rand::Rng;
+
+use crate::{
+    statemachine::{self, GameState},
+    Game, RejoinMessage,
+};
+
+/// Hooks into a Game that is run by [`run_game_with`], which are called around every Step of the
+/// Game
+pub trait GameObserver<R, SI, ST> {
+    /// Called before every Step of the Game, which allows for changes to the Game, like adding
+    /// Spectators
+    fn before_step(&mut self, game: &mut Game<R, SI, ST>) -> impl Future<Output = ()> + Send;
+
+    /// Called after every Step of the Game with the next State, which is None once the Game is
+    /// done
+    fn after_step(&mut self, game: &Game<R, SI, ST>, state: Option<&GameState>);
+}
+
+impl<R, SI, ST> GameObserver<R, SI, ST> for () {
+    fn before_step(&mut self, _: &mut Game<R, SI, ST>) -> impl Future<Output = ()> + Send {
+        std::future::ready(())
+    }
+
+    fn after_step(&mut self, _: &Game<R, SI, ST>, _: Option<&GameState>) {}
+}
+
+/// Runs the given Game from the start of the first Turn until it is done, using the `distr` for
+/// all the Rolls.
+///
+/// Players can rejoin the Game at any Time by sending their new Connection over the `rejoin_rx`
+pub async fn run_game<R, SI, ST, D>(
+    game: &mut Game<R, SI, ST>,
+    rejoin_rx: &mut tokio::sync::mpsc::UnboundedReceiver<RejoinMessage<SI, ST>>,
+    distr: &mut D,
+) where
+    R: Rng,
+    SI: Sink<Message>,
+    <SI as futures::Sink<Message>>::Error: Debug,
+    ST: Stream<Item = Result<Message, axum::Error>>,
+    D: rand::distributions::Distribution<usize>,
+{
+    run_game_with(game, rejoin_rx, distr, &mut ()).await
+}
+
+/// Runs the given Game like [`run_game`], while informing the `observer` about every Step
+pub async fn run_game_with<R, SI, ST, D, O>(
+    game: &mut Game<R, SI, ST>,
+    rejoin_rx: &mut tokio::sync::mpsc::UnboundedReceiver<RejoinMessage<SI, ST>>,
+    distr: &mut D,
+    observer: &mut O,
+) where
+    R: Rng,
+    SI: Sink<Message>,
+    <SI as futures::Sink<Message>>::Error: Debug,
+    ST: Stream<Item = Result<Message, axum::Error>>,
+    D: rand::distributions::Distribution<usize>,
+    O: GameObserver<R, SI, ST>,
+{
+    let mut state = GameState::StartTurn { attempt: 0 };
+    loop {
+        observer.before_step(game).await;
+
+        let next = statemachine::step(state, game, rejoin_rx, distr).await;
+        observer.after_step(game, next.as_ref());
+
+        state = match next {
+            Some(s) => s,
+            None => break,
+        };
+
+        tokio::task::yield_now().await;
+    }
+}
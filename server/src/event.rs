@@ -0,0 +1,28 @@
+use serde_derive::{Deserialize, Serialize};
+
+use crate::Figure;
+
+/// A single Event that occured during a Game, which together with all the other Events can be
+/// used to replay the Game
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum GameEvent {
+    /// The Player rolled the given Value
+    Rolled { player: usize, value: usize },
+    /// The Player moved one of their Figures
+    Moved {
+        player: usize,
+        figure: usize,
+        from: Figure,
+        to: Figure,
+    },
+    /// A Figure of a Player was captured by another Player and penalized with the
+    /// [`CapturePenalty`](crate::CapturePenalty) of the Game, which either sends it back to the
+    /// Start or moves it back by some Fields
+    Captured {
+        by: usize,
+        player: usize,
+        figure: usize,
+    },
+    /// The Turn of the Player ended without them moving any Figure
+    TurnSkipped { player: usize },
+}
@@ -1,13 +1,20 @@
-use std::fmt::Debug;
+use std::{fmt::Debug, time::Duration};
 
-use axum::extract::ws::{Message, WebSocket};
+use axum::extract::ws::Message;
 use futures::{
     stream::{SplitSink, SplitStream},
-    Sink, Stream,
+    Sink, SinkExt, Stream,
 };
 use rand::{Rng, SeedableRng};
 
-use crate::{Figure, GameError, GamePlayer, GameResponse};
+use crate::{
+    Board, CapturePenalty, Figure, GameError, GameEvent, GamePlayer, GameRequest, GameResponse,
+    HouseEntry, MoveError, PlayerColor, RejoinKey, RollAuditLog, TurnDirection,
+};
+
+/// Decides whether a Player is skipped, when the Turn is passed on to them
+pub type SkipPredicate<SI, ST> =
+    Box<dyn Fn(&GamePlayer<SplitSink<SI, Message>, SplitStream<ST>>) -> bool + Send + Sync>;
 
 /// A Game Instance that stores all the relevant information/data
 pub struct Game<R, SI, ST> {
@@ -16,15 +23,174 @@ pub struct Game<R, SI, ST> {
     pub next_player: usize,
     pub rng: R,
     pub ranking: Vec<usize>,
+    /// The Spectators that receive all the public Updates of the Game, but never take a Turn
+    pub spectators: Vec<SplitSink<SI, Message>>,
+    /// The Rejoin-Codes handed out to Spectators, which let them reconnect to the Game
+    spectator_codes: Vec<uuid::Uuid>,
+    /// The Channel over which all the public Updates of the Game are published, for Observers that
+    /// are not connected directly to the Game
+    events: tokio::sync::broadcast::Sender<GameResponse>,
+    /// Whether Figures standing on one of the Start-Fields can not be captured
+    pub safe_starts: bool,
+    /// What happens to the Figures that got captured
+    pub capture_penalty: CapturePenalty,
+    /// The Team of every Player, like `Some(vec![0, 1, 0, 1])` for Players sitting across the
+    /// Board from each other playing together. Teammates can not capture each other and the Game
+    /// ends once all the Members of a Team finished
+    pub teams: Option<Vec<usize>>,
+    /// The Layout of the Board the Game is played on
+    pub board: Board,
+    /// The Rule for how Figures enter the House
+    pub house_entry: HouseEntry,
+    /// Whether two Figures of the same Player can share a Field on the Track, forming a Blockade
+    /// that can not be passed by any Figure
+    pub allow_blockades: bool,
+    /// Whether a 6 has to be used to move a Figure out of the Start, as long as there are Figures
+    /// left in the Start. Otherwise the Player can freely choose which Figure to move, once they
+    /// have Figures on the Field
+    pub six_forces_start_exit: bool,
+    /// Whether a Figure on the Start-Field of its Player has to be moved off first, as long as
+    /// there are Figures left in the Start, so that it does not block them from entering the Board
+    pub vacate_start: bool,
+    /// Whether a Player gets another Turn, after capturing a Figure of another Player
+    pub extra_turn_on_capture: bool,
+    /// The Direction in which the Turn is passed on to the next Player
+    pub direction: TurnDirection,
+    /// Which Players are skipped when passing on the Turn, in addition to the Players that are
+    /// already done. By default nobody else is skipped, as everyone can always try to roll a 6
+    pub should_skip: SkipPredicate<SI, ST>,
+    /// The maximum Number of 6s a Player can roll in a Row, where rolling one more ends their
+    /// Turn without a Move
+    pub max_sixes_in_row: Option<usize>,
+    /// Whether the Figure moved last during the Turn is sent back to the Start, when the Player
+    /// rolls more than [`Game::max_sixes_in_row`] 6s in a Row
+    pub too_many_sixes_send_home: bool,
+    /// The Number of additional Attempts a Player gets to roll a 6, while all their Figures are
+    /// stuck in the Start. A Value of `0` means that the Player only gets a single Attempt
+    pub start_attempts: usize,
+    /// The maximum Number of Turns, after which the Game is ended with the remaining Players
+    /// being ranked by the Number of Figures in their House
+    pub max_turns: Option<usize>,
+    /// The Number of Turns played so far
+    pub(crate) turns: usize,
+    /// How often every Value from 1 to 6 was rolled so far
+    roll_counts: [usize; 6],
+    /// Whether every Roll is recorded in the [`Game::roll_audit_log`], which lets anyone verify
+    /// afterwards that the Rolls were not changed
+    pub audit_rolls: bool,
+    /// The Hash-Chained Log of all the Rolls, if they are audited
+    roll_audit: RollAuditLog,
+    /// Whether the Players can start another Round once the Game is done, by all of them
+    /// requesting a Rematch
+    pub rematch: bool,
+    /// How long to wait for a Message from a Player, before sending them a Ping
+    pub ping_interval: Duration,
+    /// How long a Player can stay silent, before they are considered to be disconnected
+    pub pong_timeout: Duration,
+    /// How long to wait for a disconnected Player to rejoin, before the other Players are told
+    /// that they left. Players rejoining in this Time do not cause any Notifications
+    pub reconnect_grace: Duration,
+    /// The Player that lost their Connection, but the other Players were not told about yet
+    pub(crate) pending_leave: Option<usize>,
+    /// How long the current Player can undo their Move with [`GameRequest::Undo`], before it is
+    /// committed. Without a Window, Moves are committed right away
+    pub undo_window: Option<Duration>,
+    /// The Figures of all the Players, the Length of the History and the Figure moved during the
+    /// Turn from before the last Move, while it can still be undone
+    pub(crate) undo: Option<(Vec<[Figure; 4]>, usize, Option<usize>)>,
+    /// The Figure the current Player moved last during their current Turn, if they moved any
+    pub(crate) moved_this_turn: Option<usize>,
+    /// A Request of the current Player, that was received by the previous State but is handled by
+    /// the next one
+    pub(crate) pending_request: Option<GameRequest>,
+    /// All the Events that occured in the Game so far
+    pub(crate) history: Vec<GameEvent>,
+    /// The Sequence-Number of the latest Broadcast to the Players
+    seq: u64,
+    /// Whether the Game is currently paused, while waiting for a Player to reconnect or resume it
+    pub(crate) paused: bool,
+}
+
+/// Everything the active Player needs to know to make their next Decision
+#[derive(Debug, Clone, PartialEq)]
+pub struct TurnSummary {
+    /// The Value rolled by the Player, if they already rolled
+    pub value: Option<usize>,
+    /// The Indices of all the Figures that could be moved by the Value
+    pub legal_moves: Vec<usize>,
+    /// The Figure that has to be moved by the Rules, which is then moved without asking the
+    /// Player
+    pub forced_figure: Option<usize>,
+    /// Whether the forced Figure has to leave the Start
+    pub must_leave_start: bool,
+    /// Whether no Figure can be moved by the Value
+    pub no_moves: bool,
+}
+
+/// The Number of public Updates that are buffered for slow Subscribers of the Game
+const EVENT_CAPACITY: usize = 64;
+/// The maximum Number of Characters in a single Chat Message
+pub const MAX_CHAT_LENGTH: usize = 280;
+/// The minimum Number of Players needed to play a Game
+pub const MIN_PLAYERS: usize = 2;
+/// The maximum Number of Players supported by the default Board
+pub const MAX_PLAYERS: usize = 4;
+
+/// Checks if a Game can be played with the given Number of Players
+pub fn validate_player_count(player_count: usize) -> Result<(), GameError> {
+    if player_count < MIN_PLAYERS {
+        return Err(GameError::Protocol {
+            detail: format!("{} Players are not enough", player_count),
+        });
+    }
+    if player_count > MAX_PLAYERS {
+        return Err(GameError::Protocol {
+            detail: format!("{} Players are too many", player_count),
+        });
+    }
+
+    Ok(())
 }
 
-impl Game<rand::rngs::SmallRng, WebSocket, WebSocket> {
-    /// Creates a new Game instance with the given ID, playercount and players
-    pub fn new<IP>(id: uuid::Uuid, players: IP) -> Self
+impl<SI, ST> Game<rand::rngs::SmallRng, SI, ST>
+where
+    SI: Sink<Message>,
+    <SI as futures::Sink<Message>>::Error: Debug,
+    ST: Stream<Item = Result<Message, axum::Error>>,
+{
+    /// Creates a new Game instance with the given ID and players.
+    ///
+    /// Fails if the Game can not be played with the given Number of Players
+    pub fn new<IP>(id: uuid::Uuid, players: IP) -> Result<Self, GameError>
     where
-        IP: IntoIterator<Item = GamePlayer<SplitSink<WebSocket, Message>, SplitStream<WebSocket>>>,
+        IP: IntoIterator<Item = GamePlayer<SplitSink<SI, Message>, SplitStream<ST>>>,
     {
-        Self::new_with_rng(id, players, rand::rngs::SmallRng::from_entropy())
+        Self::validated(id, players, rand::rngs::SmallRng::from_entropy())
+    }
+
+    /// Creates a new Game instance with the given ID and players, where the starting Player and
+    /// all the Rolls are derived from the given Seed, which makes the Game reproducible.
+    ///
+    /// Fails if the Game can not be played with the given Number of Players
+    pub fn with_seed<IP>(id: uuid::Uuid, players: IP, seed: u64) -> Result<Self, GameError>
+    where
+        IP: IntoIterator<Item = GamePlayer<SplitSink<SI, Message>, SplitStream<ST>>>,
+    {
+        Self::validated(id, players, rand::rngs::SmallRng::seed_from_u64(seed))
+    }
+
+    fn validated<IP>(
+        id: uuid::Uuid,
+        players: IP,
+        rng: rand::rngs::SmallRng,
+    ) -> Result<Self, GameError>
+    where
+        IP: IntoIterator<Item = GamePlayer<SplitSink<SI, Message>, SplitStream<ST>>>,
+    {
+        let players: Vec<_> = players.into_iter().collect();
+        validate_player_count(players.len())?;
+
+        Ok(Self::new_with_rng(id, players, rng))
     }
 }
 
@@ -35,116 +201,1017 @@ where
     <SI as futures::Sink<Message>>::Error: Debug,
     ST: Stream<Item = Result<Message, axum::Error>>,
 {
-    /// Create a new Game instance with the given ID, players and rng
-    pub fn new_with_rng<IP>(id: uuid::Uuid, players: IP, rng: R) -> Self
+    /// Create a new Game instance with the given ID, players and rng, where the starting Player
+    /// is drawn from the rng.
+    ///
+    /// Unlike [`Game::new`] the Number of Players is not validated, but there has to be at least
+    /// one Player
+    pub fn new_with_rng<IP>(id: uuid::Uuid, players: IP, mut rng: R) -> Self
     where
         IP: IntoIterator<Item = GamePlayer<SplitSink<SI, Message>, SplitStream<ST>>>,
     {
-        let player_vec: Vec<_> = players.into_iter().collect();
+        let player_vec: Vec<_> = players
+            .into_iter()
+            .map(|p| {
+                let figures = p.figures.clone();
+                (p, figures)
+            })
+            .collect();
 
         let player_count = player_vec.len();
+        let next_player = rng.gen_range(0..player_count);
+
+        Self::from_state(id, player_vec, next_player, Vec::new(), rng)
+    }
+
+    /// Create a Game instance from an already existing State, where every Player is given together
+    /// with the Positions of their Figures.
+    ///
+    /// All the Players contained in the `ranking` are considered to be done already.
+    pub fn from_state<IP>(
+        id: uuid::Uuid,
+        players: IP,
+        next_player: usize,
+        ranking: Vec<usize>,
+        rng: R,
+    ) -> Self
+    where
+        IP: IntoIterator<
+            Item = (
+                GamePlayer<SplitSink<SI, Message>, SplitStream<ST>>,
+                [Figure; 4],
+            ),
+        >,
+    {
+        let player_vec: Vec<_> = players
+            .into_iter()
+            .enumerate()
+            .map(|(i, (mut player, figures))| {
+                player.figures = figures;
+                player.done = ranking.contains(&i);
+                player
+            })
+            .collect();
+
+        let board = Board::for_players(player_vec.len());
 
         Game {
             id,
             players: player_vec,
-            next_player: rand::thread_rng().gen_range(0..player_count),
+            next_player,
             rng,
-            ranking: Vec::new(),
+            ranking,
+            spectators: Vec::new(),
+            spectator_codes: Vec::new(),
+            events: tokio::sync::broadcast::channel(EVENT_CAPACITY).0,
+            safe_starts: false,
+            capture_penalty: CapturePenalty::default(),
+            teams: None,
+            board,
+            house_entry: HouseEntry::default(),
+            allow_blockades: false,
+            six_forces_start_exit: false,
+            vacate_start: true,
+            extra_turn_on_capture: false,
+            direction: TurnDirection::default(),
+            should_skip: Box::new(|_| false),
+            max_sixes_in_row: None,
+            too_many_sixes_send_home: false,
+            start_attempts: 2,
+            max_turns: None,
+            turns: 0,
+            roll_counts: [0; 6],
+            audit_rolls: false,
+            roll_audit: RollAuditLog::new(),
+            rematch: false,
+            ping_interval: Duration::from_secs(10),
+            pong_timeout: Duration::from_secs(30),
+            reconnect_grace: Duration::ZERO,
+            pending_leave: None,
+            undo_window: None,
+            undo: None,
+            moved_this_turn: None,
+            pending_request: None,
+            history: Vec::new(),
+            seq: 0,
+            paused: false,
         }
     }
 
-    /// Checks if the game or players is done
-    pub fn check_move(&mut self, player: usize) {
-        let player_figures: Vec<_> = self
-            .players
-            .get(player)
-            .unwrap()
+    /// The ID of the Game
+    pub fn id(&self) -> uuid::Uuid {
+        self.id
+    }
+
+    /// Lets the Game start with the Turn of the given Player, instead of the randomly chosen one
+    pub fn with_starting_player(mut self, index: usize) -> Self {
+        assert!(
+            index < self.players.len(),
+            "The starting Player {} does not exist",
+            index
+        );
+        self.next_player = index;
+        self
+    }
+
+    /// Publishes all the public Updates of the Game on the given Channel, which allows Observers
+    /// to subscribe before the Game is created
+    pub fn with_events(mut self, events: tokio::sync::broadcast::Sender<GameResponse>) -> Self {
+        self.events = events;
+        self
+    }
+
+    /// Subscribes to all the public Updates of the Game, which are the same ones that are sent to
+    /// the Spectators
+    pub fn subscribe(&self) -> tokio::sync::broadcast::Receiver<GameResponse> {
+        self.events.subscribe()
+    }
+
+    /// The Player whose Turn it currently is
+    pub fn active_player(&self) -> &GamePlayer<SplitSink<SI, Message>, SplitStream<ST>> {
+        &self.players[self.next_player]
+    }
+
+    /// The Player that follows the given Player in the [`TurnDirection`] of the Game, regardless
+    /// of whether they are already done
+    pub fn player_after(&self, player: usize) -> usize {
+        let count = self.players.len();
+        match self.direction {
+            TurnDirection::Clockwise => (player + 1) % count,
+            TurnDirection::CounterClockwise => (player + count - 1) % count,
+        }
+    }
+
+    /// The next Player after the given Player that gets a Turn, skipping all the Players that are
+    /// done or rejected by [`Game::should_skip`]. If every other Player is skipped, the Turn goes
+    /// back to the given Player, as long as they are not skipped as well.
+    ///
+    /// # Returns
+    /// * `Some` the Index of the next Player
+    /// * `None` if every Player, including the given one, is skipped
+    pub fn next_movable_player(&self, player: usize) -> Option<usize> {
+        let mut next = player;
+        for _ in 0..self.players.len() {
+            next = self.player_after(next);
+
+            let candidate = &self.players[next];
+            if !candidate.is_done() && !(self.should_skip)(candidate) {
+                return Some(next);
+            }
+        }
+
+        None
+    }
+
+    /// The Indices of all the Players, that lost their Connection and did not rejoin yet
+    pub fn disconnected_players(&self) -> Vec<usize> {
+        self.players
+            .iter()
+            .enumerate()
+            .filter(|(_, p)| !p.is_connected())
+            .map(|(i, _)| i)
+            .collect()
+    }
+
+    /// Whether the Game is currently paused, because it waits for a Player to reconnect or resume
+    /// the Game
+    pub fn is_paused(&self) -> bool {
+        self.paused
+    }
+
+    /// The Sequence-Number of the latest Broadcast to the Players, which increases with every
+    /// Broadcast and lets the Players notice missed Responses
+    pub fn seq(&self) -> u64 {
+        self.seq
+    }
+
+    /// The Number of Turns played so far
+    pub fn turns(&self) -> usize {
+        self.turns
+    }
+
+    /// How often every Value was rolled so far, where the first Entry counts the 1s and the last
+    /// one counts the 6s
+    pub fn roll_stats(&self) -> [usize; 6] {
+        self.roll_counts
+    }
+
+    /// The Log of all the Rolls so far, which is only recorded if [`Game::audit_rolls`] is enabled
+    pub fn roll_audit_log(&self) -> &RollAuditLog {
+        &self.roll_audit
+    }
+
+    /// Counts the given Roll of the current Player for the Statistics of the Game and records it
+    /// in the Audit-Log, if enabled.
+    ///
+    /// Values outside of 1 to 6 are not counted
+    pub(crate) fn record_roll(&mut self, value: usize) {
+        if self.audit_rolls {
+            self.roll_audit.record(self.turns, self.next_player, value);
+        }
+
+        match value
+            .checked_sub(1)
+            .and_then(|i| self.roll_counts.get_mut(i))
+        {
+            Some(count) => *count += 1,
+            None => tracing::warn!("Not counting unusual Roll {}", value),
+        };
+    }
+
+    /// All the Events that occured in the Game so far, in the Order they occured in
+    pub fn history(&self) -> &[GameEvent] {
+        &self.history
+    }
+
+    /// Finds the Index of the Player with the given Rejoin-Code
+    pub fn player_index_by_code(&self, code: uuid::Uuid) -> Option<usize> {
+        self.players.iter().position(|p| p.rejoin_code == code)
+    }
+
+    /// Finds the Index of the Player identified by the given Rejoin-Key.
+    ///
+    /// A Name only identifies a Player if no other Player in the Game has the same Name and only
+    /// while that Player is disconnected, as the Names of all the Players are public and could
+    /// otherwise be used to take over the Seat of a connected Player. For the same Reason, only
+    /// disconnected Players can be kicked.
+    pub fn player_index_by_rejoin_key(&self, key: &RejoinKey) -> Option<usize> {
+        match key {
+            RejoinKey::Code(code) => self.player_index_by_code(*code),
+            RejoinKey::Name(name) => {
+                let mut matching = self
+                    .players
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, p)| p.name == *name)
+                    .map(|(i, _)| i);
+
+                match (matching.next(), matching.next()) {
+                    (Some(index), None) if !self.players[index].is_connected() => Some(index),
+                    _ => None,
+                }
+            }
+            RejoinKey::Kick(index) => self
+                .players
+                .get(*index)
+                .is_some_and(|p| !p.is_connected())
+                .then_some(*index),
+            RejoinKey::Spectate => None,
+        }
+    }
+
+    /// Calculates the absolute Position on the Board for a Figure of the given Player, that has
+    /// moved the given Number of Fields from its Start-Field
+    pub fn absolute_position(&self, player: usize, moved: usize) -> usize {
+        self.board.absolute_position(player, moved)
+    }
+
+    /// Calculates the absolute Position of the given Figure of a Player on the shared Track of the
+    /// Board.
+    ///
+    /// # Returns
+    /// * `Some` the absolute Position, if the Figure is on the shared Track
+    /// * `None` if the Figure is still in the Start or already in the House
+    pub fn field_position(&self, player: usize, figure: &Figure) -> Option<usize> {
+        match figure {
+            Figure::OnField { moved } if *moved < self.board.fields => {
+                Some(self.absolute_position(player, *moved))
+            }
+            _ => None,
+        }
+    }
+
+    /// Summarizes the current Decision of the active Player, after rolling the given Value.
+    ///
+    /// A Figure on the Start-Field may have to make room for the Figures still in the Start (see
+    /// [`Game::must_vacate_start`]), otherwise a 6 has to be used to move a Figure out of the
+    /// Start, if the Rules demand it or there is no other Figure to move. Without a Value, the
+    /// Player still has to roll and there are no Moves yet
+    pub fn turn_summary(&self, value: Option<usize>) -> TurnSummary {
+        let value = match value {
+            Some(v) => v,
+            None => {
+                return TurnSummary {
+                    value: None,
+                    legal_moves: Vec::new(),
+                    forced_figure: None,
+                    must_leave_start: false,
+                    no_moves: false,
+                }
+            }
+        };
+
+        let player = &self.players[self.next_player];
+        let blockades = self.blockades_for(self.next_player);
+        let legal_moves =
+            player.legal_moves(value, &self.board, self.house_entry, blockades.as_deref());
+
+        // Both only apply if the Move is possible at all, so that a blocked Start-Field never
+        // leads to two Figures sharing it
+        let start_field_figure = self.must_vacate_start(self.next_player);
+        let start_exit_figure = player
             .figures
             .iter()
-            .filter_map(|f| match f {
-                Figure::OnField { moved } => Some((*moved + player * 10) % 40),
-                _ => None,
+            .position(|f| f == &Figure::InStart)
+            .filter(|_| {
+                value == 6 && (self.six_forces_start_exit || !player.has_figures_on_field())
+            });
+        let forced_figure = start_field_figure
+            .or(start_exit_figure)
+            .filter(|i| legal_moves.contains(i));
+
+        TurnSummary {
+            value: Some(value),
+            no_moves: legal_moves.is_empty(),
+            must_leave_start: start_field_figure.is_none() && forced_figure.is_some(),
+            forced_figure,
+            legal_moves,
+        }
+    }
+
+    /// The Figure of the given Player that has to be moved off their Start-Field first, because it
+    /// blocks the Figures still in the Start from entering the Board.
+    ///
+    /// # Returns
+    /// * `Some` the Index of the Figure on the Start-Field, if the Game demands vacating it
+    /// * `None` if the Rule is disabled, the Start-Field is empty or there are no Figures left in
+    ///   the Start
+    pub fn must_vacate_start(&self, player: usize) -> Option<usize> {
+        if !self.vacate_start {
+            return None;
+        }
+
+        let player = &self.players[player];
+        if !player.has_figures_in_start() {
+            return None;
+        }
+
+        player
+            .figures
+            .iter()
+            .position(|f| f == &Figure::OnField { moved: 0 })
+    }
+
+    /// The absolute Positions of all the Fields on the shared Track, that are blocked by two
+    /// Figures of the same Player
+    pub fn blockades(&self) -> Vec<usize> {
+        let mut blockades: Vec<_> = self
+            .players
+            .iter()
+            .enumerate()
+            .flat_map(|(pindex, p)| {
+                let positions: Vec<_> = p
+                    .figures
+                    .iter()
+                    .filter_map(|f| self.field_position(pindex, f))
+                    .collect();
+
+                positions
+                    .iter()
+                    .enumerate()
+                    .filter(|(i, pos)| positions[..*i].contains(pos))
+                    .map(|(_, pos)| *pos)
+                    .collect::<Vec<_>>()
             })
             .collect();
+        blockades.sort_unstable();
+        blockades.dedup();
+
+        blockades
+    }
 
-        tracing::trace!("Current Player Figure Positions {:?}", player_figures);
+    /// The Blockades as seen by the given Player, counted from their Start-Field, or None if
+    /// Blockades are not allowed in the Game
+    pub(crate) fn blockades_for(&self, player: usize) -> Option<Vec<usize>> {
+        if !self.allow_blockades {
+            return None;
+        }
 
-        for (pindex, player) in self
-            .players
-            .iter_mut()
+        Some(
+            self.blockades()
+                .into_iter()
+                .map(|abs| self.board.relative_position(player, abs))
+                .collect(),
+        )
+    }
+
+    /// Captures all the Figures of other Players, that share the Field the given Figure of the
+    /// Player just landed on, by applying the [`CapturePenalty`] to them.
+    ///
+    /// Only Figures on the shared Track are considered, Figures in the Start or House can neither
+    /// capture nor be captured.
+    ///
+    /// # Returns
+    /// The Player and Figure Indices of all the captured Figures
+    pub fn check_move(&mut self, player: usize, figure: usize) -> Vec<(usize, usize)> {
+        let captures = match self.players[player].figures.get(figure) {
+            Some(f) => self.captures_at(player, f),
+            None => return Vec::new(),
+        };
+
+        for (pindex, findex) in captures.iter().copied() {
+            self.penalize(pindex, findex);
+        }
+
+        captures
+    }
+
+    /// Applies the [`CapturePenalty`] to the given Figure of the Player, that just got captured
+    pub(crate) fn penalize(&mut self, player: usize, figure: usize) {
+        let victim = &mut self.players[player];
+        let penalized = match (self.capture_penalty, &victim.figures[figure]) {
+            (CapturePenalty::BackSteps(steps), Figure::OnField { moved }) => moved
+                .checked_sub(steps)
+                .map(|moved| Figure::OnField { moved })
+                .filter(|f| !victim.figures.contains(f))
+                .unwrap_or(Figure::InStart),
+            _ => Figure::InStart,
+        };
+        tracing::trace!(
+            "Figure {:?} of Player {} is moved to {:?}",
+            figure,
+            player,
+            penalized
+        );
+        victim.figures[figure] = penalized;
+    }
+
+    /// The Player and Figure Indices of all the Figures of other Players, that would be captured by
+    /// a Figure of the given Player standing at the given Position, without capturing them
+    pub fn captures_at(&self, player: usize, figure: &Figure) -> Vec<(usize, usize)> {
+        let target = match self.field_position(player, figure) {
+            Some(t) => t,
+            None => return Vec::new(),
+        };
+
+        tracing::trace!("Figure of Player {} landed on {}", player, target);
+
+        self.players
+            .iter()
             .enumerate()
-            .filter(|(i, _)| *i != player)
-        {
-            for fig in player.figures.iter_mut() {
-                let pos = match fig {
-                    Figure::OnField { moved } => (*moved + pindex * 10) % 40,
-                    _ => continue,
-                };
+            .filter(|(i, _)| *i != player && !self.are_teammates(player, *i))
+            .filter_map(|(pindex, p)| {
+                let findex = p.figure_at_absolute(
+                    target,
+                    self.board.player_starts[pindex],
+                    self.board.fields,
+                )?;
 
-                if player_figures.contains(&pos) {
-                    *fig = Figure::InStart;
-                    tracing::trace!("Figure {:?} of Player {} is done", fig, pindex);
+                if self.safe_starts && self.board.is_start_field(target) {
+                    tracing::trace!("Figure {} of Player {} is on a safe Field", findex, pindex);
+                    return None;
                 }
-            }
+
+                Some((pindex, findex))
+            })
+            .collect()
+    }
+
+    /// The Indices of all the Figures of the current Player, that could legally be moved onto the
+    /// given absolute Position of the shared Track with the rolled Value
+    pub fn figures_moving_to(&self, absolute: usize, value: usize) -> Vec<usize> {
+        (0..self.players[self.next_player].figures.len())
+            .filter(|figure| {
+                self.preview_move(*figure, value).is_ok_and(|(to, _)| {
+                    self.field_position(self.next_player, &to) == Some(absolute)
+                })
+            })
+            .collect()
+    }
+
+    /// Where the given Figure of the current Player would end up for the rolled Value and which
+    /// Figures it would capture there, without changing anything
+    pub fn preview_move(
+        &self,
+        figure: usize,
+        value: usize,
+    ) -> Result<(Figure, Vec<(usize, usize)>), MoveError> {
+        let blockades = self.blockades_for(self.next_player);
+        let to = self.players[self.next_player].preview_move(
+            figure,
+            value,
+            &self.board,
+            self.house_entry,
+            blockades.as_deref(),
+        )?;
+        let captures = self.captures_at(self.next_player, &to);
+
+        Ok((to, captures))
+    }
+
+    /// Informs all the Players and Spectators about the Figures captured by the given Player
+    pub async fn send_captures(&mut self, by: usize, captures: &[(usize, usize)]) {
+        for (player, figure) in captures.iter().copied() {
+            let resp = GameResponse::Captured { by, player, figure };
+
+            // Players that can not be reached will be noticed when sending the State afterwards
+            let _ = self.broadcast(&resp).await;
+            self.send_spectators(&resp).await;
         }
     }
 
-    /// Sends all the Rejoin-Codes for the Players to them
+    /// Sends all the Rejoin-Codes for the Players to them.
+    ///
+    /// A failed send does not stop the Codes from being sent to the remaining Players, but the
+    /// Error for the last Player that could not be reached is still returned afterwards.
     pub async fn send_rejoin_codes(&mut self) -> Result<(), GameError> {
-        for player in self.players.iter_mut() {
+        let mut result = Ok(());
+        for (index, player) in self.players.iter_mut().enumerate() {
             let msg = GameResponse::RejoinCode {
                 game: self.id,
                 code: player.rejoin_code,
             };
 
-            player.send_resp(&msg).await?;
+            if let Err(e) = player.send_resp(&msg).await {
+                result = Err(Self::send_error(index, e));
+            }
         }
 
-        Ok(())
+        result
     }
 
-    /// Sends the new State to the Players of the Game
-    pub async fn send_state(&mut self) -> Result<(), GameError> {
-        let state = GameResponse::State {
+    /// Sends the given Response to all the Players of the Game, with the next Sequence-Number.
+    ///
+    /// A failed send does not stop the Response from being sent to the remaining Players, but the
+    /// Error for the last Player that could not be reached is still returned afterwards.
+    pub async fn broadcast(&mut self, resp: &GameResponse) -> Result<(), GameError> {
+        self.seq += 1;
+
+        let mut result = Ok(());
+        for (index, player) in self.players.iter_mut().enumerate() {
+            if let Err(e) = player.send_sequenced(resp, self.seq).await {
+                tracing::warn!("Could not send Response to Player {}", index);
+                result = Err(Self::send_error(index, e));
+            }
+        }
+
+        result
+    }
+
+    /// Attaches the Player to the Error of sending them a Response, if they could not be reached
+    fn send_error(player: usize, err: GameError) -> GameError {
+        match err {
+            GameError::Disconnect => GameError::Send { player },
+            other => other,
+        }
+    }
+
+    /// The current State of the Game, as it is sent to the Players
+    pub fn state(&self) -> GameResponse {
+        GameResponse::State {
             players: self
                 .players
                 .iter()
                 .map(|p| (p.name.clone(), p.figures.clone()))
                 .collect(),
+            positions: self
+                .players
+                .iter()
+                .enumerate()
+                .map(|(i, p)| {
+                    let mut positions = [None; 4];
+                    for (position, figure) in positions.iter_mut().zip(p.figures.iter()) {
+                        *position = self.field_position(i, figure);
+                    }
+                    positions
+                })
+                .collect(),
+            remaining: self
+                .players
+                .iter()
+                .map(|p| p.remaining_distance(&self.board))
+                .collect(),
+        }
+    }
+
+    /// The current Board of the Game, where every Figure is placed at its absolute Position
+    pub fn board(&self) -> GameResponse {
+        let mut cells = vec![None; self.board.fields];
+        let mut houses = vec![vec![None; self.board.house_size]; self.players.len()];
+        let mut starts = vec![Vec::new(); self.players.len()];
+
+        for (pindex, player) in self.players.iter().enumerate() {
+            for (findex, figure) in player.figures.iter().enumerate() {
+                match figure {
+                    Figure::InStart => starts[pindex].push(findex),
+                    Figure::InHouse { pos } => houses[pindex][*pos] = Some(findex),
+                    Figure::OnField { .. } => {
+                        if let Some(pos) = self.field_position(pindex, figure) {
+                            cells[pos] = Some((pindex, findex));
+                        }
+                    }
+                };
+            }
+        }
+
+        GameResponse::Board {
+            cells,
+            houses,
+            starts,
+        }
+    }
+
+    /// Renders the Board as Text, which is mostly useful for Debugging.
+    ///
+    /// Every Figure is shown as the Letter of its Player followed by its Index, like `A0` or `B2`,
+    /// and empty Positions are shown as `..`. The first Line contains all the Fields of the shared
+    /// Track at their absolute Positions, followed by a Line for the House and Start of every
+    /// Player. On the Track, only the first Figure of a Blockade is shown
+    pub fn to_ascii_board(&self) -> String {
+        let letter = |player: usize| char::from(b'A' + (player % 26) as u8);
+        let marker = |player: usize, figure: usize| format!("{}{}", letter(player), figure);
+
+        let mut cells = vec![None; self.board.fields];
+        for (pindex, player) in self.players.iter().enumerate() {
+            for (findex, figure) in player.figures.iter().enumerate() {
+                if let Some(pos) = self.field_position(pindex, figure) {
+                    cells[pos].get_or_insert_with(|| marker(pindex, findex));
+                }
+            }
+        }
+
+        let mut lines = vec![format!(
+            "Track:{}",
+            cells
+                .iter()
+                .map(|c| format!(" {}", c.as_deref().unwrap_or("..")))
+                .collect::<String>()
+        )];
+        for (pindex, player) in self.players.iter().enumerate() {
+            let mut house = vec![None; self.board.house_size];
+            let mut start = Vec::new();
+            for (findex, figure) in player.figures.iter().enumerate() {
+                match figure {
+                    Figure::InHouse { pos } => house[*pos] = Some(marker(pindex, findex)),
+                    Figure::InStart => start.push(marker(pindex, findex)),
+                    Figure::OnField { .. } => {}
+                };
+            }
+
+            lines.push(format!(
+                "House {}:{}",
+                letter(pindex),
+                house
+                    .iter()
+                    .map(|h| format!(" {}", h.as_deref().unwrap_or("..")))
+                    .collect::<String>()
+            ));
+            lines.push(format!(
+                "Start {}:{}",
+                letter(pindex),
+                start.iter().map(|s| format!(" {}", s)).collect::<String>()
+            ));
+        }
+
+        lines.join("\n")
+    }
+
+    /// Sends the new State and Board to the Players of the Game
+    pub async fn send_state(&mut self) -> Result<(), GameError> {
+        let mut result = Ok(());
+        for resp in [self.state(), self.board()] {
+            if let Err(e) = self.broadcast(&resp).await {
+                result = Err(e);
+            }
+            self.send_spectators(&resp).await;
+        }
+
+        result
+    }
+
+    /// Adds a new Spectator to the Game and sends them the current State and Board of the Game,
+    /// followed by a Rejoin-Code that lets them reconnect later on.
+    ///
+    /// Returns the Rejoin-Code of the Spectator or None if they could not be reached
+    pub async fn add_spectator(&mut self, sink: SplitSink<SI, Message>) -> Option<uuid::Uuid> {
+        let code = uuid::Uuid::new_v4();
+        let rejoin = GameResponse::RejoinCode {
+            game: self.id,
+            code,
         };
-        for player in self.players.iter_mut() {
-            player.send_resp(&state).await?;
+
+        self.watch(sink, Some(rejoin)).await?;
+        self.spectator_codes.push(code);
+
+        Some(code)
+    }
+
+    /// Reconnects a Spectator with the given Rejoin-Code and sends them the current State of the
+    /// Game.
+    ///
+    /// Returns false if the Code does not belong to a Spectator of the Game
+    pub async fn rejoin_spectator(
+        &mut self,
+        code: uuid::Uuid,
+        sink: SplitSink<SI, Message>,
+    ) -> bool {
+        if !self.spectator_codes.contains(&code) {
+            tracing::warn!("Unknown Spectator Code {:?}", code);
+            return false;
+        }
+
+        self.watch(sink, None).await.is_some()
+    }
+
+    /// The Rejoin-Codes of all the Spectators of the Game
+    pub fn spectator_codes(&self) -> &[uuid::Uuid] {
+        &self.spectator_codes
+    }
+
+    /// Sends the current State, Board and active Player to the Sink, followed by the optional
+    /// extra Response, and adds it to the Spectators
+    async fn watch(
+        &mut self,
+        mut sink: SplitSink<SI, Message>,
+        extra: Option<GameResponse>,
+    ) -> Option<()> {
+        let active = GameResponse::ActivePlayer {
+            player: self.next_player,
+        };
+
+        for resp in [self.state(), self.board(), active]
+            .into_iter()
+            .chain(extra)
+        {
+            let content = serde_json::to_string(&resp)
+                .expect("Serializing a Response to send should always work as the Fromat is known");
+            if let Err(e) = sink.send(Message::Text(content)).await {
+                tracing::warn!("Could not add Spectator: {:?}", e);
+                return None;
+            }
+        }
+
+        self.spectators.push(sink);
+        Some(())
+    }
+
+    /// Sends the given Response to all the Spectators of the Game and publishes it to all the
+    /// Subscribers.
+    ///
+    /// Spectators that can not be reached anymore are removed from the Game
+    pub async fn send_spectators(&mut self, resp: &GameResponse) {
+        // Publishing only fails if there is currently no Subscriber
+        let _ = self.events.send(resp.clone());
+
+        if self.spectators.is_empty() {
+            return;
+        }
+
+        let content = serde_json::to_string(resp)
+            .expect("Serializing a Response to send should always work as the Fromat is known");
+
+        let mut connected = Vec::with_capacity(self.spectators.len());
+        for mut spectator in self.spectators.drain(..) {
+            match spectator.send(Message::Text(content.clone())).await {
+                Ok(_) => connected.push(spectator),
+                Err(e) => {
+                    tracing::debug!("Removing Spectator: {:?}", e);
+                }
+            };
+        }
+        self.spectators = connected;
+    }
+
+    /// Sends a Chat Message from the given Player to all the Players and Spectators.
+    ///
+    /// Messages longer than [`MAX_CHAT_LENGTH`] are rejected and only the sending Player is
+    /// informed about it.
+    pub async fn chat(&mut self, player: usize, text: String) {
+        if text.chars().count() > MAX_CHAT_LENGTH {
+            tracing::debug!("Rejecting Chat Message from Player {}", player);
+
+            let resp = GameResponse::InvalidRequest {
+                reason: format!(
+                    "Chat Messages can be at most {} Characters",
+                    MAX_CHAT_LENGTH
+                ),
+            };
+            let _ = self.players[player].send_resp(&resp).await;
+            return;
+        }
+
+        let resp = GameResponse::Chat {
+            player,
+            name: self.players[player].name.clone(),
+            text,
+        };
+
+        // Players that can not be reached will be noticed during the normal Game Flow
+        let _ = self.broadcast(&resp).await;
+        self.send_spectators(&resp).await;
+    }
+
+    /// All the Players of the Game, as they are sent to a Player asking for them
+    pub fn roster(&self) -> GameResponse {
+        GameResponse::Roster {
+            players: self
+                .players
+                .iter()
+                .enumerate()
+                .map(|(i, p)| (i, p.name.clone(), p.is_connected()))
+                .collect(),
+        }
+    }
+
+    /// Sends the current State and the Indications of all the Players to only the given Player,
+    /// together with the latest Sequence-Number
+    pub async fn resync_player(&mut self, player: usize) -> Result<(), GameError> {
+        let mut resps = vec![self.state(), self.board()];
+        resps.extend(
+            self.players
+                .iter()
+                .enumerate()
+                .map(|(i, p)| GameResponse::IndicatePlayer {
+                    player: i,
+                    name: p.name.clone(),
+                    you: i == player,
+                    team: self.team_of(i),
+                    color: PlayerColor::for_seat(i),
+                }),
+        );
+
+        // The Responses carry the latest Sequence-Number, so the Player knows they are up to date
+        let target = &mut self.players[player];
+        for resp in resps.iter() {
+            target
+                .send_sequenced(resp, self.seq)
+                .await
+                .map_err(|e| Self::send_error(player, e))?;
         }
 
         Ok(())
     }
 
-    /// Indicate the Players
+    /// Indicate the Players, where every Player receives all the Indications in a single Batch
     pub async fn indicate_players(&mut self) -> Result<(), GameError> {
         let indications: Vec<_> = self
             .players
             .iter()
             .enumerate()
-            .map(|(i, player)| (i, player.name.clone()))
+            .map(|(i, player)| (i, player.name.clone(), self.team_of(i)))
             .collect();
 
+        let mut result = Ok(());
         for (index, player) in self.players.iter_mut().enumerate() {
-            for (indic_index, indic_name) in indications.iter() {
-                let resp = GameResponse::IndicatePlayer {
-                    player: *indic_index,
-                    name: indic_name.clone(),
-                    you: *indic_index == index,
-                };
+            let resps = indications
+                .iter()
+                .map(
+                    |(indic_index, indic_name, indic_team)| GameResponse::IndicatePlayer {
+                        player: *indic_index,
+                        name: indic_name.clone(),
+                        you: *indic_index == index,
+                        team: *indic_team,
+                        color: PlayerColor::for_seat(*indic_index),
+                    },
+                )
+                .collect();
 
-                player.send_resp(&resp).await?;
+            if let Err(e) = player.send_batch(resps).await {
+                result = Err(Self::send_error(index, e));
             }
         }
 
-        Ok(())
+        result
+    }
+
+    /// Computes the Ranking of all the Players, even if the Game is not done yet.
+    ///
+    /// The Players that are already done keep their Place from the current Ranking, all the
+    /// remaining Players are ordered by the Number of Figures in their House and then by the total
+    /// Progress of their Figures. Players that are still tied after that are ranked by their Seat,
+    /// so the lower Seat always places higher and the Ranking is deterministic.
+    pub fn compute_ranking(&self) -> Vec<usize> {
+        let progress = |player: &GamePlayer<_, _>| -> usize {
+            player
+                .figures
+                .iter()
+                .map(|f| match f {
+                    Figure::InStart => 0,
+                    Figure::OnField { moved } => *moved,
+                    Figure::InHouse { pos } => self.board.fields + pos,
+                })
+                .sum()
+        };
+
+        let mut remaining: Vec<_> = (0..self.players.len())
+            .filter(|i| !self.ranking.contains(i))
+            .collect();
+        remaining.sort_by_key(|i| {
+            let player = &self.players[*i];
+            (
+                std::cmp::Reverse((player.figures_in_house(), progress(player))),
+                *i,
+            )
+        });
+
+        self.ranking.iter().copied().chain(remaining).collect()
+    }
+
+    /// The Team of the given Player, if the Game is played in Teams
+    pub fn team_of(&self, player: usize) -> Option<usize> {
+        self.teams.as_ref()?.get(player).copied()
+    }
+
+    /// Whether the two Players are different Members of the same Team
+    pub fn are_teammates(&self, first: usize, second: usize) -> bool {
+        first != second
+            && self
+                .team_of(first)
+                .is_some_and(|team| self.team_of(second) == Some(team))
+    }
+
+    /// The first Team, of which all the Members already finished. Players without an Entry in the
+    /// [`Game::teams`] do not belong to any Team
+    pub fn finished_team(&self) -> Option<usize> {
+        let teams = self.teams.as_ref()?;
+        self.ranking
+            .iter()
+            .filter_map(|p| teams.get(*p).copied())
+            .find(|team| {
+                teams
+                    .iter()
+                    .zip(self.players.iter())
+                    .filter(|(t, _)| *t == team)
+                    .all(|(_, p)| p.is_done())
+            })
+    }
+
+    /// Checks that the Positions of all the Figures are consistent with the Rules, which catches
+    /// Mistakes in the Rules early.
+    ///
+    /// # Panics
+    /// If a Figure is outside of the Track or House, or if two Figures of the same Player share a
+    /// Position, which is only allowed on the Track when Blockades are allowed
+    pub fn assert_invariants(&self) {
+        for (pindex, player) in self.players.iter().enumerate() {
+            for (findex, figure) in player.figures.iter().enumerate() {
+                match figure {
+                    Figure::OnField { moved } => assert!(
+                        *moved < self.board.fields,
+                        "Figure {} of Player {} moved {} Fields on a Track with {} Fields",
+                        findex,
+                        pindex,
+                        moved,
+                        self.board.fields
+                    ),
+                    Figure::InHouse { pos } => assert!(
+                        *pos < self.board.house_size,
+                        "Figure {} of Player {} is at Position {} in a House of Size {}",
+                        findex,
+                        pindex,
+                        pos,
+                        self.board.house_size
+                    ),
+                    Figure::InStart => continue,
+                }
+
+                let stacking = self.allow_blockades && matches!(figure, Figure::OnField { .. });
+                if let Some(other) = player.figures[..findex].iter().position(|f| f == figure) {
+                    assert!(
+                        stacking,
+                        "Figures {} and {} of Player {} are both at {:?}",
+                        other, findex, pindex, figure
+                    );
+                }
+            }
+        }
     }
 
     /// Check if the Game is done
     pub fn is_done(&self) -> bool {
         self.players.iter().all(|p| p.done)
     }
+
+    /// Resets the Game to its initial State, so the same Players can play another Round.
+    ///
+    /// All the Figures are moved back to the Start, everything tracked about the previous Round,
+    /// including the Sequence-Numbers, is cleared and the starting Player is drawn from the rng
+    /// again, while the Names, Connections and Rejoin-Codes of the Players are kept
+    pub fn reset(&mut self) {
+        for player in self.players.iter_mut() {
+            player.figures = [
+                Figure::InStart,
+                Figure::InStart,
+                Figure::InStart,
+                Figure::InStart,
+            ];
+            player.done = false;
+            player.rolls.clear();
+            player.consecutive_sixes = 0;
+            player.queued.clear();
+        }
+
+        self.ranking.clear();
+        self.history.clear();
+        self.turns = 0;
+        self.moved_this_turn = None;
+        self.roll_counts = [0; 6];
+        self.roll_audit.clear();
+        self.seq = 0;
+        self.paused = false;
+        self.pending_leave = None;
+        self.undo = None;
+        self.pending_request = None;
+        self.next_player = self.rng.gen_range(0..self.players.len());
+    }
 }
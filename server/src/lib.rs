@@ -6,27 +6,177 @@ use axum::extract::ws::Message;
 use futures::stream::{SplitSink, SplitStream};
 use serde_derive::{Deserialize, Serialize};
 
+pub mod bot;
+pub mod lobby;
 pub mod statemachine;
 
+mod board;
+pub use board::Board;
+
 mod game;
-pub use game::Game;
+pub use game::{
+    validate_player_count, Game, TurnSummary, MAX_CHAT_LENGTH, MAX_PLAYERS, MIN_PLAYERS,
+};
 
 mod player;
-pub use player::GamePlayer;
+pub use player::{simulate_move, GamePlayer, ROLL_HISTORY_SIZE};
+
+mod event;
+pub use event::GameEvent;
+
+mod audit;
+pub use audit::{RollAuditEntry, RollAuditLog, GENESIS_HASH};
+
+mod replay;
+pub use replay::ReplayError;
+
+mod registry;
+pub use registry::{RegistryWrite, RejoinEntry, RejoinRegistry, MAX_ENTRY_AGE};
 
-pub type RejoinMessage<SI, ST> = (uuid::Uuid, (SplitSink<SI, Message>, SplitStream<ST>));
+mod driver;
+pub use driver::{run_game, run_game_with, GameObserver};
+
+#[cfg(feature = "schema")]
+pub mod schema;
+
+mod snapshot;
+pub use snapshot::{GameSnapshot, PlayerSnapshot, PlayerView};
+
+pub type RejoinMessage<SI, ST> = (RejoinKey, (SplitSink<SI, Message>, SplitStream<ST>));
+
+/// The Key used by a Player to rejoin a running Game
+#[derive(Debug, Clone, PartialEq)]
+pub enum RejoinKey {
+    /// The Rejoin-Code that was sent to the Player
+    Code(uuid::Uuid),
+    /// The Name of the Player, which only works if no other Player has the same Name and the
+    /// Player is disconnected
+    Name(String),
+    /// The Index of a disconnected Player that was kicked from the Game, whose Seat is taken over
+    /// by the new Connection, like a Bot replacing the Player
+    Kick(usize),
+    /// A new Spectator, whose Connection is added to the Spectators of the Game instead of taking
+    /// over the Seat of a Player. Spectators reconnect with the [`RejoinKey::Code`] they received
+    Spectate,
+}
 
 /// The Errors that could be returned while running a Game
 #[derive(Debug, PartialEq)]
 pub enum GameError {
     /// A Player disconnected
     Disconnect,
-    /// Other Errors occured
-    Other(&'static str),
+    /// A Response could not be serialized to be sent
+    Serialization,
+    /// A Response could not be sent to the given Player, who is now considered to be disconnected
+    Send { player: usize },
+    /// Something did not match what the Protocol or the Rules of the Game expect
+    Protocol { detail: String },
+}
+
+/// The Reasons for why a Figure could not be moved
+#[derive(Debug, PartialEq)]
+pub enum MoveError {
+    /// The Player has no Figure with the given Index
+    UnknownFigure,
+    /// The Figure is still in the Start and can not be moved normally
+    InStart,
+    /// The Figure would move past the end of the House
+    Overshoot,
+    /// The Target is already occupied by another Figure of the same Player
+    Occupied,
+    /// The Figure would not land exactly on the last free Position of the House
+    Inexact,
+    /// The Figure would jump over another Figure in the House
+    Blocked,
+    /// The Figure would pass or land on a Field blocked by two Figures of the same Player
+    Blockade,
+}
+
+/// The Rule for how Figures enter the House at the end of the Track
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum HouseEntry {
+    /// Figures can enter the House on any free Position, but can not move past its end
+    #[default]
+    Loose,
+    /// Figures can only enter the House by landing exactly on its last free Position
+    Exact,
+    /// Figures that would move past the end of the House bounce back by the remaining Fields
+    Bounce,
+}
+
+/// The Direction in which the Turn is passed on to the next Player
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum TurnDirection {
+    /// The Turn is passed on to the Player with the next higher Index
+    #[default]
+    Clockwise,
+    /// The Turn is passed on to the Player with the next lower Index
+    CounterClockwise,
+}
+
+/// What happens to a Figure, that got captured by another Player
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum CapturePenalty {
+    /// The Figure is sent back to the Start
+    #[default]
+    ToStart,
+    /// The Figure is moved back by the given Number of Fields and only returns to the Start, if it
+    /// would move back past its Start-Field or onto another of its own Figures
+    BackSteps(usize),
+}
+
+/// The Color of a Player, which is assigned by the Seat of the Player, so that all the Clients
+/// show the same Colors
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub enum PlayerColor {
+    Red,
+    Blue,
+    Green,
+    Yellow,
+    Purple,
+    Orange,
+    Black,
+    White,
+}
+
+impl PlayerColor {
+    /// All the Colors in the Order they are assigned to the Seats
+    pub const PALETTE: [PlayerColor; 8] = [
+        PlayerColor::Red,
+        PlayerColor::Blue,
+        PlayerColor::Green,
+        PlayerColor::Yellow,
+        PlayerColor::Purple,
+        PlayerColor::Orange,
+        PlayerColor::Black,
+        PlayerColor::White,
+    ];
+
+    /// The Color of the Player on the given Seat, where the Palette starts over once every Color
+    /// was assigned
+    pub fn for_seat(seat: usize) -> Self {
+        Self::PALETTE[seat % Self::PALETTE.len()]
+    }
+}
+
+/// The Reason for why a Game ended
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub enum GameEndReason {
+    /// All the Players finished with all their Figures in the House
+    AllFinished,
+    /// The maximum Number of Turns was reached, before all the Players finished
+    TurnCapReached,
+    /// The Game could not be continued and was stopped early
+    Aborted,
+    /// All the Members of a Team finished, so their Team won the Game
+    TeamFinished,
 }
 
 /// A Figure of a Player
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub enum Figure {
     /// The Figure is still on the Start Field
     InStart,
@@ -39,13 +189,64 @@ pub enum Figure {
     InHouse { pos: usize },
 }
 
+/// The Requests sent by a Player to the Server.
+///
+/// Every Request is serialized as a JSON-Object with its Variant in the `type` Field and all its
+/// other Fields next to it, like `{"type":"Roll"}` or `{"type":"Move","figure":2}`
 #[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(tag = "type")]
 pub enum GameRequest {
+    Ready,
+    /// Changes the Name of the Player, which is only possible before the Game started
+    SetName {
+        name: String,
+    },
     Roll,
-    Move { figure: usize },
+    Move {
+        figure: usize,
+    },
+    /// Reverts the Move made last by the Player, while the Undo-Window of the Game is still open
+    Undo,
+    /// Moves the single Figure, that would land on the given absolute Field of the shared Track
+    /// with the current Roll
+    MoveTo {
+        absolute: usize,
+    },
+    /// Asks where the Figure would end up for the current Roll, without actually moving it
+    PreviewMove {
+        figure: usize,
+    },
+    Chat {
+        text: String,
+    },
+    RequestState,
+    RollHistory,
+    /// Asks for the Rejoin-Code of the Player again, in case they lost it
+    GetRejoinCode,
+    /// Asks for all the Players of the Game, for example after missing their Indications
+    ListPlayers,
+    Rematch,
+    Pause,
+    Resume,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+/// A Response together with the Sequence-Number of the Broadcast it belongs to, which is
+/// serialized as the Response with an additional `seq` Field, like `{"seq":3,"type":"Turn"}`
+#[derive(Debug, Serialize)]
+pub struct Sequenced<'a> {
+    pub seq: u64,
+    #[serde(flatten)]
+    pub resp: &'a GameResponse,
+}
+
+/// The Responses sent by the Server to the Players and Spectators.
+///
+/// Every Response is serialized as a JSON-Object with its Variant in the `type` Field and all its
+/// other Fields next to it, like `{"type":"Turn"}` or `{"type":"Rolled","value":6,"can_move":true}`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(tag = "type")]
 pub enum GameResponse {
     RejoinCode {
         game: uuid::Uuid,
@@ -55,19 +256,116 @@ pub enum GameResponse {
         player: usize,
         name: String,
         you: bool,
+        /// The Team of the Player, if the Game is played in Teams
+        team: Option<usize>,
+        /// The Color of the Player, which is determined by their Seat
+        color: PlayerColor,
     },
+    AllReady,
     State {
         players: Vec<(String, [Figure; 4])>,
+        /// The absolute Position of every Figure on the shared Track, in the same Order as the
+        /// `players`, where Figures in the Start or House have no Position
+        positions: Vec<[Option<usize>; 4]>,
+        /// The Number of Steps every Player still needs to move, until all their Figures are in
+        /// the House, in the same Order as the `players`
+        remaining: Vec<usize>,
+    },
+    /// The Board with all the Figures at their absolute Positions.
+    ///
+    /// Every Cell of the shared Track contains the Player and Figure on it, every House contains
+    /// the Figures of its Player at their Position and every Start lists the Figures still in it
+    Board {
+        cells: Vec<Option<(usize, usize)>>,
+        houses: Vec<Vec<Option<usize>>>,
+        starts: Vec<Vec<usize>>,
     },
     Turn,
+    ActivePlayer {
+        player: usize,
+    },
     Rolled {
         value: usize,
         can_move: bool,
+        /// How many more Attempts the Player has to roll a 6, while all their Figures are stuck in
+        /// the Start. Left out otherwise, so the Response stays as documented
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        attempts_left: Option<usize>,
+    },
+    MoveOptions {
+        value: usize,
+        figures: Vec<usize>,
+    },
+    /// A Figure of a Player was captured by another Player and penalized with the
+    /// [`CapturePenalty`] of the Game, so its new Position is only known from the next State
+    Captured {
+        by: usize,
+        player: usize,
+        figure: usize,
+    },
+    ChooseAnotherFigure {
+        value: usize,
+    },
+    /// Where the previewed Figure would end up and which Figures of other Players it would
+    /// capture there
+    MovePreview {
+        to: Figure,
+        captures: Vec<(usize, usize)>,
     },
     PlayerDone {
         player: usize,
     },
+    /// The Player finished first and won the Game, which is sent as soon as they finished, while
+    /// the other Players can still play on for the remaining Places
+    Winner {
+        player: usize,
+    },
     GameDone {
         ranking: Vec<usize>,
+        reason: GameEndReason,
+    },
+    Error {
+        reason: String,
+    },
+    Chat {
+        player: usize,
+        name: String,
+        text: String,
+    },
+    InvalidRequest {
+        reason: String,
+    },
+    /// The Player lost their Connection and the Game waits for them to rejoin
+    PlayerLeft {
+        player: usize,
+    },
+    /// The Player rejoined the Game after losing their Connection
+    PlayerRejoined {
+        player: usize,
+    },
+    /// The Game is stalled until all of these Players reconnected, which is sent again every time
+    /// one of them rejoined
+    WaitingFor {
+        players: Vec<usize>,
+    },
+    /// The most recent Rolls of the Player, starting with the oldest one
+    RollHistory {
+        rolls: Vec<usize>,
+    },
+    /// The Index, Name and whether they are currently connected for every Player of the Game
+    Roster {
+        players: Vec<(usize, String, bool)>,
+    },
+    /// The Player paused the Game, which does not continue until they resume it
+    Paused {
+        player: usize,
+    },
+    /// The Player resumed the paused Game
+    Resumed {
+        player: usize,
+    },
+    /// Multiple Responses sent together in a single Frame, which should be handled in Order
+    Batch {
+        responses: Vec<GameResponse>,
     },
 }
@@ -0,0 +1,257 @@
+use std::fmt::Debug;
+
+use axum::extract::ws::{close_code, CloseFrame, Message};
+use futures::{Sink, SinkExt, Stream, StreamExt};
+
+use crate::{GameError, GamePlayer, GameRequest, GameResponse, PlayerColor};
+
+/// The maximum Number of Characters in the Name of a Player
+pub const MAX_NAME_LENGTH: usize = 32;
+
+/// Checks if the given Name can be used by a Player
+pub fn validate_name(name: &str) -> Result<(), &'static str> {
+    if name.trim().is_empty() {
+        return Err("The Name must not be empty");
+    }
+    if name.chars().count() > MAX_NAME_LENGTH {
+        return Err("The Name is too long");
+    }
+
+    Ok(())
+}
+
+/// Makes the given Name unique among the already taken Names, by appending a Suffix like
+/// `name (2)` if needed
+pub fn unique_name<'n, I>(name: String, taken: I) -> String
+where
+    I: IntoIterator<Item = &'n str>,
+{
+    let taken: Vec<_> = taken.into_iter().collect();
+    if !taken.contains(&name.as_str()) {
+        return name;
+    }
+
+    (2..)
+        .map(|i| format!("{} ({})", name, i))
+        .find(|candidate| !taken.contains(&candidate.as_str()))
+        .expect("There are only finitely many Names taken")
+}
+
+/// Adds a newly connected Player with the given Name to the Lobby and informs all the Players
+/// about everyone that joined so far.
+///
+/// A Player with an invalid Name is rejected, by closing their Connection with the Reason
+pub async fn join_player<Tx, Rx>(
+    players: &mut Vec<GamePlayer<Tx, Rx>>,
+    name: String,
+    (mut send, recv): (Tx, Rx),
+) -> Result<(), GameError>
+where
+    Tx: Sink<Message> + Unpin,
+    <Tx as Sink<Message>>::Error: Debug,
+    Rx: Stream<Item = Result<Message, axum::Error>> + Unpin,
+{
+    if let Err(reason) = validate_name(&name) {
+        tracing::warn!("Rejecting Player {:?}: {}", name, reason);
+
+        let close = Message::Close(Some(CloseFrame {
+            code: close_code::POLICY,
+            reason: reason.into(),
+        }));
+        // The Player is rejected either way, so a failed Close does not matter
+        let _ = send.send(close).await;
+        return Err(GameError::Protocol {
+            detail: reason.to_string(),
+        });
+    }
+
+    let name = unique_name(name, players.iter().map(|p| p.name.as_str()));
+    players.push(GamePlayer::new(name, (send, recv)));
+
+    indicate_lobby(players).await;
+
+    Ok(())
+}
+
+/// Renames the given Player in the Lobby and informs all the Players about the new Name.
+///
+/// An invalid Name is rejected like when joining, but the Player stays in the Lobby with their
+/// old Name
+pub async fn rename_player<Tx, Rx>(
+    players: &mut [GamePlayer<Tx, Rx>],
+    player: usize,
+    name: String,
+) -> Result<(), GameError>
+where
+    Tx: Sink<Message> + Unpin,
+    <Tx as Sink<Message>>::Error: Debug,
+    Rx: Stream<Item = Result<Message, axum::Error>> + Unpin,
+{
+    if let Err(reason) = validate_name(&name) {
+        tracing::warn!(
+            "Rejecting Name {:?} for Player {}: {}",
+            name,
+            player,
+            reason
+        );
+
+        let resp = GameResponse::InvalidRequest {
+            reason: reason.to_string(),
+        };
+        // A failed send will be noticed while waiting for the Player
+        let _ = players[player].send_resp(&resp).await;
+        return Err(GameError::Protocol {
+            detail: reason.to_string(),
+        });
+    }
+
+    let name = unique_name(
+        name,
+        players
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| *i != player)
+            .map(|(_, p)| p.name.as_str()),
+    );
+    tracing::debug!("Renaming Player {:?} to {:?}", players[player].name, name);
+    players[player].name = name;
+
+    indicate_lobby(players).await;
+
+    Ok(())
+}
+
+/// Sends the Information about every Player in the Lobby to all of them
+async fn indicate_lobby<Tx, Rx>(players: &mut [GamePlayer<Tx, Rx>])
+where
+    Tx: Sink<Message> + Unpin,
+    <Tx as Sink<Message>>::Error: Debug,
+    Rx: Stream<Item = Result<Message, axum::Error>> + Unpin,
+{
+    let all_players: Vec<_> = players
+        .iter()
+        .enumerate()
+        .map(|(i, p)| (i, p.name.clone()))
+        .collect();
+    for (index, player) in players.iter_mut().enumerate() {
+        let resps = all_players
+            .iter()
+            .map(|ind_player| GameResponse::IndicatePlayer {
+                player: ind_player.0,
+                name: ind_player.1.clone(),
+                you: ind_player.0 == index,
+                // The Teams are only known once the Game is set up
+                team: None,
+                color: PlayerColor::for_seat(ind_player.0),
+            })
+            .collect();
+
+        // We ignore errors at this stage, because we only do this for a better UX
+        let _ = player.send_batch(resps).await;
+    }
+}
+
+/// What happened with a Player in the Lobby
+#[derive(Debug, PartialEq)]
+pub enum LobbyEvent {
+    /// The Player with the given Index is ready to start the Game
+    Ready(usize),
+    /// The Player with the given Index disconnected from the Lobby
+    Left(usize),
+}
+
+/// Waits for the next Player in the Lobby to get ready or leave, regardless of their Seat.
+///
+/// Renames are handled directly, so Players can change their Name at any Time before the Game
+/// starts, but any other Requests received in the meantime are ignored. Without any Players this
+/// never returns.
+pub async fn next_lobby_event<Tx, Rx>(players: &mut [GamePlayer<Tx, Rx>]) -> LobbyEvent
+where
+    Tx: Sink<Message> + Unpin,
+    <Tx as Sink<Message>>::Error: Debug,
+    Rx: Stream<Item = Result<Message, axum::Error>> + Unpin,
+{
+    if players.is_empty() {
+        return std::future::pending().await;
+    }
+
+    loop {
+        let (received, index, _) =
+            futures::future::select_all(players.iter_mut().map(|p| p.recv.next())).await;
+
+        let msg_text = match received {
+            Some(Ok(Message::Text(t))) => t,
+            Some(Ok(Message::Close(_))) | None => {
+                tracing::warn!("Player {:?} left the Lobby", players[index].name);
+                return LobbyEvent::Left(index);
+            }
+            Some(Ok(other)) => {
+                tracing::debug!("Ignoring Message {:?}", other);
+                continue;
+            }
+            Some(Err(e)) => {
+                tracing::error!("Error receiving {:?}", e);
+                return LobbyEvent::Left(index);
+            }
+        };
+
+        match serde_json::from_str(&msg_text) {
+            Ok(GameRequest::Ready) => {
+                tracing::debug!("Player {:?} is ready", players[index].name);
+                return LobbyEvent::Ready(index);
+            }
+            Ok(GameRequest::SetName { name }) => {
+                // A rejected Name was already reported to the Player
+                let _ = rename_player(players, index, name).await;
+            }
+            Ok(other) => {
+                tracing::warn!("Expected Ready but got {:?}", other);
+            }
+            Err(e) => {
+                tracing::error!("Error Message({:?}): {:?}", msg_text, e);
+            }
+        };
+    }
+}
+
+/// Waits until every Player has indicated that they are ready to start the Game and then informs
+/// all the Players about it, like [`wait_for_remaining_ready`] with nobody being ready yet
+pub async fn wait_for_ready<Tx, Rx>(players: &mut [GamePlayer<Tx, Rx>]) -> Result<(), GameError>
+where
+    Tx: Sink<Message> + Unpin,
+    <Tx as Sink<Message>>::Error: Debug,
+    Rx: Stream<Item = Result<Message, axum::Error>> + Unpin,
+{
+    let ready = vec![false; players.len()];
+    wait_for_remaining_ready(players, ready).await
+}
+
+/// Waits until all the Players, that are not marked as ready yet, have indicated that they are
+/// ready to start the Game and then informs all the Players about it.
+///
+/// The Players are handled in the Order their Messages arrive in, see [`next_lobby_event`]
+pub async fn wait_for_remaining_ready<Tx, Rx>(
+    players: &mut [GamePlayer<Tx, Rx>],
+    mut ready: Vec<bool>,
+) -> Result<(), GameError>
+where
+    Tx: Sink<Message> + Unpin,
+    <Tx as Sink<Message>>::Error: Debug,
+    Rx: Stream<Item = Result<Message, axum::Error>> + Unpin,
+{
+    while ready.iter().any(|r| !r) {
+        match next_lobby_event(players).await {
+            LobbyEvent::Ready(index) => ready[index] = true,
+            LobbyEvent::Left(_) => return Err(GameError::Disconnect),
+        };
+    }
+
+    let mut result = Ok(());
+    for player in players.iter_mut() {
+        if let Err(e) = player.send_resp(&GameResponse::AllReady).await {
+            result = Err(e);
+        }
+    }
+
+    result
+}
@@ -4,38 +4,157 @@ use axum::{
         Json, Path, State,
     },
     http::header,
-    response::{Html, IntoResponse},
+    response::{
+        sse::{Event, KeepAlive, Sse},
+        Html, IntoResponse,
+    },
     routing::get,
     routing::post,
     Router,
 };
 use futures::StreamExt;
-use server::{Game, GamePlayer, GameResponse, RejoinMessage};
+use rand::Rng;
+use server::{
+    bot::{BotPlayer, Seat, Strategy},
+    lobby::LobbyEvent,
+    Board, Game, GameObserver, GamePlayer, GameResponse, RejoinKey, RejoinMessage, RejoinRegistry,
+};
 use std::{
     collections::HashMap,
     fmt::Debug,
     net::SocketAddr,
-    sync::{Arc, Mutex},
+    sync::{
+        atomic::{AtomicBool, AtomicUsize, Ordering},
+        Arc, Mutex,
+    },
 };
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
 use uuid::Uuid;
 
-use serde_derive::Deserialize;
+use serde_derive::{Deserialize, Serialize};
+
+/// The Seat of a Player, which is either connected over a WebSocket or a Bot
+type PlayerSeat = Seat<WebSocket>;
+
+/// The Number of public Updates of a Session that are buffered for slow Subscribers
+const EVENT_CAPACITY: usize = 64;
+
+/// How long a Player can take to rejoin, before the other Players are told that they left
+const RECONNECT_GRACE: std::time::Duration = std::time::Duration::from_secs(5);
+
+/// The Number of Sessions that can run at the same Time, unless configured otherwise
+const DEFAULT_MAX_SESSIONS: usize = 256;
 
 #[derive(Debug)]
 struct AppState {
     sessions: Mutex<HashMap<Uuid, Session>>,
+    /// The Rejoin-Codes of all the started Games, including the ones from before a Restart
+    registry: Arc<Mutex<RejoinRegistry>>,
+    /// The maximum Number of Sessions that can run at the same Time
+    max_sessions: usize,
 }
 
 #[derive(Debug)]
 struct Session {
     join: Arc<tokio::sync::mpsc::UnboundedSender<(String, WebSocket)>>,
-    rejoin: Arc<tokio::sync::mpsc::UnboundedSender<RejoinMessage<WebSocket, WebSocket>>>,
+    rejoin: Arc<tokio::sync::mpsc::UnboundedSender<RejoinMessage<PlayerSeat, PlayerSeat>>>,
+    /// The public Updates of the Game, which can be subscribed to before the Game is started
+    events: tokio::sync::broadcast::Sender<GameResponse>,
+    player_count: usize,
+    progress: Arc<SessionProgress>,
+}
+
+impl Session {
+    /// Whether the Session stopped running, which drops all the Receivers of the Session
+    fn is_finished(&self) -> bool {
+        self.join.is_closed()
+    }
+}
+
+/// The Progress of a Session, which is updated by the Session itself
+#[derive(Debug, Default)]
+struct SessionProgress {
+    joined: AtomicUsize,
+    started: AtomicBool,
+    status: Mutex<SessionStatus>,
+}
+
+/// The publicly visible Status of the Game in a Session
+#[derive(Debug, Default, Clone, Serialize)]
+struct SessionStatus {
+    players: Vec<String>,
+    /// Whether each of the Players is currently connected
+    connected: Vec<bool>,
+    next_player: usize,
+    ranking: Vec<usize>,
+    /// How often every Value from 1 to 6 was rolled so far
+    roll_counts: [usize; 6],
+    finished: bool,
+}
+
+impl SessionProgress {
+    /// Updates the Status with the current State of the Game
+    fn publish<R: Rng>(&self, game: &Game<R, PlayerSeat, PlayerSeat>, finished: bool) {
+        let mut status = self.status.lock().unwrap();
+        status.players = game.players.iter().map(|p| p.name.clone()).collect();
+        status.connected = game.players.iter().map(|p| p.is_connected()).collect();
+        status.next_player = game.next_player;
+        status.ranking = game.ranking.clone();
+        status.roll_counts = game.roll_stats();
+        status.finished = finished;
+    }
+}
+
+/// Keeps the Progress of a Session up to date, while its Game is running
+struct SessionObserver {
+    progress: Arc<SessionProgress>,
+}
+
+impl<R> GameObserver<R, PlayerSeat, PlayerSeat> for SessionObserver
+where
+    R: Rng + Send,
+{
+    async fn before_step(&mut self, game: &mut Game<R, PlayerSeat, PlayerSeat>) {
+        self.progress.publish(game, false);
+    }
+
+    fn after_step(
+        &mut self,
+        game: &Game<R, PlayerSeat, PlayerSeat>,
+        _: Option<&server::statemachine::GameState>,
+    ) {
+        self.progress.publish(game, false);
+    }
+}
+
+/// The Receivers over which a running Session gets all its new Connections
+struct SessionReceivers {
+    join: tokio::sync::mpsc::UnboundedReceiver<(String, WebSocket)>,
+    /// Rejoining Players as well as new and rejoining Spectators
+    rejoin: tokio::sync::mpsc::UnboundedReceiver<RejoinMessage<PlayerSeat, PlayerSeat>>,
+}
+
+#[derive(Debug, PartialEq, Serialize)]
+struct SessionInfo {
+    id: Uuid,
+    player_count: usize,
+    joined: usize,
+    started: bool,
 }
 
 #[derive(Debug, Deserialize)]
 struct CreateRequest {
     players: usize,
+    /// The Number of Seats that should be taken by Bots
+    #[serde(default)]
+    bots: usize,
+    /// The Seed for the Rolls of the Game, which makes the Game reproducible
+    #[serde(default)]
+    seed: Option<u64>,
+    /// Whether the Players can ask for a Rematch once the Game is done, instead of the Session
+    /// ending right away
+    #[serde(default)]
+    rematch: bool,
 }
 
 #[tokio::main]
@@ -51,8 +170,20 @@ async fn main() {
         .with((!machine_log).then(|| tracing_subscriber::fmt::layer().pretty()))
         .init();
 
+    let registry = match std::env::var("REJOIN_REGISTRY") {
+        Ok(path) => RejoinRegistry::load(path).expect("Loading the Rejoin-Registry"),
+        Err(_) => RejoinRegistry::new(),
+    };
+
+    let max_sessions = match std::env::var("MAX_SESSIONS") {
+        Ok(max) => max.parse().expect("Parsing the maximum Number of Sessions"),
+        Err(_) => DEFAULT_MAX_SESSIONS,
+    };
+
     let state = Arc::new(AppState {
         sessions: Mutex::new(HashMap::new()),
+        registry: Arc::new(Mutex::new(registry)),
+        max_sessions,
     });
 
     let app = Router::new()
@@ -60,8 +191,14 @@ async fn main() {
         .route("/style.css", get(style))
         .route("/dice.css", get(dice_style))
         .route("/create", post(create))
+        .route("/sessions", get(list_sessions))
+        .route("/session/:id", get(session_status))
         .route("/join/:session/:name", get(join_handler))
         .route("/rejoin/:game/:key", get(rejoin_handler))
+        .route("/rejoin_by_name/:game/:name", get(rejoin_by_name_handler))
+        .route("/spectate/:session", get(spectate_handler))
+        .route("/session/:id/kick/:player", post(kick_handler))
+        .route("/session/:id/events", get(events_handler))
         .with_state(state);
 
     let addr = SocketAddr::from(([0, 0, 0, 0], 3000));
@@ -83,12 +220,7 @@ async fn join_handler(
 
     let target_session = match sessions.get(&session) {
         Some(s) => s,
-        None => {
-            return axum::response::Response::builder()
-                .status(axum::http::status::StatusCode::BAD_REQUEST)
-                .body(axum::body::boxed(String::new()))
-                .unwrap();
-        }
+        None => return missing_game_response(false),
     };
 
     let target_tx = target_session.join.clone();
@@ -110,106 +242,416 @@ async fn rejoin_handler(
     let target_session = match sessions.get(&game) {
         Some(s) => s,
         None => {
-            return axum::response::Response::builder()
-                .status(axum::http::status::StatusCode::BAD_REQUEST)
-                .body(axum::body::boxed(String::new()))
-                .unwrap();
+            let known = state.registry.lock().unwrap().find(game, key).is_some();
+            return missing_game_response(known);
+        }
+    };
+
+    // The Game itself knows whether the Code belongs to a Player or a Spectator
+    let target_tx = target_session.rejoin.clone();
+
+    ws.on_upgrade(move |socket| async move {
+        target_tx
+            .send((RejoinKey::Code(key), Seat::Remote(socket).split()))
+            .expect("");
+    })
+}
+
+async fn rejoin_by_name_handler(
+    Path((game, name)): Path<(Uuid, String)>,
+    ws: WebSocketUpgrade,
+    State(state): State<Arc<AppState>>,
+) -> axum::response::Response {
+    tracing::trace!("Rejoin Game {:?} as {:?}", game, name);
+
+    let sessions = state.sessions.lock().unwrap();
+
+    let target_session = match sessions.get(&game) {
+        Some(s) => s,
+        None => {
+            let known = state.registry.lock().unwrap().contains_game(game);
+            return missing_game_response(known);
         }
     };
 
+    if let Err(status) = check_rejoin_name(target_session, &name) {
+        return axum::response::Response::builder()
+            .status(status)
+            .body(axum::body::boxed(String::new()))
+            .unwrap();
+    }
+
     let target_tx = target_session.rejoin.clone();
 
     ws.on_upgrade(move |socket| async move {
-        target_tx.send((key, socket.split())).expect("");
+        target_tx
+            .send((RejoinKey::Name(name), Seat::Remote(socket).split()))
+            .expect("");
     })
 }
 
+/// The Response for joining, rejoining or spectating a Game that is not running, which is always
+/// a 404.
+///
+/// Games that are still known from the Rejoin-Registry did run at some point, but are not running
+/// anymore, for example because the Server was restarted in the meantime, which is explained in
+/// the Body.
+fn missing_game_response(known: bool) -> axum::response::Response {
+    let body = if known {
+        "The Game is no longer running".to_string()
+    } else {
+        String::new()
+    };
+
+    axum::response::Response::builder()
+        .status(axum::http::status::StatusCode::NOT_FOUND)
+        .body(axum::body::boxed(body))
+        .unwrap()
+}
+
+/// Checks if the Name identifies exactly one Player in the Session, who is currently disconnected.
+///
+/// The Names of all Players are public, so a connected Player can not be replaced by using their
+/// Name
+fn check_rejoin_name(session: &Session, name: &str) -> Result<(), axum::http::status::StatusCode> {
+    let status = session.progress.status.lock().unwrap();
+
+    let mut matching = status
+        .players
+        .iter()
+        .enumerate()
+        .filter(|(_, p)| p.as_str() == name)
+        .map(|(i, _)| i);
+    match (matching.next(), matching.next()) {
+        (None, _) => Err(axum::http::status::StatusCode::NOT_FOUND),
+        (Some(index), None) if !status.connected.get(index).copied().unwrap_or(true) => Ok(()),
+        _ => Err(axum::http::status::StatusCode::CONFLICT),
+    }
+}
+
+async fn spectate_handler(
+    Path(session): Path<Uuid>,
+    ws: WebSocketUpgrade,
+    State(state): State<Arc<AppState>>,
+) -> axum::response::Response {
+    tracing::trace!("Spectate {:?}", session);
+
+    let sessions = state.sessions.lock().unwrap();
+
+    let target_session = match sessions.get(&session) {
+        Some(s) => s,
+        None => return missing_game_response(false),
+    };
+
+    let target_tx = target_session.rejoin.clone();
+
+    ws.on_upgrade(|socket| async move {
+        target_tx
+            .send((RejoinKey::Spectate, Seat::Remote(socket).split()))
+            .expect("");
+    })
+}
+
+/// Kicks the Player from a running Game and lets a Bot take over their Seat.
+///
+/// Only disconnected Players can be kicked, so nobody can take the Seat of a Player that is still
+/// playing
+async fn kick_handler(
+    Path((session, player)): Path<(Uuid, usize)>,
+    State(state): State<Arc<AppState>>,
+) -> axum::response::Response {
+    tracing::trace!("Kick Player {} from {:?}", player, session);
+
+    let sessions = state.sessions.lock().unwrap();
+
+    let status = match sessions.get(&session) {
+        None => axum::http::status::StatusCode::NOT_FOUND,
+        Some(s) if player >= s.player_count => axum::http::status::StatusCode::BAD_REQUEST,
+        Some(s) if !s.progress.started.load(Ordering::SeqCst) => {
+            axum::http::status::StatusCode::CONFLICT
+        }
+        Some(s)
+            if s.progress
+                .status
+                .lock()
+                .unwrap()
+                .connected
+                .get(player)
+                .copied()
+                .unwrap_or(true) =>
+        {
+            axum::http::status::StatusCode::CONFLICT
+        }
+        Some(s) => {
+            let board = Board::for_players(s.player_count);
+            let bot = Seat::Bot(BotPlayer::substitute(Strategy::default(), board));
+            match s.rejoin.send((RejoinKey::Kick(player), bot.split())) {
+                Ok(_) => axum::http::status::StatusCode::OK,
+                Err(_) => axum::http::status::StatusCode::GONE,
+            }
+        }
+    };
+
+    axum::response::Response::builder()
+        .status(status)
+        .body(axum::body::boxed(String::new()))
+        .unwrap()
+}
+
 async fn create(
     State(state): State<Arc<AppState>>,
     Json(content): Json<CreateRequest>,
-) -> impl IntoResponse {
+) -> axum::response::Response {
     tracing::trace!("Create Game {:?}", content);
 
+    if let Err(e) = server::validate_player_count(content.players) {
+        tracing::warn!("Invalid Number of Players: {:?}", e);
+        return axum::response::Response::builder()
+            .status(axum::http::status::StatusCode::BAD_REQUEST)
+            .body(axum::body::boxed(String::new()))
+            .unwrap();
+    }
+
+    if content.bots > content.players {
+        return axum::response::Response::builder()
+            .status(axum::http::status::StatusCode::BAD_REQUEST)
+            .body(axum::body::boxed(String::new()))
+            .unwrap();
+    }
+
+    let mut games = state.sessions.lock().unwrap();
+    games.retain(|_, s| !s.is_finished());
+    if games.len() >= state.max_sessions {
+        tracing::warn!("Reached the maximum of {} Sessions", state.max_sessions);
+        return axum::response::Response::builder()
+            .status(axum::http::status::StatusCode::SERVICE_UNAVAILABLE)
+            .body(axum::body::boxed(
+                "Too many Games are running, try again later".to_string(),
+            ))
+            .unwrap();
+    }
+
     let gameid = Uuid::new_v4();
+    let player_count = content.players;
 
     let (join_tx, join_rx) = tokio::sync::mpsc::unbounded_channel();
     let (rejoin_tx, rejoin_rx) = tokio::sync::mpsc::unbounded_channel();
-    tokio::spawn(start_session(gameid, content.players, join_rx, rejoin_rx));
-
-    {
-        let mut games = state.sessions.lock().unwrap();
-        games.insert(
-            gameid,
-            Session {
-                join: Arc::new(join_tx),
-                rejoin: Arc::new(rejoin_tx),
-            },
-        );
-    }
+    let (events, _) = tokio::sync::broadcast::channel(EVENT_CAPACITY);
+    let progress = Arc::new(SessionProgress::default());
+    let session = start_session(
+        gameid,
+        content,
+        SessionReceivers {
+            join: join_rx,
+            rejoin: rejoin_rx,
+        },
+        events.clone(),
+        progress.clone(),
+        state.registry.clone(),
+    );
+    let app = state.clone();
+    tokio::spawn(async move {
+        session.await;
 
-    gameid.to_string()
+        // Nobody can join or rejoin a finished Game, so its Session and Rejoin-Codes are not
+        // needed anymore
+        tracing::debug!("Removing finished Session {:?}", gameid);
+        // The File is written after releasing the Lock, to not block the other Handlers
+        let write = app.registry.lock().unwrap().remove_game(gameid);
+        if let Some(write) = write {
+            if let Err(e) = write.write().await {
+                tracing::warn!("Could not persist the removed Rejoin-Codes: {:?}", e);
+            }
+        }
+        app.sessions.lock().unwrap().remove(&gameid);
+    });
+
+    games.insert(
+        gameid,
+        Session {
+            join: Arc::new(join_tx),
+            rejoin: Arc::new(rejoin_tx),
+            events,
+            player_count,
+            progress,
+        },
+    );
+
+    gameid.to_string().into_response()
+}
+
+async fn list_sessions(State(state): State<Arc<AppState>>) -> Json<Vec<SessionInfo>> {
+    let sessions = state.sessions.lock().unwrap();
+
+    let infos = sessions
+        .iter()
+        .map(|(id, session)| SessionInfo {
+            id: *id,
+            player_count: session.player_count,
+            joined: session.progress.joined.load(Ordering::SeqCst),
+            started: session.progress.started.load(Ordering::SeqCst),
+        })
+        .collect();
+
+    Json(infos)
 }
 
-#[tracing::instrument(skip(n_players, rejoin_players, player_count))]
+async fn session_status(
+    Path(session): Path<Uuid>,
+    State(state): State<Arc<AppState>>,
+) -> axum::response::Response {
+    let sessions = state.sessions.lock().unwrap();
+
+    match sessions.get(&session) {
+        Some(s) => Json(s.progress.status.lock().unwrap().clone()).into_response(),
+        None => axum::http::status::StatusCode::NOT_FOUND.into_response(),
+    }
+}
+
+#[tracing::instrument(skip(request, receivers, events, progress, registry))]
 async fn start_session(
     id: Uuid,
-    player_count: usize,
-    mut n_players: tokio::sync::mpsc::UnboundedReceiver<(String, WebSocket)>,
-    mut rejoin_players: tokio::sync::mpsc::UnboundedReceiver<RejoinMessage<WebSocket, WebSocket>>,
+    request: CreateRequest,
+    receivers: SessionReceivers,
+    events: tokio::sync::broadcast::Sender<GameResponse>,
+    progress: Arc<SessionProgress>,
+    registry: Arc<Mutex<RejoinRegistry>>,
 ) {
-    tracing::debug!("Waiting for Players");
+    let SessionReceivers {
+        join: mut n_players,
+        rejoin: mut rejoin_players,
+    } = receivers;
+    let CreateRequest {
+        players: player_count,
+        bots,
+        seed,
+        rematch,
+    } = request;
 
-    let mut players = Vec::new();
-    while let Some((name, ws)) = n_players.recv().await {
-        // Add the newly connected Player
-        players.push(GamePlayer::new(name, ws.split()));
-        if players.len() == player_count {
-            break;
-        }
+    let mut players: Vec<_> = (0..bots)
+        .map(|i| {
+            GamePlayer::new(
+                format!("Bot {}", i + 1),
+                Seat::Bot(BotPlayer::new(Board::for_players(player_count))).split(),
+            )
+        })
+        .collect();
+    progress.joined.store(players.len(), Ordering::SeqCst);
+    progress.status.lock().unwrap().players = players.iter().map(|p| p.name.clone()).collect();
+
+    tracing::debug!("Waiting for Players");
 
-        // Send the information of all the currently joined Players around
-        let all_players: Vec<_> = players
-            .iter()
-            .enumerate()
-            .map(|(i, p)| (i, p.name.clone()))
-            .collect();
-        for (index, player) in players.iter_mut().enumerate() {
-            for ind_player in all_players.iter() {
-                let resp = GameResponse::IndicatePlayer {
-                    player: ind_player.0,
-                    name: ind_player.1.clone(),
-                    you: ind_player.0 == index,
+    // The Players that joined already can get ready or change their Name, while waiting for the
+    // others
+    let mut ready = vec![false; players.len()];
+    while players.len() < player_count {
+        tokio::select! {
+            joined = n_players.recv() => {
+                let (name, ws) = match joined {
+                    Some(p) => p,
+                    None => break,
                 };
 
-                // We ignore errors at this stage, because we only do this for a better UX
-                let _ = player.send_resp(&resp).await;
+                // Add the newly connected Player
+                if let Err(e) =
+                    server::lobby::join_player(&mut players, name, Seat::Remote(ws).split()).await
+                {
+                    tracing::warn!("Player could not join: {:?}", e);
+                    continue;
+                }
+                ready.push(false);
+                progress.joined.store(players.len(), Ordering::SeqCst);
             }
-        }
+            event = server::lobby::next_lobby_event(&mut players) => match event {
+                LobbyEvent::Ready(index) => ready[index] = true,
+                LobbyEvent::Left(index) => {
+                    tracing::error!("Player {} left before the Game started", index);
+                    return;
+                }
+            },
+        };
+        progress.status.lock().unwrap().players = players.iter().map(|p| p.name.clone()).collect();
     }
 
-    tracing::debug!("Starting Game");
+    tracing::debug!("Waiting for Players to be ready");
 
-    let mut game = Game::new(id, players);
-    let mut gamestate = server::statemachine::GameState::StartTurn { attempt: 0 };
+    if let Err(e) = server::lobby::wait_for_remaining_ready(&mut players, ready).await {
+        tracing::error!("Players did not get ready: {:?}", e);
+        return;
+    }
+    // Players could have changed their Names while getting ready
+    progress.status.lock().unwrap().players = players.iter().map(|p| p.name.clone()).collect();
 
-    // Here we use unwrap because we dont really have any good way to handle any potential issues here
-    game.send_state().await.unwrap();
-    game.indicate_players().await.unwrap();
-    game.send_rejoin_codes().await.unwrap();
+    tracing::debug!("Starting Game");
+    progress.started.store(true, Ordering::SeqCst);
+
+    let game = match seed {
+        Some(seed) => Game::with_seed(id, players, seed),
+        None => Game::new(id, players),
+    };
+    let mut game = match game {
+        Ok(g) => g.with_events(events),
+        Err(e) => {
+            tracing::error!("Could not create Game: {:?}", e);
+            return;
+        }
+    };
+    // Bots always ask for a Rematch, so a Game without any real Players would never end
+    game.rematch = rematch && bots < player_count;
+    game.reconnect_grace = RECONNECT_GRACE;
+    let write = registry.lock().unwrap().register(&game);
+    if let Some(write) = write {
+        if let Err(e) = write.write().await {
+            tracing::warn!("Could not persist the Rejoin-Codes: {:?}", e);
+        }
+    }
+    // Players that can not be reached at this point will be noticed during the first Turns
+    if let Err(e) = game.send_state().await {
+        tracing::warn!("Sending initial State: {:?}", e);
+    }
+    if let Err(e) = game.indicate_players().await {
+        tracing::warn!("Indicating Players: {:?}", e);
+    }
+    if let Err(e) = game.send_rejoin_codes().await {
+        tracing::warn!("Sending Rejoin-Codes: {:?}", e);
+    }
 
     let mut distr = rand::distributions::Uniform::new_inclusive(1, 6);
 
-    loop {
-        gamestate =
-            match server::statemachine::step(gamestate, &mut game, &mut rejoin_players, &mut distr)
-                .await
-            {
-                Some(gs) => gs,
-                None => break,
+    let mut observer = SessionObserver {
+        progress: progress.clone(),
+    };
+    server::run_game_with(&mut game, &mut rejoin_players, &mut distr, &mut observer).await;
+
+    progress.publish(&game, true);
+}
+
+/// Streams all the public Updates of the Game in a Session as Server-Sent Events, where every
+/// Event contains a single Response as JSON
+async fn events_handler(
+    Path(session): Path<Uuid>,
+    State(state): State<Arc<AppState>>,
+) -> axum::response::Response {
+    let events = match state.sessions.lock().unwrap().get(&session) {
+        Some(s) => s.events.subscribe(),
+        None => return axum::http::status::StatusCode::NOT_FOUND.into_response(),
+    };
+
+    let stream = futures::stream::unfold(events, |mut events| async move {
+        loop {
+            match events.recv().await {
+                Ok(resp) => return Some((Event::default().json_data(&resp), events)),
+                Err(tokio::sync::broadcast::error::RecvError::Lagged(missed)) => {
+                    tracing::warn!("Event Subscriber missed {} Events", missed);
+                }
+                Err(tokio::sync::broadcast::error::RecvError::Closed) => return None,
             };
+        }
+    });
 
-        tokio::task::yield_now().await;
-    }
+    Sse::new(stream)
+        .keep_alive(KeepAlive::default())
+        .into_response()
 }
 
 // Include utf-8 file at **compile** time.
@@ -238,3 +680,376 @@ async fn dice_style() -> impl IntoResponse {
 
     ([(header::CONTENT_TYPE, "text/css")], content)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn app_state() -> Arc<AppState> {
+        Arc::new(AppState {
+            sessions: Mutex::new(HashMap::new()),
+            registry: Arc::new(Mutex::new(RejoinRegistry::new())),
+            max_sessions: DEFAULT_MAX_SESSIONS,
+        })
+    }
+
+    /// A Session that already stopped running
+    fn finished_session() -> Session {
+        Session {
+            join: Arc::new(tokio::sync::mpsc::unbounded_channel().0),
+            rejoin: Arc::new(tokio::sync::mpsc::unbounded_channel().0),
+            events: tokio::sync::broadcast::channel(EVENT_CAPACITY).0,
+            player_count: 2,
+            progress: Arc::new(SessionProgress::default()),
+        }
+    }
+
+    #[tokio::test]
+    async fn list_created_session() {
+        let state = app_state();
+
+        let Json(infos) = list_sessions(State(state.clone())).await;
+        assert!(infos.is_empty());
+
+        create(
+            State(state.clone()),
+            Json(CreateRequest {
+                players: 2,
+                bots: 0,
+                seed: None,
+                rematch: false,
+            }),
+        )
+        .await;
+        let id = *state.sessions.lock().unwrap().keys().next().unwrap();
+
+        let Json(infos) = list_sessions(State(state)).await;
+        assert_eq!(
+            vec![SessionInfo {
+                id,
+                player_count: 2,
+                joined: 0,
+                started: false,
+            }],
+            infos
+        );
+    }
+
+    #[test]
+    fn rematch_is_opt_in() {
+        let request: CreateRequest = serde_json::from_str(r#"{"players":2}"#).unwrap();
+        assert!(!request.rematch);
+
+        let request: CreateRequest =
+            serde_json::from_str(r#"{"players":2,"rematch":true}"#).unwrap();
+        assert!(request.rematch);
+    }
+
+    #[tokio::test]
+    async fn create_with_too_many_bots() {
+        let state = app_state();
+
+        let resp = create(
+            State(state.clone()),
+            Json(CreateRequest {
+                players: 2,
+                bots: 3,
+                seed: None,
+                rematch: false,
+            }),
+        )
+        .await;
+        assert_eq!(axum::http::status::StatusCode::BAD_REQUEST, resp.status());
+        assert!(state.sessions.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn create_with_invalid_player_count() {
+        let state = app_state();
+
+        for players in [0, 1, 5] {
+            let resp = create(
+                State(state.clone()),
+                Json(CreateRequest {
+                    players,
+                    bots: 0,
+                    seed: None,
+                    rematch: false,
+                }),
+            )
+            .await;
+            assert_eq!(axum::http::status::StatusCode::BAD_REQUEST, resp.status());
+        }
+        assert!(state.sessions.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn create_beyond_max_sessions() {
+        let state = Arc::new(AppState {
+            sessions: Mutex::new(HashMap::new()),
+            registry: Arc::new(Mutex::new(RejoinRegistry::new())),
+            max_sessions: 2,
+        });
+        let request = || {
+            Json(CreateRequest {
+                players: 2,
+                bots: 0,
+                seed: None,
+                rematch: false,
+            })
+        };
+
+        for _ in 0..2 {
+            let resp = create(State(state.clone()), request()).await;
+            assert_eq!(axum::http::status::StatusCode::OK, resp.status());
+        }
+
+        let resp = create(State(state.clone()), request()).await;
+        assert_eq!(
+            axum::http::status::StatusCode::SERVICE_UNAVAILABLE,
+            resp.status()
+        );
+        assert_eq!(2, state.sessions.lock().unwrap().len());
+
+        // Finished Sessions are cleaned up to make room for new ones
+        let finished = *state.sessions.lock().unwrap().keys().next().unwrap();
+        state
+            .sessions
+            .lock()
+            .unwrap()
+            .insert(finished, finished_session());
+
+        let resp = create(State(state.clone()), request()).await;
+        assert_eq!(axum::http::status::StatusCode::OK, resp.status());
+
+        let sessions = state.sessions.lock().unwrap();
+        assert_eq!(2, sessions.len());
+        assert!(!sessions.contains_key(&finished));
+    }
+
+    #[tokio::test]
+    async fn finished_session_is_removed() {
+        let state = app_state();
+
+        // A Game of only Bots plays on its own until it is done
+        let resp = create(
+            State(state.clone()),
+            Json(CreateRequest {
+                players: 2,
+                bots: 2,
+                seed: Some(7),
+                rematch: false,
+            }),
+        )
+        .await;
+        assert_eq!(axum::http::status::StatusCode::OK, resp.status());
+        let id = *state.sessions.lock().unwrap().keys().next().unwrap();
+
+        tokio::time::timeout(std::time::Duration::from_secs(30), async {
+            while state.sessions.lock().unwrap().contains_key(&id) {
+                tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+            }
+        })
+        .await
+        .expect("The Game should finish");
+        assert!(!state.registry.lock().unwrap().contains_game(id));
+
+        let resp = session_status(Path(id), State(state)).await;
+        assert_eq!(axum::http::status::StatusCode::NOT_FOUND, resp.status());
+    }
+
+    #[tokio::test]
+    async fn kick_player() {
+        let state = app_state();
+
+        let resp = kick_handler(Path((Uuid::new_v4(), 0)), State(state.clone())).await;
+        assert_eq!(axum::http::status::StatusCode::NOT_FOUND, resp.status());
+
+        create(
+            State(state.clone()),
+            Json(CreateRequest {
+                players: 2,
+                bots: 0,
+                seed: None,
+                rematch: false,
+            }),
+        )
+        .await;
+        let id = *state.sessions.lock().unwrap().keys().next().unwrap();
+
+        let resp = kick_handler(Path((id, 2)), State(state.clone())).await;
+        assert_eq!(axum::http::status::StatusCode::BAD_REQUEST, resp.status());
+
+        let resp = kick_handler(Path((id, 1)), State(state.clone())).await;
+        assert_eq!(axum::http::status::StatusCode::CONFLICT, resp.status());
+
+        state.sessions.lock().unwrap()[&id]
+            .progress
+            .started
+            .store(true, Ordering::SeqCst);
+        state.sessions.lock().unwrap()[&id]
+            .progress
+            .status
+            .lock()
+            .unwrap()
+            .connected = vec![false, true];
+
+        // Connected Players can not be kicked
+        let resp = kick_handler(Path((id, 1)), State(state.clone())).await;
+        assert_eq!(axum::http::status::StatusCode::CONFLICT, resp.status());
+
+        let resp = kick_handler(Path((id, 0)), State(state)).await;
+        assert_eq!(axum::http::status::StatusCode::OK, resp.status());
+    }
+
+    #[tokio::test]
+    async fn session_events() {
+        let state = app_state();
+
+        let resp = events_handler(Path(Uuid::new_v4()), State(state.clone())).await;
+        assert_eq!(axum::http::status::StatusCode::NOT_FOUND, resp.status());
+
+        create(
+            State(state.clone()),
+            Json(CreateRequest {
+                players: 2,
+                bots: 0,
+                seed: None,
+                rematch: false,
+            }),
+        )
+        .await;
+        let id = *state.sessions.lock().unwrap().keys().next().unwrap();
+
+        let resp = events_handler(Path(id), State(state.clone())).await;
+        assert_eq!(axum::http::status::StatusCode::OK, resp.status());
+        assert_eq!(
+            "text/event-stream",
+            resp.headers()[header::CONTENT_TYPE].to_str().unwrap()
+        );
+
+        state.sessions.lock().unwrap()[&id]
+            .events
+            .send(GameResponse::Turn)
+            .unwrap();
+
+        let mut body = resp.into_body();
+        let data = axum::body::HttpBody::data(&mut body)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            "data:{\"type\":\"Turn\"}\n\n",
+            std::str::from_utf8(&data).unwrap()
+        );
+    }
+
+    #[tokio::test]
+    async fn status_of_session() {
+        let state = app_state();
+
+        let resp = session_status(Path(Uuid::new_v4()), State(state.clone())).await;
+        assert_eq!(axum::http::status::StatusCode::NOT_FOUND, resp.status());
+
+        create(
+            State(state.clone()),
+            Json(CreateRequest {
+                players: 2,
+                bots: 0,
+                seed: None,
+                rematch: false,
+            }),
+        )
+        .await;
+        let id = *state.sessions.lock().unwrap().keys().next().unwrap();
+
+        let resp = session_status(Path(id), State(state.clone())).await;
+        assert_eq!(axum::http::status::StatusCode::OK, resp.status());
+
+        {
+            let sessions = state.sessions.lock().unwrap();
+            let mut status = sessions[&id].progress.status.lock().unwrap();
+            status.players = vec!["first".to_string(), "second".to_string()];
+            status.connected = vec![true, false];
+            status.next_player = 1;
+        }
+
+        let resp = session_status(Path(id), State(state)).await;
+        let body = axum::body::HttpBody::data(&mut resp.into_body())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            serde_json::json!({
+                "players": ["first", "second"],
+                "connected": [true, false],
+                "next_player": 1,
+                "ranking": [],
+                "roll_counts": [0, 0, 0, 0, 0, 0],
+                "finished": false,
+            }),
+            serde_json::from_slice::<serde_json::Value>(&body).unwrap()
+        );
+    }
+
+    #[tokio::test]
+    async fn rejoin_to_missing_game() {
+        for known in [true, false] {
+            assert_eq!(
+                axum::http::status::StatusCode::NOT_FOUND,
+                missing_game_response(known).status()
+            );
+        }
+
+        let body = axum::body::HttpBody::data(&mut missing_game_response(true).into_body())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!("The Game is no longer running".as_bytes(), &body[..]);
+    }
+
+    #[tokio::test]
+    async fn rejoin_name_check() {
+        let state = app_state();
+
+        create(
+            State(state.clone()),
+            Json(CreateRequest {
+                players: 3,
+                bots: 0,
+                seed: None,
+                rematch: false,
+            }),
+        )
+        .await;
+        let id = *state.sessions.lock().unwrap().keys().next().unwrap();
+
+        let sessions = state.sessions.lock().unwrap();
+        let session = &sessions[&id];
+        {
+            let mut status = session.progress.status.lock().unwrap();
+            status.players = vec![
+                "unique".to_string(),
+                "duplicate".to_string(),
+                "duplicate".to_string(),
+            ];
+            status.connected = vec![true, false, false];
+        }
+
+        // The Seat of a connected Player can not be taken over by their Name
+        assert_eq!(
+            Err(axum::http::status::StatusCode::CONFLICT),
+            check_rejoin_name(session, "unique")
+        );
+        session.progress.status.lock().unwrap().connected[0] = false;
+        assert_eq!(Ok(()), check_rejoin_name(session, "unique"));
+        assert_eq!(
+            Err(axum::http::status::StatusCode::CONFLICT),
+            check_rejoin_name(session, "duplicate")
+        );
+        assert_eq!(
+            Err(axum::http::status::StatusCode::NOT_FOUND),
+            check_rejoin_name(session, "other")
+        );
+    }
+}
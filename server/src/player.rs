@@ -1,9 +1,14 @@
-use std::fmt::Debug;
+use std::{collections::VecDeque, fmt::Debug, time::Instant};
 
 use axum::extract::ws::Message;
 use futures::SinkExt;
 
-use crate::{Figure, GameError, GameResponse};
+use crate::{
+    Board, Figure, GameError, GameRequest, GameResponse, HouseEntry, MoveError, Sequenced,
+};
+
+/// The Number of recent Rolls that are remembered for every Player
+pub const ROLL_HISTORY_SIZE: usize = 10;
 
 /// A Player instance in a running Game
 #[derive(Debug)]
@@ -11,9 +16,22 @@ pub struct GamePlayer<Tx, Rx> {
     pub name: String,
     pub send: Tx,
     pub recv: Rx,
+    /// Every Player always has exactly 4 Figures, which is a deliberate Limit of the Game and
+    /// therefore also of the Protocol, like in [`GameResponse::State`]. Only the Board and the
+    /// Size of the Houses can change
     pub figures: [Figure; 4],
+    /// The last Time anything was received from the Player
+    pub last_seen: Instant,
     pub(crate) done: bool,
+    pub(crate) connected: bool,
     pub(crate) rejoin_code: uuid::Uuid,
+    /// The most recent Rolls of the Player, starting with the oldest one
+    pub(crate) rolls: VecDeque<usize>,
+    /// The Number of 6s the Player rolled in a Row during their current Turn
+    pub(crate) consecutive_sixes: usize,
+    /// The Requests the Player sent before it was their Turn, which are handled once their Turn
+    /// starts
+    pub(crate) queued: VecDeque<GameRequest>,
 }
 
 impl<Tx, Rx> GamePlayer<Tx, Rx> {
@@ -30,24 +48,255 @@ impl<Tx, Rx> GamePlayer<Tx, Rx> {
             ],
             rejoin_code: uuid::Uuid::new_v4(),
             done: false,
+            connected: true,
+            last_seen: Instant::now(),
+            rolls: VecDeque::with_capacity(ROLL_HISTORY_SIZE),
+            consecutive_sixes: 0,
+            queued: VecDeque::new(),
         }
     }
 
-    pub fn has_moveable_figure(&self) -> bool {
-        let figures_in_house: usize = self
-            .figures
+    /// Whether the Player is currently considered to be connected
+    pub fn is_connected(&self) -> bool {
+        self.connected
+    }
+
+    /// The Code that the Player can use to rejoin the Game after a Disconnect
+    pub fn rejoin_code(&self) -> uuid::Uuid {
+        self.rejoin_code
+    }
+
+    /// Remembers the given Roll, while only keeping the last [`ROLL_HISTORY_SIZE`] Rolls, and
+    /// counts the 6s rolled in a Row
+    pub fn record_roll(&mut self, value: usize) {
+        if self.rolls.len() == ROLL_HISTORY_SIZE {
+            self.rolls.pop_front();
+        }
+        self.rolls.push_back(value);
+
+        if value == 6 {
+            self.consecutive_sixes += 1;
+        } else {
+            self.consecutive_sixes = 0;
+        }
+    }
+
+    /// The Number of 6s the Player rolled in a Row during their current Turn
+    pub fn consecutive_sixes(&self) -> usize {
+        self.consecutive_sixes
+    }
+
+    /// The most recent Rolls of the Player, starting with the oldest one
+    pub fn recent_rolls(&self) -> Vec<usize> {
+        self.rolls.iter().copied().collect()
+    }
+
+    /// Whether the Player has a Figure that could still be moved on the Board, which are all the
+    /// Figures on the Field and the Figures in the House that are not yet packed at its End
+    pub fn has_moveable_figure(&self, board: &Board) -> bool {
+        let figures_in_house = self.figures_in_house();
+
+        self.figures_on_field() > 0
+            || self.figures.iter().any(|f| match f {
+                Figure::InHouse { pos } => *pos < board.house_size.saturating_sub(figures_in_house),
+                _ => false,
+            })
+    }
+
+    /// The Number of Figures that already reached the House
+    pub fn figures_in_house(&self) -> usize {
+        self.figures
             .iter()
             .filter(|f| matches!(f, Figure::InHouse { .. }))
-            .count();
+            .count()
+    }
 
+    /// The Number of Figures on the shared Track, which left the Start but did not reach the
+    /// House yet
+    pub fn figures_on_field(&self) -> usize {
         self.figures
             .iter()
-            .any(|f| matches!(f, Figure::OnField { .. }))
-            || self.figures.iter().any(|f| match f {
-                Figure::InHouse { pos } => *pos < 4 - figures_in_house,
-                _ => false,
+            .filter(|f| matches!(f, Figure::OnField { .. }))
+            .count()
+    }
+
+    /// The Number of Figures that are still in the Start
+    pub fn figures_in_start(&self) -> usize {
+        self.figures
+            .iter()
+            .filter(|f| matches!(f, Figure::InStart))
+            .count()
+    }
+
+    /// The Number of Steps all the Figures still need to move, until every Figure reached its
+    /// final Position in the House on the given Board.
+    ///
+    /// Leaving the Start counts as a single Step and as the House is filled from the Back, the
+    /// Figure furthest ahead has to reach the last Position of the House, the next Figure the one
+    /// in front of it and so on
+    pub fn remaining_distance(&self, board: &Board) -> usize {
+        // The Progress along the Path from the Start over the Track into the House, where the
+        // Start itself is at 0
+        let mut progress: Vec<usize> = self
+            .figures
+            .iter()
+            .map(|f| match f {
+                Figure::InStart => 0,
+                Figure::OnField { moved } => moved + 1,
+                Figure::InHouse { pos } => board.fields + pos + 1,
             })
+            .collect();
+        progress.sort_unstable_by(|a, b| b.cmp(a));
+
+        let last = board.fields + board.house_size;
+        progress
+            .into_iter()
+            .enumerate()
+            .map(|(i, p)| last.saturating_sub(i).saturating_sub(p))
+            .sum()
+    }
+
+    /// Finds the Figure standing on the given absolute Position of the shared Track, for a Board
+    /// with `board_size` Fields, where the Start-Field of the Player is at the absolute Position
+    /// `offset`.
+    ///
+    /// Figures in the Start or House are never on the shared Track.
+    pub fn figure_at_absolute(
+        &self,
+        abs: usize,
+        offset: usize,
+        board_size: usize,
+    ) -> Option<usize> {
+        self.figures.iter().position(|f| match f {
+            Figure::OnField { moved } if *moved < board_size => {
+                (moved + offset) % board_size == abs
+            }
+            _ => false,
+        })
     }
+
+    /// Returns the Indices of all the Figures that could be moved by the given value.
+    ///
+    /// This considers that a Figure can only leave the Start with a 6, can only enter the House as
+    /// allowed by the `house_entry` Rule and can not move onto a Field already occupied by another
+    /// Figure of the Player.
+    ///
+    /// If Blockades are allowed, `blockades` contains the Fields with a Blockade, counted from the
+    /// Start-Field of the Player, and two Figures of the Player may share a Field.
+    pub fn legal_moves(
+        &self,
+        value: usize,
+        board: &Board,
+        house_entry: HouseEntry,
+        blockades: Option<&[usize]>,
+    ) -> Vec<usize> {
+        (0..self.figures.len())
+            .filter(|i| {
+                simulate_move(&self.figures, *i, value, board, house_entry, blockades).is_ok()
+            })
+            .collect()
+    }
+}
+
+/// The furthest Position in the House, that is not occupied by any Figure other than the one
+/// with the given Index
+fn last_free_house_pos(figures: &[Figure], index: usize, house_size: usize) -> Option<usize> {
+    (0..house_size).rev().find(|pos| {
+        !figures
+            .iter()
+            .enumerate()
+            .any(|(i, f)| i != index && f == &Figure::InHouse { pos: *pos })
+    })
+}
+
+/// Calculates the Position the Figure with the given Index would end up at, when moved by the
+/// specified amount on the given Board, without actually moving it.
+///
+/// This considers that a Figure can only leave the Start with a 6, can only enter the House as
+/// allowed by the `house_entry` Rule, can neither jump over Figures in the House nor pass any of
+/// the `blockades` and can not move onto a Field already occupied by another of the `figures`.
+pub fn simulate_move(
+    figures: &[Figure],
+    index: usize,
+    amount: usize,
+    board: &Board,
+    house_entry: HouseEntry,
+    blockades: Option<&[usize]>,
+) -> Result<Figure, MoveError> {
+    let figure = figures.get(index).ok_or(MoveError::UnknownFigure)?;
+    let board_size = board.fields;
+
+    // The House directly follows the Track, so both can be treated as one continuous Path
+    let start = match figure {
+        Figure::InStart => None,
+        Figure::OnField { moved } => Some(*moved),
+        Figure::InHouse { pos } => Some(board_size + *pos),
+    };
+    let current = match figure {
+        Figure::InStart if amount == 6 => 0,
+        Figure::InStart => return Err(MoveError::InStart),
+        Figure::OnField { moved } => *moved + amount,
+        Figure::InHouse { pos } => board_size + *pos + amount,
+    };
+    let last = board_size + board.house_size - 1;
+
+    let target = if current <= last {
+        current
+    } else if house_entry == HouseEntry::Bounce {
+        (2 * last).saturating_sub(current)
+    } else {
+        return Err(MoveError::Overshoot);
+    };
+
+    // Figures can not jump over other Figures in the House, neither on the Way in nor when
+    // bouncing back from its End
+    if let Some(start) = start {
+        let forward = (start + 1)..=current.min(last);
+        let back = (target + 1)..last.min(current);
+        let blocked = forward
+            .chain(back)
+            .filter(|p| *p >= board_size && *p != target)
+            .any(|p| {
+                figures.iter().enumerate().any(|(i, f)| {
+                    i != index
+                        && f == &Figure::InHouse {
+                            pos: p - board_size,
+                        }
+                })
+            });
+        if blocked {
+            return Err(MoveError::Blocked);
+        }
+    }
+
+    // A Blockade can neither be passed nor landed on, not even by the Figures of its Owner
+    if let Some(blockades) = blockades {
+        let path = start.map_or(0, |s| s + 1)..=current.min(board_size - 1);
+        if blockades.iter().any(|b| path.contains(b)) {
+            return Err(MoveError::Blockade);
+        }
+    }
+
+    let n_state = if target < board_size {
+        Figure::OnField { moved: target }
+    } else {
+        let pos = target - board_size;
+        if house_entry == HouseEntry::Exact
+            && last_free_house_pos(figures, index, board.house_size) != Some(pos)
+        {
+            return Err(MoveError::Inexact);
+        }
+
+        Figure::InHouse { pos }
+    };
+
+    // With Blockades, a Figure can join another one of the Player on the Track
+    let stacking = blockades.is_some() && matches!(n_state, Figure::OnField { .. });
+    if !stacking && figures.iter().any(|f| f == &n_state) {
+        return Err(MoveError::Occupied);
+    }
+
+    Ok(n_state)
 }
 
 impl<Tx, Rx> GamePlayer<Tx, Rx>
@@ -57,7 +306,7 @@ where
 {
     /// Check if a Figure of the Player is still in the Start
     pub fn has_figures_in_start(&self) -> bool {
-        self.figures.iter().any(|f| matches!(f, Figure::InStart))
+        self.figures_in_start() > 0
     }
 
     /// Check if a Figure is still available to move
@@ -79,87 +328,124 @@ where
         self.done
     }
 
-    /// Try to send a given Response to the Player
+    /// Try to send a given Response to the Player.
+    ///
+    /// If the Response could not be sent, the Player is marked as disconnected
     pub async fn send_resp(&mut self, resp: &GameResponse) -> Result<(), GameError> {
-        let content = serde_json::to_string(resp)
-            .expect("Serializing a Response to send should always work as the Fromat is known");
+        let content = serde_json::to_string(resp).map_err(|e| {
+            tracing::error!("Error serializing Response: {:?}", e);
+            GameError::Serialization
+        })?;
+        self.send_content(content).await
+    }
+
+    /// Try to send all the given Responses to the Player in a single Frame, as a
+    /// [`GameResponse::Batch`] if there is more than one of them, like [`GamePlayer::send_resp`]
+    pub async fn send_batch(&mut self, mut resps: Vec<GameResponse>) -> Result<(), GameError> {
+        match resps.len() {
+            0 => Ok(()),
+            1 => self.send_resp(&resps.remove(0)).await,
+            _ => {
+                self.send_resp(&GameResponse::Batch { responses: resps })
+                    .await
+            }
+        }
+    }
+
+    /// Try to send a given Response together with its Sequence-Number to the Player, like
+    /// [`GamePlayer::send_resp`]
+    pub async fn send_sequenced(&mut self, resp: &GameResponse, seq: u64) -> Result<(), GameError> {
+        let content = serde_json::to_string(&Sequenced { seq, resp }).map_err(|e| {
+            tracing::error!("Error serializing Response: {:?}", e);
+            GameError::Serialization
+        })?;
+        self.send_content(content).await
+    }
+
+    async fn send_content(&mut self, content: String) -> Result<(), GameError> {
         match self.send.send(Message::Text(content)).await {
             Ok(_) => Ok(()),
             Err(e) => {
                 tracing::error!("Error sending Response: {:?}", e);
+                self.connected = false;
                 Err(GameError::Disconnect)
             }
         }
     }
 
-    /// Tries to move a given Figure by the specified amount.
+    /// Sends a Ping to the Player, to check if the Connection is still alive.
     ///
-    /// # Returns
-    /// * `Some` the new Position for the Figure
-    /// * `None` if the Figure could not be moved to the attempted position
-    #[must_use]
-    pub fn move_figure(&mut self, index: usize, amount: usize) -> Option<&Figure> {
-        let figure = self.figures.get_mut(index)?;
-
-        let n_state = match figure {
-            Figure::InStart => Figure::InStart,
-            Figure::OnField { moved } => {
-                let target = *moved + amount;
-
-                if target < 40 {
-                    tracing::debug!(
-                        "Move Figure {:?} on Field by {:?} from {:?} to {:?}",
-                        index,
-                        amount,
-                        moved,
-                        *moved + amount,
-                    );
-
-                    Figure::OnField { moved: target }
-                } else {
-                    let dif = target - 40;
-                    tracing::debug!(
-                        "Move Figure {:?} on Field by {:?} from {:?} to House {:?}",
-                        index,
-                        amount,
-                        moved,
-                        dif,
-                    );
-
-                    if dif < 4 {
-                        Figure::InHouse { pos: dif }
-                    } else {
-                        Figure::OnField { moved: *moved }
-                    }
-                }
-            }
-            Figure::InHouse { pos } => {
-                let target = *pos + amount;
-
-                tracing::debug!(
-                    "Move Figure {:?} in House by {:?} from {:?} to {:?}",
-                    index,
-                    amount,
-                    pos,
-                    target,
-                );
-
-                if target < 4 {
-                    Figure::InHouse { pos: target }
-                } else {
-                    Figure::InHouse { pos: *pos }
-                }
+    /// If the Ping could not be sent, the Player is marked as disconnected
+    pub async fn ping(&mut self) -> Result<(), GameError> {
+        match self.send.send(Message::Ping(Vec::new())).await {
+            Ok(_) => Ok(()),
+            Err(e) => {
+                tracing::error!("Error sending Ping: {:?}", e);
+                self.connected = false;
+                Err(GameError::Disconnect)
             }
-        };
+        }
+    }
 
-        if self.figures.iter().any(|f| f == &n_state) {
-            return None;
+    /// Answers a Ping of the Player with a Pong containing the same Payload.
+    ///
+    /// If the Pong could not be sent, the Player is marked as disconnected
+    pub async fn pong(&mut self, payload: Vec<u8>) -> Result<(), GameError> {
+        match self.send.send(Message::Pong(payload)).await {
+            Ok(_) => Ok(()),
+            Err(e) => {
+                tracing::error!("Error sending Pong: {:?}", e);
+                self.connected = false;
+                Err(GameError::Disconnect)
+            }
         }
+    }
+
+    /// Calculates where the given Figure would end up, when moved by the specified amount, like
+    /// [`GamePlayer::move_figure`] but without actually moving it
+    pub fn preview_move(
+        &self,
+        index: usize,
+        amount: usize,
+        board: &Board,
+        house_entry: HouseEntry,
+        blockades: Option<&[usize]>,
+    ) -> Result<Figure, MoveError> {
+        simulate_move(&self.figures, index, amount, board, house_entry, blockades)
+    }
+
+    /// Tries to move a given Figure by the specified amount on the given Board, following the
+    /// given `house_entry` Rule and without passing any of the `blockades`, like for
+    /// [`GamePlayer::legal_moves`].
+    ///
+    /// # Returns
+    /// * `Ok` the new Position for the Figure
+    /// * `Err` the reason why the Figure could not be moved to the attempted position
+    pub fn move_figure(
+        &mut self,
+        index: usize,
+        amount: usize,
+        board: &Board,
+        house_entry: HouseEntry,
+        blockades: Option<&[usize]>,
+    ) -> Result<&Figure, MoveError> {
+        let n_state = simulate_move(&self.figures, index, amount, board, house_entry, blockades)?;
 
-        let figure = self.figures.get_mut(index)?;
+        tracing::debug!(
+            "Move Figure {:?} by {:?} from {:?} to {:?}",
+            index,
+            amount,
+            self.figures[index],
+            n_state,
+        );
+
+        let figure = self
+            .figures
+            .get_mut(index)
+            .expect("We already checked that the Index is valid");
         *figure = n_state;
 
-        Some(figure)
+        Ok(figure)
     }
 
     pub fn check_done(&mut self) -> bool {
@@ -193,9 +479,14 @@ mod tests {
                 recv: (),
                 rejoin_code: uuid::Uuid::new_v4(),
                 done: false,
+                connected: true,
+                last_seen: Instant::now(),
+                rolls: VecDeque::new(),
+                consecutive_sixes: 0,
+                queued: VecDeque::new(),
             };
 
-            assert!(!player.has_moveable_figure());
+            assert!(!player.has_moveable_figure(&Board::default()));
         }
 
         {
@@ -211,9 +502,14 @@ mod tests {
                 recv: (),
                 rejoin_code: uuid::Uuid::new_v4(),
                 done: false,
+                connected: true,
+                last_seen: Instant::now(),
+                rolls: VecDeque::new(),
+                consecutive_sixes: 0,
+                queued: VecDeque::new(),
             };
 
-            assert!(player.has_moveable_figure());
+            assert!(player.has_moveable_figure(&Board::default()));
         }
 
         {
@@ -229,9 +525,14 @@ mod tests {
                 recv: (),
                 rejoin_code: uuid::Uuid::new_v4(),
                 done: false,
+                connected: true,
+                last_seen: Instant::now(),
+                rolls: VecDeque::new(),
+                consecutive_sixes: 0,
+                queued: VecDeque::new(),
             };
 
-            assert!(!player.has_moveable_figure());
+            assert!(!player.has_moveable_figure(&Board::default()));
         }
         {
             let player = GamePlayer {
@@ -246,9 +547,369 @@ mod tests {
                 recv: (),
                 rejoin_code: uuid::Uuid::new_v4(),
                 done: false,
+                connected: true,
+                last_seen: Instant::now(),
+                rolls: VecDeque::new(),
+                consecutive_sixes: 0,
+                queued: VecDeque::new(),
+            };
+
+            assert!(player.has_moveable_figure(&Board::default()));
+        }
+
+        {
+            // A larger House leaves more Room to move a Figure further in
+            let player = player([
+                Figure::InHouse { pos: 3 },
+                Figure::InStart,
+                Figure::InStart,
+                Figure::InStart,
+            ]);
+            let board = Board {
+                house_size: 6,
+                ..Board::default()
             };
 
-            assert!(player.has_moveable_figure());
+            assert!(!player.has_moveable_figure(&Board::default()));
+            assert!(player.has_moveable_figure(&board));
         }
     }
+
+    fn player(figures: [Figure; 4]) -> GamePlayer<(), ()> {
+        GamePlayer {
+            name: "test".to_string(),
+            figures,
+            send: (),
+            recv: (),
+            rejoin_code: uuid::Uuid::new_v4(),
+            done: false,
+            connected: true,
+            last_seen: Instant::now(),
+            rolls: VecDeque::new(),
+            consecutive_sixes: 0,
+            queued: VecDeque::new(),
+        }
+    }
+
+    #[test]
+    fn legal_moves_start() {
+        let player = player([
+            Figure::InStart,
+            Figure::InStart,
+            Figure::InStart,
+            Figure::InStart,
+        ]);
+
+        assert_eq!(
+            Vec::<usize>::new(),
+            player.legal_moves(3, &Board::default(), HouseEntry::Loose, None)
+        );
+        assert_eq!(
+            vec![0, 1, 2, 3],
+            player.legal_moves(6, &Board::default(), HouseEntry::Loose, None)
+        );
+
+        let player = self::player([
+            Figure::OnField { moved: 0 },
+            Figure::InStart,
+            Figure::InStart,
+            Figure::InStart,
+        ]);
+        assert_eq!(
+            vec![0],
+            player.legal_moves(6, &Board::default(), HouseEntry::Loose, None)
+        );
+    }
+
+    #[test]
+    fn legal_moves_field() {
+        let player = player([
+            Figure::OnField { moved: 5 },
+            Figure::OnField { moved: 8 },
+            Figure::OnField { moved: 38 },
+            Figure::InStart,
+        ]);
+
+        assert_eq!(
+            vec![0, 1, 2],
+            player.legal_moves(1, &Board::default(), HouseEntry::Loose, None)
+        );
+        assert_eq!(
+            vec![1, 2],
+            player.legal_moves(3, &Board::default(), HouseEntry::Loose, None)
+        );
+        assert_eq!(
+            vec![0, 1, 3],
+            player.legal_moves(6, &Board::default(), HouseEntry::Loose, None)
+        );
+    }
+
+    #[test]
+    fn legal_moves_house() {
+        let player = player([
+            Figure::InHouse { pos: 0 },
+            Figure::InHouse { pos: 2 },
+            Figure::OnField { moved: 39 },
+            Figure::InStart,
+        ]);
+
+        assert_eq!(
+            vec![0, 1],
+            player.legal_moves(1, &Board::default(), HouseEntry::Loose, None)
+        );
+        // Neither Figure can jump over the Figures already in the House
+        assert_eq!(
+            Vec::<usize>::new(),
+            player.legal_moves(3, &Board::default(), HouseEntry::Loose, None)
+        );
+        assert_eq!(
+            Vec::<usize>::new(),
+            player.legal_moves(4, &Board::default(), HouseEntry::Loose, None)
+        );
+        assert_eq!(
+            Vec::<usize>::new(),
+            player.legal_moves(5, &Board::default(), HouseEntry::Loose, None)
+        );
+    }
+
+    #[test]
+    fn figure_at_absolute() {
+        let player = player([
+            Figure::OnField { moved: 5 },
+            Figure::OnField { moved: 38 },
+            Figure::InHouse { pos: 0 },
+            Figure::InStart,
+        ]);
+
+        assert_eq!(Some(0), player.figure_at_absolute(5, 0, 40));
+        assert_eq!(Some(0), player.figure_at_absolute(15, 10, 40));
+        assert_eq!(Some(1), player.figure_at_absolute(8, 10, 40));
+        assert_eq!(None, player.figure_at_absolute(6, 0, 40));
+        // Neither the House nor the Start are on the shared Track
+        assert_eq!(None, player.figure_at_absolute(0, 0, 40));
+        assert_eq!(None, player.figure_at_absolute(40, 0, 40));
+    }
+
+    #[test]
+    fn figure_counts() {
+        let mixed = player([
+            Figure::InHouse { pos: 3 },
+            Figure::OnField { moved: 12 },
+            Figure::InStart,
+            Figure::InStart,
+        ]);
+        assert_eq!(1, mixed.figures_in_house());
+        assert_eq!(1, mixed.figures_on_field());
+        assert_eq!(2, mixed.figures_in_start());
+
+        let spread = player([
+            Figure::OnField { moved: 0 },
+            Figure::OnField { moved: 39 },
+            Figure::InHouse { pos: 0 },
+            Figure::InHouse { pos: 1 },
+        ]);
+        assert_eq!(2, spread.figures_in_house());
+        assert_eq!(2, spread.figures_on_field());
+        assert_eq!(0, spread.figures_in_start());
+    }
+
+    #[test]
+    fn remaining_distance() {
+        let board = Board::default();
+
+        let all_start = player([
+            Figure::InStart,
+            Figure::InStart,
+            Figure::InStart,
+            Figure::InStart,
+        ]);
+        assert_eq!(44 + 43 + 42 + 41, all_start.remaining_distance(&board));
+
+        let mid_field = player([
+            Figure::OnField { moved: 10 },
+            Figure::OnField { moved: 20 },
+            Figure::InStart,
+            Figure::InStart,
+        ]);
+        assert_eq!(
+            (44 - 21) + (43 - 11) + 42 + 41,
+            mid_field.remaining_distance(&board)
+        );
+
+        let near_done = player([
+            Figure::InHouse { pos: 3 },
+            Figure::InHouse { pos: 2 },
+            Figure::InHouse { pos: 0 },
+            Figure::OnField { moved: 38 },
+        ]);
+        assert_eq!(1 + 2, near_done.remaining_distance(&board));
+
+        let finished = player([
+            Figure::InHouse { pos: 3 },
+            Figure::InHouse { pos: 2 },
+            Figure::InHouse { pos: 1 },
+            Figure::InHouse { pos: 0 },
+        ]);
+        assert_eq!(0, finished.remaining_distance(&board));
+    }
+
+    #[test]
+    fn simulate_move_start() {
+        let board = Board::default();
+        let figures = [
+            Figure::InStart,
+            Figure::InStart,
+            Figure::OnField { moved: 3 },
+            Figure::InStart,
+        ];
+
+        assert_eq!(
+            Err(MoveError::InStart),
+            simulate_move(&figures, 0, 5, &board, HouseEntry::Loose, None)
+        );
+        assert_eq!(
+            Ok(Figure::OnField { moved: 0 }),
+            simulate_move(&figures, 0, 6, &board, HouseEntry::Loose, None)
+        );
+        assert_eq!(
+            Err(MoveError::UnknownFigure),
+            simulate_move(&figures, 4, 6, &board, HouseEntry::Loose, None)
+        );
+        // The Figure is not actually moved
+        assert_eq!(Figure::InStart, figures[0]);
+    }
+
+    #[test]
+    fn simulate_move_field() {
+        let board = Board::default();
+        let figures = [
+            Figure::OnField { moved: 10 },
+            Figure::OnField { moved: 13 },
+            Figure::InStart,
+            Figure::InStart,
+        ];
+
+        assert_eq!(
+            Ok(Figure::OnField { moved: 12 }),
+            simulate_move(&figures, 0, 2, &board, HouseEntry::Loose, None)
+        );
+        assert_eq!(
+            Err(MoveError::Occupied),
+            simulate_move(&figures, 0, 3, &board, HouseEntry::Loose, None)
+        );
+        // Figures of the same Player can stack, once Blockades are allowed
+        assert_eq!(
+            Ok(Figure::OnField { moved: 13 }),
+            simulate_move(&figures, 0, 3, &board, HouseEntry::Loose, Some(&[]))
+        );
+        assert_eq!(
+            Err(MoveError::Blockade),
+            simulate_move(&figures, 0, 4, &board, HouseEntry::Loose, Some(&[12]))
+        );
+    }
+
+    #[test]
+    fn simulate_move_house() {
+        let board = Board::default();
+        let figures = [
+            Figure::OnField { moved: 38 },
+            Figure::InHouse { pos: 1 },
+            Figure::InHouse { pos: 3 },
+            Figure::InStart,
+        ];
+
+        assert_eq!(
+            Ok(Figure::InHouse { pos: 0 }),
+            simulate_move(&figures, 0, 2, &board, HouseEntry::Loose, None)
+        );
+        assert_eq!(
+            Err(MoveError::Occupied),
+            simulate_move(&figures, 0, 3, &board, HouseEntry::Loose, None)
+        );
+        assert_eq!(
+            Err(MoveError::Blocked),
+            simulate_move(&figures, 0, 4, &board, HouseEntry::Loose, None)
+        );
+        assert_eq!(
+            Err(MoveError::Overshoot),
+            simulate_move(&figures, 0, 6, &board, HouseEntry::Loose, None)
+        );
+        assert_eq!(
+            Err(MoveError::Inexact),
+            simulate_move(&figures, 0, 2, &board, HouseEntry::Exact, None)
+        );
+        assert_eq!(
+            Ok(Figure::InHouse { pos: 2 }),
+            simulate_move(&figures, 1, 1, &board, HouseEntry::Exact, None)
+        );
+        assert_eq!(
+            Err(MoveError::Blocked),
+            simulate_move(&figures, 1, 3, &board, HouseEntry::Bounce, None)
+        );
+
+        let bouncing = [
+            Figure::InHouse { pos: 1 },
+            Figure::InStart,
+            Figure::InStart,
+            Figure::InStart,
+        ];
+        assert_eq!(
+            Ok(Figure::InHouse { pos: 2 }),
+            simulate_move(&bouncing, 0, 3, &board, HouseEntry::Bounce, None)
+        );
+    }
+
+    #[test]
+    fn enter_house_near_finish() {
+        let board = Board::default();
+        let enters_house = |figures: &[Figure], index, roll, house_entry| {
+            matches!(
+                simulate_move(figures, index, roll, &board, house_entry, None),
+                Ok(Figure::InHouse { .. })
+            )
+        };
+        let near_finish = [
+            Figure::OnField { moved: 38 },
+            Figure::InHouse { pos: 3 },
+            Figure::OnField { moved: 20 },
+            Figure::InStart,
+        ];
+
+        // Loose: any free Position in the House can be reached
+        assert!(!enters_house(&near_finish, 0, 1, HouseEntry::Loose));
+        assert!(enters_house(&near_finish, 0, 2, HouseEntry::Loose));
+        assert!(enters_house(&near_finish, 0, 4, HouseEntry::Loose));
+        assert!(!enters_house(&near_finish, 0, 5, HouseEntry::Loose));
+        assert!(!enters_house(&near_finish, 0, 6, HouseEntry::Loose));
+
+        // Exact: only the furthest free Position in the House can be reached
+        assert!(!enters_house(&near_finish, 0, 2, HouseEntry::Exact));
+        assert!(enters_house(&near_finish, 0, 4, HouseEntry::Exact));
+
+        // Bounce: Figures bounce back from the End of the House, but can not jump over others
+        assert!(!enters_house(&near_finish, 0, 6, HouseEntry::Bounce));
+        let bouncing = [
+            Figure::OnField { moved: 38 },
+            Figure::InStart,
+            Figure::InStart,
+            Figure::InStart,
+        ];
+        assert!(enters_house(&bouncing, 0, 6, HouseEntry::Bounce));
+
+        // Figures far away from the House or in the Start can not enter it
+        assert!(!enters_house(&near_finish, 2, 6, HouseEntry::Loose));
+        assert!(!enters_house(&near_finish, 3, 6, HouseEntry::Loose));
+    }
+
+    #[test]
+    fn roll_history_is_capped() {
+        let mut player = GamePlayer::new("test".to_string(), ((), ()));
+
+        for value in 1..=ROLL_HISTORY_SIZE + 2 {
+            player.record_roll(value);
+        }
+
+        let expected: Vec<_> = (3..=ROLL_HISTORY_SIZE + 2).collect();
+        assert_eq!(expected, player.recent_rolls());
+    }
 }
@@ -0,0 +1,185 @@
+use std::{
+    fmt::Debug,
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::{Duration, SystemTime},
+};
+
+use axum::extract::ws::Message;
+use futures::{Sink, Stream};
+use rand::Rng;
+use serde_derive::{Deserialize, Serialize};
+
+use crate::Game;
+
+/// How long the Entries of a Game are kept in a persisted Registry. No Game survives a Restart of
+/// the Server, so older Entries are dropped when the Registry is loaded
+pub const MAX_ENTRY_AGE: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// The Rejoin-Information of a single Player in a Game
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RejoinEntry {
+    pub game: uuid::Uuid,
+    pub player: usize,
+    pub code: uuid::Uuid,
+    pub name: String,
+    /// When the Game was registered, in Seconds since the Unix-Epoch
+    #[serde(default)]
+    pub registered: u64,
+}
+
+/// A Registry of all the Rejoin-Codes handed out to Players, which can optionally be persisted to
+/// a File so that it survives a Restart of the Server
+#[derive(Debug, Default)]
+pub struct RejoinRegistry {
+    path: Option<PathBuf>,
+    entries: Vec<RejoinEntry>,
+    /// The Number of Writes handed out so far
+    generation: u64,
+    /// The Generation of the last Write to the File
+    written: Arc<tokio::sync::Mutex<u64>>,
+}
+
+/// A Write of the Registry to its File, which is returned by every Change to the Registry so that
+/// it can be done after releasing any Lock on the Registry
+#[must_use]
+#[derive(Debug)]
+pub struct RegistryWrite {
+    path: PathBuf,
+    entries: Vec<RejoinEntry>,
+    generation: u64,
+    written: Arc<tokio::sync::Mutex<u64>>,
+}
+
+impl RegistryWrite {
+    /// Writes the Entries to the File, unless a more recent Write of the Registry already
+    /// happened, so that the File never goes back to an older Version
+    pub async fn write(self) -> std::io::Result<()> {
+        let mut written = self.written.lock().await;
+        if *written >= self.generation {
+            return Ok(());
+        }
+
+        let content = serde_json::to_vec(&self.entries)?;
+        tokio::fs::write(&self.path, content).await?;
+        *written = self.generation;
+
+        Ok(())
+    }
+}
+
+/// The current Time in Seconds since the Unix-Epoch
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default()
+}
+
+impl RejoinRegistry {
+    /// Creates a new empty Registry, which is only kept in Memory
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Loads the Registry from the given File, which is then also updated whenever new Entries
+    /// are registered.
+    ///
+    /// A File that does not exist yet is treated like an empty Registry and all the Entries that
+    /// are older than [`MAX_ENTRY_AGE`] are dropped.
+    pub fn load<P>(path: P) -> std::io::Result<Self>
+    where
+        P: Into<PathBuf>,
+    {
+        let path = path.into();
+
+        let mut entries: Vec<RejoinEntry> = match std::fs::read(&path) {
+            Ok(content) => serde_json::from_slice(&content)?,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Vec::new(),
+            Err(e) => return Err(e),
+        };
+        let oldest = now().saturating_sub(MAX_ENTRY_AGE.as_secs());
+        entries.retain(|e| e.registered >= oldest);
+
+        Ok(Self {
+            path: Some(path),
+            entries,
+            ..Default::default()
+        })
+    }
+
+    /// The Path of the File the Registry is persisted to
+    pub fn path(&self) -> Option<&Path> {
+        self.path.as_deref()
+    }
+
+    /// All the Entries in the Registry
+    pub fn entries(&self) -> &[RejoinEntry] {
+        &self.entries
+    }
+
+    /// Registers the Rejoin-Codes of all the Players in the given Game.
+    ///
+    /// Returns the Write to persist the Registry, if it is backed by a File
+    pub fn register<R, SI, ST>(&mut self, game: &Game<R, SI, ST>) -> Option<RegistryWrite>
+    where
+        R: Rng,
+        SI: Sink<Message>,
+        <SI as futures::Sink<Message>>::Error: Debug,
+        ST: Stream<Item = Result<Message, axum::Error>>,
+    {
+        let registered = now();
+        self.entries.extend(
+            game.players
+                .iter()
+                .enumerate()
+                .map(|(player, p)| RejoinEntry {
+                    game: game.id(),
+                    player,
+                    code: p.rejoin_code(),
+                    name: p.name.clone(),
+                    registered,
+                }),
+        );
+
+        self.save()
+    }
+
+    /// Finds the Entry for the given Rejoin-Code in the Game
+    pub fn find(&self, game: uuid::Uuid, code: uuid::Uuid) -> Option<&RejoinEntry> {
+        self.entries
+            .iter()
+            .find(|e| e.game == game && e.code == code)
+    }
+
+    /// Checks if the Registry contains any Entry for the Game
+    pub fn contains_game(&self, game: uuid::Uuid) -> bool {
+        self.entries.iter().any(|e| e.game == game)
+    }
+
+    /// Removes all the Entries of the finished Game.
+    ///
+    /// Returns the Write to persist the Registry, if it is backed by a File and changed
+    pub fn remove_game(&mut self, game: uuid::Uuid) -> Option<RegistryWrite> {
+        let before = self.entries.len();
+        self.entries.retain(|e| e.game != game);
+        if self.entries.len() == before {
+            return None;
+        }
+
+        self.save()
+    }
+
+    /// The Write of the current Entries to the File of the Registry, if it is backed by one
+    fn save(&mut self) -> Option<RegistryWrite> {
+        let path = self.path.clone()?;
+
+        self.generation += 1;
+        Some(RegistryWrite {
+            path,
+            entries: self.entries.clone(),
+            generation: self.generation,
+            written: self.written.clone(),
+        })
+    }
+}
@@ -0,0 +1,151 @@
+use std::fmt::Debug;
+
+use axum::extract::ws::Message;
+use futures::{Sink, Stream};
+use rand::Rng;
+
+use crate::{Figure, Game, GameEvent, MoveError};
+
+/// The Reasons for why a History of Events could not be replayed, which all contain the Index of
+/// the offending Event
+#[derive(Debug, PartialEq)]
+pub enum ReplayError {
+    /// The Event refers to a Player or Figure that does not exist in the Game
+    Unknown { index: usize },
+    /// The rolled Value is not between 1 and 6
+    InvalidRoll { index: usize },
+    /// The Player moved a Figure without rolling for it first
+    NotRolled { index: usize },
+    /// The moved Figure was not where the Event claims it started from
+    WrongStart { index: usize },
+    /// The Figure could not legally be moved with the rolled Value
+    IllegalMove { index: usize, reason: MoveError },
+    /// The Figure ended up somewhere else than the Event claims
+    WrongTarget { index: usize },
+    /// The captured Figure did not share the Field with the last moved Figure of the Player
+    IllegalCapture { index: usize },
+}
+
+impl<R, SI, ST> Game<R, SI, ST>
+where
+    R: Rng,
+    SI: Sink<Message>,
+    <SI as futures::Sink<Message>>::Error: Debug,
+    ST: Stream<Item = Result<Message, axum::Error>>,
+{
+    /// Replays the given Events on this fresh Game, while checking that every Event is legal with
+    /// the Rules of the Game, which results in the same Board as the Game the Events were
+    /// recorded in.
+    ///
+    /// A Figure can only be moved by the Value its Player rolled last, except for being sent back
+    /// to the Start for rolling too many 6s in a Row, and can only capture the Figures sharing its
+    /// new Field. Afterwards the Player of the last Event is the current Player.
+    ///
+    /// Every Turn that ended before the last Event is counted, like in the Game the Events were
+    /// recorded in.
+    pub fn replay(mut self, events: &[GameEvent]) -> Result<Self, ReplayError> {
+        // The unused Roll of the current Player and the Figure they moved last
+        let mut roll = None;
+        let mut last_moved = None;
+        // Whether the last Event ended the Turn of the current Player
+        let mut turn_over = false;
+
+        for (index, event) in events.iter().enumerate() {
+            let player = match event {
+                GameEvent::Rolled { player, .. }
+                | GameEvent::Moved { player, .. }
+                | GameEvent::Captured { by: player, .. }
+                | GameEvent::TurnSkipped { player } => *player,
+            };
+            if player >= self.players.len() {
+                return Err(ReplayError::Unknown { index });
+            }
+            // The next Turn only starts with its first Roll, as Captures still belong to the Move
+            let next_turn = turn_over && matches!(event, GameEvent::Rolled { .. });
+            if player != self.next_player || next_turn {
+                if index > 0 {
+                    self.turns += 1;
+                }
+                self.next_player = player;
+                roll = None;
+                last_moved = None;
+                turn_over = false;
+            }
+
+            match event {
+                GameEvent::Rolled { value, .. } => {
+                    if !(1..=6).contains(value) {
+                        return Err(ReplayError::InvalidRoll { index });
+                    }
+
+                    self.record_roll(*value);
+                    self.players[player].record_roll(*value);
+                    roll = Some(*value);
+                }
+                GameEvent::Moved {
+                    figure, from, to, ..
+                } => {
+                    let current = self.players[player]
+                        .figures
+                        .get(*figure)
+                        .ok_or(ReplayError::Unknown { index })?;
+                    if current != from {
+                        return Err(ReplayError::WrongStart { index });
+                    }
+
+                    let sent_home = self.too_many_sixes_send_home && to == &Figure::InStart;
+                    let value = if sent_home {
+                        None
+                    } else {
+                        let value = roll.take().ok_or(ReplayError::NotRolled { index })?;
+                        let (target, _) = self
+                            .preview_move(*figure, value)
+                            .map_err(|reason| ReplayError::IllegalMove { index, reason })?;
+                        if &target != to {
+                            return Err(ReplayError::WrongTarget { index });
+                        }
+                        Some(value)
+                    };
+
+                    let moving = &mut self.players[player];
+                    moving.figures[*figure] = to.clone();
+                    if !moving.is_done() && moving.check_done() {
+                        self.ranking.push(player);
+                    }
+                    last_moved = Some(*figure);
+
+                    // Only a 6 lets the Player roll again, unless they just finished
+                    if let Some(value) = value {
+                        turn_over = value != 6 || self.players[player].is_done();
+                    }
+                }
+                GameEvent::Captured {
+                    player: victim,
+                    figure,
+                    ..
+                } => {
+                    let captured = last_moved
+                        .and_then(|moved| self.players[player].figures.get(moved))
+                        .map(|moved| self.captures_at(player, moved))
+                        .unwrap_or_default();
+                    if !captured.contains(&(*victim, *figure)) {
+                        return Err(ReplayError::IllegalCapture { index });
+                    }
+
+                    self.penalize(*victim, *figure);
+                    if self.extra_turn_on_capture && !self.players[player].is_done() {
+                        turn_over = false;
+                    }
+                }
+                GameEvent::TurnSkipped { .. } => {
+                    roll = None;
+                    turn_over = true;
+                }
+            };
+
+            self.history.push(event.clone());
+        }
+
+        Ok(self)
+    }
+}
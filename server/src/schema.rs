@@ -0,0 +1,21 @@
+//! The JSON Schema of the Protocol between the Server and the Clients, so Clients can generate
+//! their Types from it instead of writing them by hand
+
+use std::path::Path;
+
+use crate::{GameRequest, GameResponse};
+
+/// The JSON Schema of all the Requests and Responses, with the Schema for the Requests in the
+/// `GameRequest` Field and the one for the Responses in the `GameResponse` Field
+pub fn protocol_schema() -> serde_json::Value {
+    serde_json::json!({
+        "GameRequest": schemars::schema_for!(GameRequest),
+        "GameResponse": schemars::schema_for!(GameResponse),
+    })
+}
+
+/// Writes the [`protocol_schema`] as pretty printed JSON to the given File
+pub fn write_protocol_schema(path: &Path) -> std::io::Result<()> {
+    let content = serde_json::to_string_pretty(&protocol_schema())?;
+    std::fs::write(path, content)
+}
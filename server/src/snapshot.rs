@@ -0,0 +1,112 @@
+use std::fmt::Debug;
+
+use axum::extract::ws::Message;
+use futures::{
+    stream::{SplitSink, SplitStream},
+    Sink, Stream,
+};
+use rand::Rng;
+use serde_derive::{Deserialize, Serialize};
+
+use crate::{Figure, Game, GameError, GamePlayer};
+
+/// A serializable Snapshot of a running Game, which can be used to restore the Game later on
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GameSnapshot {
+    pub id: uuid::Uuid,
+    pub players: Vec<PlayerSnapshot>,
+    pub next_player: usize,
+    pub ranking: Vec<usize>,
+}
+
+/// The serializable Part of a single Player in a [`GameSnapshot`]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PlayerSnapshot {
+    pub name: String,
+    pub figures: [Figure; 4],
+    pub rejoin_code: uuid::Uuid,
+}
+
+/// A read-only View of a single Player, which does not depend on the Sockets of the Player
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PlayerView {
+    pub name: String,
+    pub figures: [Figure; 4],
+    pub done: bool,
+}
+
+impl<R, SI, ST> Game<R, SI, ST>
+where
+    R: Rng,
+    SI: Sink<Message>,
+    <SI as futures::Sink<Message>>::Error: Debug,
+    ST: Stream<Item = Result<Message, axum::Error>>,
+{
+    /// Creates a Snapshot of the current State of the Game
+    pub fn snapshot(&self) -> GameSnapshot {
+        GameSnapshot {
+            id: self.id(),
+            players: self
+                .players
+                .iter()
+                .map(|p| PlayerSnapshot {
+                    name: p.name.clone(),
+                    figures: p.figures.clone(),
+                    rejoin_code: p.rejoin_code,
+                })
+                .collect(),
+            next_player: self.next_player,
+            ranking: self.ranking.clone(),
+        }
+    }
+
+    /// Views of all the Players, in the same Order as the Players of the Game
+    pub fn player_views(&self) -> Vec<PlayerView> {
+        self.players
+            .iter()
+            .map(|p| PlayerView {
+                name: p.name.clone(),
+                figures: p.figures.clone(),
+                done: p.is_done(),
+            })
+            .collect()
+    }
+
+    /// Restores a Game from the given Snapshot.
+    ///
+    /// The Sockets can not be stored in the Snapshot, so they need to be provided again, in the
+    /// same Order as the Players in the Snapshot.
+    pub fn restore<IS>(snapshot: GameSnapshot, sockets: IS, rng: R) -> Result<Self, GameError>
+    where
+        IS: IntoIterator<Item = (SplitSink<SI, Message>, SplitStream<ST>)>,
+    {
+        let sockets: Vec<_> = sockets.into_iter().collect();
+        if sockets.len() != snapshot.players.len() {
+            return Err(GameError::Protocol {
+                detail: format!(
+                    "got {} Sockets for the {} Players in the Snapshot",
+                    sockets.len(),
+                    snapshot.players.len()
+                ),
+            });
+        }
+
+        let players = snapshot
+            .players
+            .into_iter()
+            .zip(sockets)
+            .map(|(player, socket)| {
+                let mut n_player = GamePlayer::new(player.name, socket);
+                n_player.rejoin_code = player.rejoin_code;
+                (n_player, player.figures)
+            });
+
+        Ok(Self::from_state(
+            snapshot.id,
+            players,
+            snapshot.next_player,
+            snapshot.ranking,
+            rng,
+        ))
+    }
+}
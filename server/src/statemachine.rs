@@ -3,52 +3,291 @@ use std::fmt::Debug;
 use axum::extract::ws::Message;
 use futures::{Sink, Stream, StreamExt};
 use rand::Rng;
+use tracing::Instrument;
 
-use crate::{Figure, Game, GameError, GameRequest, GameResponse, RejoinMessage};
+use crate::{
+    Figure, Game, GameEndReason, GameError, GameEvent, GamePlayer, GameRequest, GameResponse,
+    RejoinKey, RejoinMessage, TurnSummary,
+};
 
+/// Receives the next Request from the current Player of the Game.
+///
+/// Chat Messages, Requests for the current State or Players and Pauses can arrive at any Time and
+/// are therefore handled directly, without being returned as a Request. The other Players are
+/// listened to as well, so their Chat Messages and Requests for Information are answered right
+/// away, regardless of whose Turn it is, while all their other Requests are queued until their
+/// Turn starts. While waiting, the current Player is regularly pinged and considered to be
+/// disconnected, if nothing was received from them within the Pong-Timeout of the Game.
+///
+/// Any Player can rejoin the Game while waiting, not only the current one. With a Deadline, the
+/// `expired` State is returned once nothing was received until then.
 macro_rules! recv_msg {
-    ($receiver:expr, $prev_state:expr) => {
-        match $receiver.next().await {
-            Some(Ok(msg)) => match msg {
-                Message::Text(t) => t,
-                Message::Close(_) => {
-                    tracing::warn!("Player Disconnected");
-                    return Some(GameState::WaitingForReconnect {
-                        prev_state: $prev_state,
-                    });
+    ($game:expr, $rejoin_rx:expr, $prev_state:expr) => {
+        recv_msg!(
+            $game,
+            $rejoin_rx,
+            $prev_state,
+            None::<std::time::Instant> => *$prev_state
+        )
+    };
+    ($game:expr, $rejoin_rx:expr, $prev_state:expr, $deadline:expr => $expired:expr) => {{
+        let waiting_since = std::time::Instant::now();
+        loop {
+            // A Request received by the previous State is handled first
+            if let Some(req) = $game.pending_request.take() {
+                break req;
+            }
+
+            let current = $game.next_player;
+            // Followed by the Requests the current Player sent before their Turn
+            let (index, request) = match $game.players[current].queued.pop_front() {
+                Some(req) => (current, Ok(req)),
+                None => {
+                    let pong_timeout = $game.pong_timeout;
+                    let wait = match $deadline {
+                        Some(deadline) => {
+                            match deadline.checked_duration_since(std::time::Instant::now()) {
+                                Some(left) if !left.is_zero() => $game.ping_interval.min(left),
+                                _ => return Some($expired),
+                            }
+                        }
+                        None => $game.ping_interval,
+                    };
+                    let player = &mut $game.players[current];
+
+                    if player.last_seen.max(waiting_since).elapsed() > pong_timeout {
+                        tracing::warn!("Player {:?} did not respond in time", player.name);
+                        player.connected = false;
+                        return Some(wait_for_reconnect($game, *$prev_state).await);
+                    }
+
+                    // Rejoins are handled first, so that Players are restored as soon as possible
+                    let received = tokio::select! {
+                        biased;
+                        Some(rejoin) = $rejoin_rx.recv() => {
+                            // The current Player has to be prompted again on their new Connection,
+                            // which is done by restarting the current State
+                            if rejoin_player($game, rejoin).await == Some(current) {
+                                let prev_state = $prev_state;
+                                reprompt_player($game, &prev_state).await;
+                                return Some(*prev_state);
+                            }
+                            continue;
+                        }
+                        r = tokio::time::timeout(
+                            wait,
+                            next_message(&mut $game.players, current),
+                        ) => r,
+                    };
+                    let (index, received) = match received {
+                        Ok(r) => r,
+                        Err(_) => {
+                            if $deadline.is_some_and(|d| std::time::Instant::now() >= d) {
+                                continue;
+                            }
+                            if $game.players[current].ping().await.is_err() {
+                                return Some(wait_for_reconnect($game, *$prev_state).await);
+                            }
+                            continue;
+                        }
+                    };
+                    let player = &mut $game.players[index];
+                    player.last_seen = std::time::Instant::now();
+
+                    let msg_text = match received {
+                        Some(Ok(Message::Text(t))) => t,
+                        Some(Ok(Message::Ping(payload))) => {
+                            if player.pong(payload).await.is_err() && index == current {
+                                return Some(wait_for_reconnect($game, *$prev_state).await);
+                            }
+                            continue;
+                        }
+                        Some(Ok(Message::Pong(_))) => {
+                            continue;
+                        }
+                        Some(Ok(Message::Binary(_))) => {
+                            tracing::warn!("Binary Message from Player {:?}", player.name);
+
+                            let resp = GameResponse::InvalidRequest {
+                                reason: "binary messages are not supported".to_string(),
+                            };
+                            if player.send_resp(&resp).await.is_err() && index == current {
+                                return Some(wait_for_reconnect($game, *$prev_state).await);
+                            }
+                            continue;
+                        }
+                        Some(Ok(Message::Close(_))) | None => {
+                            tracing::warn!("Connection of Player {:?} ended", player.name);
+                            if index == current {
+                                return Some(wait_for_reconnect($game, *$prev_state).await);
+                            }
+                            // The Game only waits for the Player to reconnect once it is their Turn
+                            player.connected = false;
+                            continue;
+                        }
+                        Some(Err(e)) => {
+                            tracing::error!("Error receiving {:?}", e);
+                            if index == current {
+                                return Some(wait_for_reconnect($game, *$prev_state).await);
+                            }
+                            player.connected = false;
+                            continue;
+                        }
+                    };
+
+                    let request = serde_json::from_str::<GameRequest>(&msg_text);
+                    (index, request.map_err(|e| (msg_text, e)))
                 }
-                other => {
-                    todo!("{:?}", other)
+            };
+
+            let request = match request {
+                Ok(r) => r,
+                Err((msg_text, e)) => {
+                    tracing::error!("Error Message({:?}): {:?}", msg_text, e);
+
+                    let resp = GameResponse::InvalidRequest {
+                        reason: format!("malformed request: {}", e),
+                    };
+                    if $game.players[index].send_resp(&resp).await.is_err() && index == current {
+                        return Some(wait_for_reconnect($game, *$prev_state).await);
+                    }
+                    continue;
                 }
-            },
-            Some(Err(e)) => {
-                tracing::error!("Error receiving {:?}", e);
-                return Some(GameState::WaitingForReconnect {
-                    prev_state: $prev_state,
-                });
-            }
-            None => {
-                todo!()
-            }
+            };
+
+            match request {
+                GameRequest::Chat { text } => {
+                    $game.chat(index, text).await;
+                }
+                GameRequest::RequestState => {
+                    // A failed send will be noticed by the next Receive or Send to the Player
+                    let _ = $game.resync_player(index).await;
+                }
+                GameRequest::RollHistory => {
+                    let player = &mut $game.players[index];
+                    let resp = GameResponse::RollHistory {
+                        rolls: player.recent_rolls(),
+                    };
+                    // A failed send will be noticed by the next Receive or Send to the Player
+                    let _ = player.send_resp(&resp).await;
+                }
+                GameRequest::GetRejoinCode => {
+                    let game_id = $game.id();
+                    let player = &mut $game.players[index];
+                    let resp = GameResponse::RejoinCode {
+                        game: game_id,
+                        code: player.rejoin_code(),
+                    };
+                    // A failed send will be noticed by the next Receive or Send to the Player
+                    let _ = player.send_resp(&resp).await;
+                }
+                GameRequest::ListPlayers => {
+                    let resp = $game.roster();
+                    let player = &mut $game.players[index];
+                    // A failed send will be noticed by the next Receive or Send to the Player
+                    let _ = player.send_resp(&resp).await;
+                }
+                GameRequest::SetName { .. } => {
+                    let player = &mut $game.players[index];
+                    let resp = GameResponse::InvalidRequest {
+                        reason: "the name can only be changed before the game starts".to_string(),
+                    };
+                    // A failed send will be noticed by the next Receive or Send to the Player
+                    let _ = player.send_resp(&resp).await;
+                }
+                other if index != current => {
+                    tracing::debug!("Queueing {:?} until the Turn of Player {}", other, index);
+                    $game.players[index].queued.push_back(other);
+                }
+                GameRequest::Pause => {
+                    return Some(pause_game($game, *$prev_state).await);
+                }
+                r => break r,
+            };
         }
-    };
+    }};
 }
 
 #[derive(Debug, PartialEq)]
 pub enum GameState {
-    WaitingForReconnect { prev_state: Box<GameState> },
-    StartTurn { attempt: usize },
-    Rolled { value: usize },
+    WaitingForReconnect {
+        prev_state: Box<GameState>,
+    },
+    /// The current Player paused the Game, which continues with the previous State once they
+    /// resume it
+    Paused {
+        prev_state: Box<GameState>,
+    },
+    StartTurn {
+        attempt: usize,
+    },
+    Rolled {
+        value: usize,
+    },
+    /// The current Player moved a Figure with the rolled Value and can still undo the Move, until
+    /// the Undo-Window of the Game closes and the Game continues with the next State
+    UndoWindow {
+        value: usize,
+        next: Box<GameState>,
+    },
     MoveToNextTurn,
     Done,
 }
 
+/// Runs a single Step of the Game, starting in the given State, and returns the next State or None
+/// once the Game is done.
+///
+/// Every Step runs in a `turn` Span, which carries the current `player` as well as the `attempt`
+/// and the rolled `value`, once they are known
 pub async fn step<R, SI, ST, D>(
     prev: GameState,
     game: &mut Game<R, SI, ST>,
     rejoin_rx: &mut tokio::sync::mpsc::UnboundedReceiver<RejoinMessage<SI, ST>>,
     distr: &mut D,
 ) -> Option<GameState>
+where
+    R: Rng,
+    SI: Sink<Message>,
+    <SI as futures::Sink<Message>>::Error: Debug,
+    ST: Stream<Item = Result<Message, axum::Error>>,
+    D: rand::distributions::Distribution<usize>,
+{
+    let span = tracing::info_span!(
+        "turn",
+        player = game.next_player,
+        attempt = tracing::field::Empty,
+        value = tracing::field::Empty,
+    );
+    match &prev {
+        GameState::StartTurn { attempt } => {
+            span.record("attempt", attempt);
+        }
+        GameState::Rolled { value } => {
+            span.record("value", value);
+        }
+        _ => {}
+    };
+
+    let next = run_step(prev, game, rejoin_rx, distr)
+        .instrument(span)
+        .await;
+    game.paused = matches!(
+        next,
+        Some(GameState::WaitingForReconnect { .. } | GameState::Paused { .. })
+    );
+
+    #[cfg(debug_assertions)]
+    game.assert_invariants();
+
+    next
+}
+
+async fn run_step<R, SI, ST, D>(
+    prev: GameState,
+    game: &mut Game<R, SI, ST>,
+    rejoin_rx: &mut tokio::sync::mpsc::UnboundedReceiver<RejoinMessage<SI, ST>>,
+    distr: &mut D,
+) -> Option<GameState>
 where
     R: Rng,
     SI: Sink<Message>,
@@ -69,160 +308,268 @@ where
     tracing::trace!("Current State {:?}", prev);
 
     let next_state = match prev {
-        GameState::WaitingForReconnect { prev_state } => match rejoin_rx.recv().await {
-            Some((rejoin_key, (tx, rx))) => {
-                let player_index_res = game.players.iter().enumerate().find_map(|(i, p)| {
-                    if p.rejoin_code == rejoin_key {
-                        Some(i)
-                    } else {
-                        None
+        GameState::WaitingForReconnect { prev_state } => {
+            let rejoin = match game.pending_leave {
+                Some(player) => {
+                    let grace = game.reconnect_grace;
+                    match tokio::time::timeout(grace, rejoin_rx.recv()).await {
+                        Ok(rejoin) => rejoin,
+                        Err(_) => {
+                            tracing::debug!("Player {} did not rejoin in time", player);
+
+                            announce_leave(game, player).await;
+                            return Some(GameState::WaitingForReconnect { prev_state });
+                        }
+                    }
+                }
+                None => rejoin_rx.recv().await,
+            };
+            // Nobody was told about the Player leaving, so nobody has to be told about them
+            // rejoining either
+            let quiet = game.pending_leave.is_some();
+
+            match rejoin {
+                Some(rejoin) => match rejoin_player(game, rejoin).await {
+                    Some(_) if !game.players[game.next_player].is_connected() => {
+                        if !quiet {
+                            announce_waiting(game).await;
+                        }
+                        GameState::WaitingForReconnect { prev_state }
                     }
-                });
+                    Some(index) => {
+                        if !quiet {
+                            announce_waiting(game).await;
+                        }
+                        if index == game.next_player {
+                            reprompt_player(game, &prev_state).await;
+                        }
+                        *prev_state
+                    }
+                    None => GameState::WaitingForReconnect { prev_state },
+                },
+                None => {
+                    tracing::warn!("Players can not rejoin anymore");
+                    end_game(game, GameEndReason::Aborted).await
+                }
+            }
+        }
+        GameState::Paused { prev_state } => {
+            let req = recv_msg!(game, rejoin_rx, Box::new(GameState::Paused { prev_state }));
 
-                match player_index_res {
-                    Some(player_index) => {
-                        let rejoined_player = game
-                            .players
-                            .get_mut(player_index)
-                            .expect("We found the index by searching the same array");
+            match req {
+                GameRequest::Resume => {
+                    tracing::debug!("Resuming the Game");
 
-                        rejoined_player.send = tx;
-                        rejoined_player.recv = rx;
+                    let resp = GameResponse::Resumed {
+                        player: game.next_player,
+                    };
+                    // Players that can not be reached will be noticed once the Game continues
+                    let _ = game.broadcast(&resp).await;
+                    game.send_spectators(&resp).await;
 
-                        // We ignore these results because if any of the connections fail again, we will just re-enter this
-                        // state again later on
-                        let _ = game.send_state().await;
-                        let _ = game.indicate_players().await;
+                    reprompt_player(game, &prev_state).await;
+                    *prev_state
+                }
+                other => {
+                    tracing::warn!("Unexpected {:?} while paused", other);
 
-                        *prev_state
-                    }
-                    None => {
-                        tracing::warn!("Unknown Rejoin Key");
-                        GameState::WaitingForReconnect { prev_state }
+                    let resp = GameResponse::InvalidRequest {
+                        reason: "game is paused".to_string(),
+                    };
+                    let current_player = &mut game.players[game.next_player];
+                    if current_player.send_resp(&resp).await.is_err() {
+                        return Some(
+                            wait_for_reconnect(game, GameState::Paused { prev_state }).await,
+                        );
                     }
+
+                    GameState::Paused { prev_state }
                 }
             }
-            None => {
-                todo!()
-            }
-        },
+        }
         GameState::StartTurn { attempt } => {
+            if current_player.is_done() {
+                tracing::debug!(
+                    "Skipping Player {} as they are already done",
+                    game.next_player
+                );
+                return Some(GameState::MoveToNextTurn);
+            }
+
+            game.send_spectators(&GameResponse::ActivePlayer {
+                player: game.next_player,
+            })
+            .await;
+
+            let current_player = &mut game.players[game.next_player];
+            if !current_player.is_connected() {
+                tracing::debug!("Waiting for Player {} to reconnect", game.next_player);
+                return Some(wait_for_reconnect(game, prev).await);
+            }
+
             match current_player.send_resp(&GameResponse::Turn).await {
                 Ok(_) => {}
                 Err(e) => match e {
                     GameError::Disconnect => {
                         tracing::warn!("Player disconnected");
-                        return Some(GameState::WaitingForReconnect {
-                            prev_state: Box::new(prev),
-                        });
+                        return Some(wait_for_reconnect(game, prev).await);
                     }
-                    GameError::Other(reason) => {
-                        todo!("Other Error: {:?}", reason)
+                    other => {
+                        tracing::error!("Could not send the Turn: {:?}", other);
+                        return Some(end_game(game, GameEndReason::Aborted).await);
                     }
                 },
             };
 
-            let msg_text = recv_msg!(current_player.recv, Box::new(prev));
-
-            let req: GameRequest = match serde_json::from_str(&msg_text) {
-                Ok(r) => r,
-                Err(e) => {
-                    tracing::error!("Error Message({:?}): {:?}", msg_text, e);
-                    todo!()
-                }
-            };
+            let req = recv_msg!(game, rejoin_rx, Box::new(prev));
+            let current_player = &mut game.players[game.next_player];
 
             match req {
                 GameRequest::Roll => {
                     tracing::trace!("Rolling for Player {:?}", current_player.name);
 
+                    let attempts_left = (!current_player.has_moveable_figure(&game.board))
+                        .then(|| game.start_attempts.saturating_sub(attempt));
+
                     let value: usize = distr.sample(&mut game.rng);
+                    tracing::Span::current().record("value", value);
+                    game.record_roll(value);
 
-                    tracing::trace!("Rolled {} for Player {:?}", value, current_player.name);
+                    let TurnSummary {
+                        legal_moves,
+                        forced_figure,
+                        ..
+                    } = game.turn_summary(Some(value));
+                    let blockades = game.blockades_for(game.next_player);
 
-                    let other_figures_instart = current_player
-                        .figures
-                        .iter()
-                        .any(|f| matches!(f, Figure::InStart));
+                    let current_player = &mut game.players[game.next_player];
+                    current_player.record_roll(value);
+                    game.history.push(GameEvent::Rolled {
+                        player: game.next_player,
+                        value,
+                    });
 
-                    let can_move = current_player.has_figures_on_field()
-                        && !(value == 6 && current_player.has_figures_in_start())
-                        && !current_player.figures.iter().any(|f| match f {
-                            Figure::OnField { moved } => *moved == 0 && other_figures_instart,
-                            _ => false,
-                        });
+                    tracing::trace!("Rolled {} for Player {:?}", value, current_player.name);
+
+                    let too_many_sixes = game
+                        .max_sixes_in_row
+                        .is_some_and(|max| current_player.consecutive_sixes() > max);
+                    let can_move =
+                        !too_many_sixes && forced_figure.is_none() && !legal_moves.is_empty();
 
-                    let resp = GameResponse::Rolled { value, can_move };
+                    let resp = GameResponse::Rolled {
+                        value,
+                        can_move,
+                        attempts_left,
+                    };
+                    // The Roll counts even if the Player can not be told about it, otherwise
+                    // they could just reconnect to roll again
                     match current_player.send_resp(&resp).await {
                         Ok(_) => {}
                         Err(e) => match e {
                             GameError::Disconnect => {
-                                return Some(prev);
+                                tracing::warn!("Player disconnected after rolling");
                             }
-                            GameError::Other(reason) => {
-                                tracing::error!("Error sending Response {:?}", reason);
-                                todo!()
+                            other => {
+                                tracing::error!("Could not send the Roll: {:?}", other);
+                                return Some(end_game(game, GameEndReason::Aborted).await);
                             }
                         },
                     };
 
-                    let figure_startfield_index_res = current_player
-                        .figures
-                        .iter()
-                        .enumerate()
-                        .find(|(_, f)| match f {
-                            Figure::OnField { moved } => *moved == 0 && other_figures_instart,
-                            _ => false,
-                        });
+                    game.send_spectators(&resp).await;
+                    let current_player = &mut game.players[game.next_player];
 
-                    if let Some(findex) = figure_startfield_index_res {
-                        if current_player.move_figure(findex.0, value).is_none() {
-                            tracing::warn!("Figure could not be moved");
-                        }
+                    if current_player.is_connected() {
+                        let options = GameResponse::MoveOptions {
+                            value,
+                            figures: if can_move { legal_moves } else { Vec::new() },
+                        };
+                        // A failed send is handled once the Roll is resolved
+                        let _ = current_player.send_resp(&options).await;
+                    }
 
-                        game.check_move(game.next_player);
-                        game.send_state().await.unwrap();
+                    if too_many_sixes {
+                        tracing::debug!(
+                            "Player {:?} rolled {} 6s in a Row",
+                            current_player.name,
+                            current_player.consecutive_sixes()
+                        );
 
-                        if value == 6 {
-                            return Some(GameState::StartTurn { attempt: 0 });
-                        } else {
-                            return Some(GameState::MoveToNextTurn);
+                        let last_moved = game
+                            .too_many_sixes_send_home
+                            .then_some(game.moved_this_turn)
+                            .flatten();
+                        if let Some(findex) = last_moved {
+                            let current_player = &mut game.players[game.next_player];
+                            let from = std::mem::replace(
+                                &mut current_player.figures[findex],
+                                Figure::InStart,
+                            );
+                            game.history.push(GameEvent::Moved {
+                                player: game.next_player,
+                                figure: findex,
+                                from,
+                                to: Figure::InStart,
+                            });
                         }
+
+                        game.history.push(GameEvent::TurnSkipped {
+                            player: game.next_player,
+                        });
+                        return Some(send_state_then(game, GameState::MoveToNextTurn).await);
                     }
 
-                    if value == 6 {
-                        if let Some(index) =
-                            current_player
-                                .figures
-                                .iter()
-                                .enumerate()
-                                .find_map(|(i, f)| match f {
-                                    Figure::InStart => Some(i),
-                                    _ => None,
-                                })
-                        {
-                            *current_player
-                                .figures
-                                .get_mut(index)
-                                .expect("We just got the index by iterating over the list") =
-                                Figure::OnField { moved: 0 };
-
-                            tracing::trace!(
-                                "Moved Figure {} out of Start for Player {:?}",
-                                index,
-                                current_player.name
-                            );
+                    if let Some(findex) = forced_figure {
+                        let from = current_player.figures[findex].clone();
+                        let to = current_player
+                            .move_figure(
+                                findex,
+                                value,
+                                &game.board,
+                                game.house_entry,
+                                blockades.as_deref(),
+                            )
+                            .expect("The Figure was part of the legal Moves")
+                            .clone();
 
-                            game.check_move(game.next_player);
-                            game.send_state().await.unwrap();
+                        tracing::trace!(
+                            "Moved Figure {} from {:?} to {:?} for Player {:?}",
+                            findex,
+                            from,
+                            to,
+                            current_player.name
+                        );
 
+                        game.history.push(GameEvent::Moved {
+                            player: game.next_player,
+                            figure: findex,
+                            from,
+                            to,
+                        });
+                        game.moved_this_turn = Some(findex);
+                        let extra_turn = capture(game, findex).await;
+
+                        let next = if value == 6 || extra_turn {
                             GameState::StartTurn { attempt: 0 }
                         } else {
-                            GameState::Rolled { value }
+                            GameState::MoveToNextTurn
+                        };
+                        return Some(send_state_then(game, next).await);
+                    }
+
+                    if value == 6 || current_player.has_moveable_figure(&game.board) {
+                        if !current_player.is_connected() {
+                            // The Player is prompted for the rolled Value again once they rejoin
+                            return Some(
+                                wait_for_reconnect(game, GameState::Rolled { value }).await,
+                            );
                         }
-                    } else if current_player.has_moveable_figure() {
                         GameState::Rolled { value }
-                    } else if attempt >= 2 {
+                    } else if attempt >= game.start_attempts {
+                        game.history.push(GameEvent::TurnSkipped {
+                            player: game.next_player,
+                        });
                         GameState::MoveToNextTurn
                     } else {
                         GameState::StartTurn {
@@ -233,48 +580,213 @@ where
                 other => {
                     tracing::error!("Unexpected {:?}", other);
 
+                    let resp = GameResponse::InvalidRequest {
+                        reason: "expected Roll".to_string(),
+                    };
+                    // A failed send will be noticed once the Turn is started again
+                    let _ = current_player.send_resp(&resp).await;
+
                     GameState::StartTurn { attempt }
                 }
             }
         }
         GameState::Rolled { value } => {
-            let msg_text = recv_msg!(current_player.recv, Box::new(GameState::Rolled { value }));
+            if game.turn_summary(Some(value)).no_moves {
+                tracing::debug!("No Figure can be moved by {}", value);
 
-            let req: GameRequest = match serde_json::from_str(&msg_text) {
-                Ok(r) => r,
-                Err(e) => {
-                    tracing::error!("Error Message({:?}): {:?}", msg_text, e);
-                    todo!()
+                if value == 6 {
+                    return Some(GameState::StartTurn { attempt: 0 });
+                } else {
+                    game.history.push(GameEvent::TurnSkipped {
+                        player: game.next_player,
+                    });
+                    return Some(GameState::MoveToNextTurn);
+                }
+            }
+
+            let req = match recv_msg!(game, rejoin_rx, Box::new(GameState::Rolled { value })) {
+                GameRequest::MoveTo { absolute } => {
+                    match game.figures_moving_to(absolute, value).as_slice() {
+                        [figure] => GameRequest::Move { figure: *figure },
+                        candidates => {
+                            tracing::warn!("Can not move to {}: {:?}", absolute, candidates);
+
+                            let reason = if candidates.is_empty() {
+                                "no figure can move to this field"
+                            } else {
+                                "multiple figures can move to this field"
+                            };
+                            let resp = GameResponse::InvalidRequest {
+                                reason: reason.to_string(),
+                            };
+                            let current_player = &mut game.players[game.next_player];
+                            if current_player.send_resp(&resp).await.is_err() {
+                                return Some(
+                                    wait_for_reconnect(game, GameState::Rolled { value }).await,
+                                );
+                            }
+
+                            return Some(GameState::Rolled { value });
+                        }
+                    }
                 }
+                other => other,
             };
+            let undo = game.undo_window.map(|_| {
+                let figures = game.players.iter().map(|p| p.figures.clone()).collect();
+                (figures, game.history.len(), game.moved_this_turn)
+            });
+            let blockades = game.blockades_for(game.next_player);
+            let current_player = &mut game.players[game.next_player];
 
             match req {
+                GameRequest::Move { figure } if figure >= current_player.figures.len() => {
+                    tracing::warn!("Figure {:?} is out of range", figure);
+
+                    let resp = GameResponse::InvalidRequest {
+                        reason: "figure index out of range".to_string(),
+                    };
+                    if current_player.send_resp(&resp).await.is_err() {
+                        return Some(wait_for_reconnect(game, GameState::Rolled { value }).await);
+                    }
+
+                    GameState::Rolled { value }
+                }
+                GameRequest::PreviewMove { figure } => {
+                    let resp = match game.preview_move(figure, value) {
+                        Ok((to, captures)) => GameResponse::MovePreview { to, captures },
+                        Err(e) => {
+                            tracing::warn!("Could not preview Move: {:?}", e);
+                            GameResponse::InvalidRequest {
+                                reason: "figure can not be moved".to_string(),
+                            }
+                        }
+                    };
+                    let current_player = &mut game.players[game.next_player];
+                    if current_player.send_resp(&resp).await.is_err() {
+                        return Some(wait_for_reconnect(game, GameState::Rolled { value }).await);
+                    }
+
+                    GameState::Rolled { value }
+                }
                 GameRequest::Move { figure } => {
                     tracing::trace!("Move Figure {:?} by Rolled {:?}", figure, value);
 
-                    if current_player.move_figure(figure, value).is_none() {
-                        tracing::warn!("Could not move Figure");
-                    }
-                    let player_done = current_player.check_done();
+                    let from = current_player.figures.get(figure).cloned();
+                    match current_player.move_figure(
+                        figure,
+                        value,
+                        &game.board,
+                        game.house_entry,
+                        blockades.as_deref(),
+                    ) {
+                        Ok(to) => {
+                            let to = to.clone();
+                            game.history.push(GameEvent::Moved {
+                                player: game.next_player,
+                                figure,
+                                from: from.expect("The Figure could be moved, so it exists"),
+                                to,
+                            });
+                            game.moved_this_turn = Some(figure);
+                        }
+                        Err(e) => {
+                            tracing::warn!("Could not move Figure: {:?}", e);
+
+                            let resp = GameResponse::ChooseAnotherFigure { value };
+                            if current_player.send_resp(&resp).await.is_err() {
+                                return Some(
+                                    wait_for_reconnect(game, GameState::Rolled { value }).await,
+                                );
+                            }
 
-                    game.check_move(game.next_player);
+                            return Some(GameState::Rolled { value });
+                        }
+                    };
+                    let player_done = !game.players[game.next_player].has_figures_left();
 
-                    game.send_state().await.unwrap();
+                    let extra_turn = capture(game, figure).await;
 
-                    if value == 6 && !player_done {
+                    let next = if (value == 6 || extra_turn) && !player_done {
                         GameState::StartTurn { attempt: 0 }
                     } else {
                         GameState::MoveToNextTurn
-                    }
+                    };
+                    let next = match undo {
+                        Some(undo) => {
+                            game.undo = Some(undo);
+                            GameState::UndoWindow {
+                                value,
+                                next: Box::new(next),
+                            }
+                        }
+                        None => next,
+                    };
+                    send_state_then(game, next).await
                 }
                 other => {
                     tracing::error!("Unexpected {:?}", other);
 
+                    let resp = GameResponse::InvalidRequest {
+                        reason: "expected Move".to_string(),
+                    };
+                    if current_player.send_resp(&resp).await.is_err() {
+                        return Some(wait_for_reconnect(game, GameState::Rolled { value }).await);
+                    }
+
                     GameState::Rolled { value }
                 }
             }
         }
+        GameState::UndoWindow { value, next } => {
+            let deadline = std::time::Instant::now() + game.undo_window.unwrap_or_default();
+            let req = recv_msg!(
+                game,
+                rejoin_rx,
+                Box::new(GameState::UndoWindow { value, next }),
+                Some(deadline) => {
+                    game.undo = None;
+                    *next
+                }
+            );
+
+            match (req, game.undo.take()) {
+                (GameRequest::Undo, Some((figures, history_len, moved_this_turn))) => {
+                    tracing::debug!("Undoing the last Move of Player {}", game.next_player);
+
+                    for (player, figures) in game.players.iter_mut().zip(figures) {
+                        player.figures = figures;
+                    }
+                    game.history.truncate(history_len);
+                    game.moved_this_turn = moved_this_turn;
+
+                    let next = send_state_then(game, GameState::Rolled { value }).await;
+                    reprompt_player(game, &next).await;
+                    next
+                }
+                (other, _) => {
+                    // Anything else commits the Move right away and is handled by the next State,
+                    // as long as it is still the Turn of the Player
+                    if matches!(*next, GameState::StartTurn { .. }) {
+                        game.pending_request = Some(other);
+                    } else {
+                        tracing::warn!("Unexpected {:?} after the Move", other);
+
+                        let resp = GameResponse::InvalidRequest {
+                            reason: "the move was already committed".to_string(),
+                        };
+                        // A failed send will be noticed once it is the Turn of the Player again
+                        let _ = game.players[game.next_player].send_resp(&resp).await;
+                    }
+
+                    *next
+                }
+            }
+        }
         GameState::MoveToNextTurn => {
+            current_player.consecutive_sixes = 0;
+            game.moved_this_turn = None;
+
             if !current_player.is_done() && current_player.check_done() {
                 tracing::trace!("Player {:?} is Done", game.next_player);
 
@@ -283,38 +795,391 @@ where
                 let done_msg = GameResponse::PlayerDone {
                     player: game.next_player,
                 };
-                for player in game.players.iter_mut() {
-                    player.send_resp(&done_msg).await.unwrap();
+                // A Player that could not be reached will be noticed during the next Turns and
+                // the Ranking also contains this Information
+                let _ = game.broadcast(&done_msg).await;
+                game.send_spectators(&done_msg).await;
+
+                if game.ranking.len() == 1 {
+                    let winner_msg = GameResponse::Winner {
+                        player: game.next_player,
+                    };
+                    let _ = game.broadcast(&winner_msg).await;
+                    game.send_spectators(&winner_msg).await;
                 }
             }
 
-            if game.is_done() {
-                tracing::debug!("Game is Done");
+            game.turns += 1;
+            let capped = game.max_turns.is_some_and(|max| game.turns >= max);
+            if capped && !game.is_done() {
+                tracing::debug!("Game reached the maximum of {} Turns", game.turns);
 
-                let done_msg = GameResponse::GameDone {
-                    ranking: game.ranking.clone(),
-                };
-                for player in game.players.iter_mut() {
-                    player.send_resp(&done_msg).await.unwrap();
-                }
+                end_game(game, GameEndReason::TurnCapReached).await
+            } else if game.is_done() {
+                end_game(game, GameEndReason::AllFinished).await
+            } else if let Some(team) = game.finished_team() {
+                tracing::debug!("Team {} finished", team);
 
-                GameState::Done
-            } else {
-                for _ in 0..game.players.len() {
-                    game.next_player = (game.next_player + 1) % game.players.len();
-
-                    if !game.players[game.next_player].is_done() {
-                        break;
-                    }
-                }
+                end_game(game, GameEndReason::TeamFinished).await
+            } else if let Some(next) = game.next_movable_player(game.next_player) {
+                game.next_player = next;
 
                 GameState::StartTurn { attempt: 0 }
+            } else {
+                tracing::warn!("Every Player is skipped, so nobody can take the next Turn");
+
+                end_game(game, GameEndReason::Aborted).await
             }
         }
         GameState::Done => {
-            return None;
+            if !game.rematch || !wait_for_rematch(game).await {
+                return None;
+            }
+
+            tracing::debug!("Starting a Rematch");
+            game.reset();
+            send_state_then(game, GameState::StartTurn { attempt: 0 }).await
         }
     };
 
     Some(next_state)
 }
+
+/// Captures all the Figures hit by the given Figure of the current Player, records the Captures in
+/// the History and informs everyone about them.
+///
+/// Returns whether the Player gets another Turn for capturing any Figure
+async fn capture<R, SI, ST>(game: &mut Game<R, SI, ST>, figure: usize) -> bool
+where
+    R: Rng,
+    SI: Sink<Message>,
+    <SI as futures::Sink<Message>>::Error: Debug,
+    ST: Stream<Item = Result<Message, axum::Error>>,
+{
+    let captures = game.check_move(game.next_player, figure);
+    game.history
+        .extend(captures.iter().map(|(player, figure)| GameEvent::Captured {
+            by: game.next_player,
+            player: *player,
+            figure: *figure,
+        }));
+    game.send_captures(game.next_player, &captures).await;
+
+    game.extra_turn_on_capture && !captures.is_empty()
+}
+
+/// Ends the Game for the given Reason and informs everyone about the final Ranking.
+///
+/// Players that are not done yet are ranked by their Progress
+async fn end_game<R, SI, ST>(game: &mut Game<R, SI, ST>, reason: GameEndReason) -> GameState
+where
+    R: Rng,
+    SI: Sink<Message>,
+    <SI as futures::Sink<Message>>::Error: Debug,
+    ST: Stream<Item = Result<Message, axum::Error>>,
+{
+    tracing::debug!("Game is Done: {:?}", reason);
+
+    if !game.is_done() {
+        let ranking = game.compute_ranking();
+        for index in ranking.iter().copied() {
+            game.players[index].done = true;
+        }
+        game.ranking = ranking;
+    }
+
+    let done_msg = GameResponse::GameDone {
+        ranking: game.ranking.clone(),
+        reason,
+    };
+    // The Game is over, so there is nothing left to do for Players that can not be reached
+    // anymore
+    let _ = game.broadcast(&done_msg).await;
+    game.send_spectators(&done_msg).await;
+
+    GameState::Done
+}
+
+/// Waits until every Player asked for a Rematch, while still handling their Chat Messages.
+///
+/// Returns false as soon as any Player disconnects, as the Rematch can not take place anymore
+async fn wait_for_rematch<R, SI, ST>(game: &mut Game<R, SI, ST>) -> bool
+where
+    R: Rng,
+    SI: Sink<Message>,
+    <SI as futures::Sink<Message>>::Error: Debug,
+    ST: Stream<Item = Result<Message, axum::Error>>,
+{
+    // The Messages of the other Players are buffered in their Streams, so waiting for the Players
+    // one after another does not depend on the order they actually ask for the Rematch in
+    for index in 0..game.players.len() {
+        loop {
+            let player = &mut game.players[index];
+            // Requests that were sent during the Game, but not handled anymore, come first
+            let request = match player.queued.pop_front() {
+                Some(req) => Ok(req),
+                None => {
+                    let msg_text = match player.recv.next().await {
+                        Some(Ok(Message::Text(t))) => t,
+                        Some(Ok(Message::Close(_))) | None => {
+                            tracing::warn!("Player {:?} left after the Game", player.name);
+                            return false;
+                        }
+                        Some(Ok(_)) => continue,
+                        Some(Err(e)) => {
+                            tracing::error!("Error receiving {:?}", e);
+                            return false;
+                        }
+                    };
+
+                    serde_json::from_str(&msg_text).map_err(|e| (msg_text, e))
+                }
+            };
+
+            match request {
+                Ok(GameRequest::Rematch) => {
+                    tracing::debug!("Player {:?} wants a Rematch", player.name);
+                    break;
+                }
+                Ok(GameRequest::Chat { text }) => {
+                    game.chat(index, text).await;
+                }
+                Ok(other) => {
+                    tracing::warn!("Expected Rematch but got {:?}", other);
+                }
+                Err((msg_text, e)) => {
+                    tracing::error!("Error Message({:?}): {:?}", msg_text, e);
+                }
+            };
+        }
+    }
+
+    true
+}
+
+/// Receives the next Message from the current Player or from any of the other connected Players,
+/// together with the Index of the Player it was received from
+async fn next_message<Tx, Rx>(
+    players: &mut [GamePlayer<Tx, Rx>],
+    current: usize,
+) -> (usize, Option<Result<Message, axum::Error>>)
+where
+    Rx: Stream<Item = Result<Message, axum::Error>> + Unpin,
+{
+    let (indices, receivers): (Vec<_>, Vec<_>) = players
+        .iter_mut()
+        .enumerate()
+        .filter(|(i, p)| *i == current || p.is_connected())
+        .map(|(i, p)| (i, p.recv.next()))
+        .unzip();
+
+    let (received, position, _) = futures::future::select_all(receivers).await;
+    (indices[position], received)
+}
+
+/// Restores the Connection of the Player with the given Rejoin-Key, which can be any Player of the
+/// Game, and sends them the current State of the Game.
+///
+/// New and reconnecting Spectators are added to the Game right away as well, so they are served
+/// immediately, even while the Game is waiting for a Player.
+///
+/// Returns the Index of the Player that rejoined or None if no Player rejoined
+async fn rejoin_player<R, SI, ST>(
+    game: &mut Game<R, SI, ST>,
+    (rejoin_key, (tx, rx)): RejoinMessage<SI, ST>,
+) -> Option<usize>
+where
+    R: Rng,
+    SI: Sink<Message>,
+    <SI as futures::Sink<Message>>::Error: Debug,
+    ST: Stream<Item = Result<Message, axum::Error>>,
+{
+    let player_index = match (game.player_index_by_rejoin_key(&rejoin_key), rejoin_key) {
+        (Some(i), _) => i,
+        (None, RejoinKey::Spectate) => {
+            tracing::debug!("Adding a new Spectator");
+            game.add_spectator(tx).await;
+            return None;
+        }
+        (None, RejoinKey::Code(code)) if game.spectator_codes().contains(&code) => {
+            tracing::debug!("Spectator rejoined");
+            game.rejoin_spectator(code, tx).await;
+            return None;
+        }
+        (None, rejoin_key) => {
+            tracing::warn!("Unknown Rejoin Key {:?}", rejoin_key);
+            return None;
+        }
+    };
+    tracing::debug!("Player {} rejoined", player_index);
+
+    let rejoined_player = &mut game.players[player_index];
+    rejoined_player.send = tx;
+    rejoined_player.recv = rx;
+    rejoined_player.connected = true;
+    rejoined_player.last_seen = std::time::Instant::now();
+    rejoined_player.queued.clear();
+
+    if game.pending_leave == Some(player_index) {
+        // The other Players were never told about the Player leaving
+        game.pending_leave = None;
+    } else {
+        let resp = GameResponse::PlayerRejoined {
+            player: player_index,
+        };
+        for (_, player) in game
+            .players
+            .iter_mut()
+            .enumerate()
+            .filter(|(i, p)| *i != player_index && p.is_connected())
+        {
+            let _ = player.send_resp(&resp).await;
+        }
+        game.send_spectators(&resp).await;
+    }
+
+    // We ignore these results because Players that can not be reached are marked as disconnected
+    // and will be waited for once it is their Turn
+    let _ = game.send_state().await;
+    let _ = game.indicate_players().await;
+
+    Some(player_index)
+}
+
+/// Prompts the current Player again after they rejoined in the given State.
+///
+/// A StartTurn already prompts the Player again once it is resumed, but a Roll has to be
+/// repeated for the Player to know what to move
+async fn reprompt_player<R, SI, ST>(game: &mut Game<R, SI, ST>, state: &GameState)
+where
+    R: Rng,
+    SI: Sink<Message>,
+    <SI as futures::Sink<Message>>::Error: Debug,
+    ST: Stream<Item = Result<Message, axum::Error>>,
+{
+    let value = match state {
+        GameState::Rolled { value } => *value,
+        _ => return,
+    };
+
+    let legal_moves = game.turn_summary(Some(value)).legal_moves;
+    let player = &mut game.players[game.next_player];
+
+    let prompt = [
+        GameResponse::Rolled {
+            value,
+            can_move: !legal_moves.is_empty(),
+            attempts_left: None,
+        },
+        GameResponse::MoveOptions {
+            value,
+            figures: legal_moves,
+        },
+    ];
+    for resp in prompt.iter() {
+        if player.send_resp(resp).await.is_err() {
+            break;
+        }
+    }
+}
+
+/// Pauses the Game on behalf of the current Player and informs everyone about it, until the
+/// Player resumes the Game with the given State
+async fn pause_game<R, SI, ST>(game: &mut Game<R, SI, ST>, prev: GameState) -> GameState
+where
+    R: Rng,
+    SI: Sink<Message>,
+    <SI as futures::Sink<Message>>::Error: Debug,
+    ST: Stream<Item = Result<Message, axum::Error>>,
+{
+    if matches!(prev, GameState::Paused { .. }) {
+        return prev;
+    }
+
+    tracing::debug!("Player {} paused the Game", game.next_player);
+
+    let resp = GameResponse::Paused {
+        player: game.next_player,
+    };
+    // Players that can not be reached will be noticed once the Game continues
+    let _ = game.broadcast(&resp).await;
+    game.send_spectators(&resp).await;
+
+    GameState::Paused {
+        prev_state: Box::new(prev),
+    }
+}
+
+/// Informs all the connected Players and Spectators, that the current Player left the Game and
+/// that the Game is waiting for them to reconnect, before continuing with the given State
+async fn wait_for_reconnect<R, SI, ST>(game: &mut Game<R, SI, ST>, next: GameState) -> GameState
+where
+    R: Rng,
+    SI: Sink<Message>,
+    <SI as futures::Sink<Message>>::Error: Debug,
+    ST: Stream<Item = Result<Message, axum::Error>>,
+{
+    if game.reconnect_grace.is_zero() {
+        announce_leave(game, game.next_player).await;
+    } else {
+        // The other Players are only told about it, if the Player does not rejoin in time
+        game.pending_leave = Some(game.next_player);
+    }
+
+    GameState::WaitingForReconnect {
+        prev_state: Box::new(next),
+    }
+}
+
+/// Informs all the connected Players and the Spectators, that the given Player left the Game
+async fn announce_leave<R, SI, ST>(game: &mut Game<R, SI, ST>, left: usize)
+where
+    R: Rng,
+    SI: Sink<Message>,
+    <SI as futures::Sink<Message>>::Error: Debug,
+    ST: Stream<Item = Result<Message, axum::Error>>,
+{
+    game.pending_leave = None;
+
+    let resp = GameResponse::PlayerLeft { player: left };
+    for player in game.players.iter_mut().filter(|p| p.is_connected()) {
+        let _ = player.send_resp(&resp).await;
+    }
+    game.send_spectators(&resp).await;
+    announce_waiting(game).await;
+}
+
+/// Informs all the connected Players and the Spectators about the Players the Game is waiting for
+async fn announce_waiting<R, SI, ST>(game: &mut Game<R, SI, ST>)
+where
+    R: Rng,
+    SI: Sink<Message>,
+    <SI as futures::Sink<Message>>::Error: Debug,
+    ST: Stream<Item = Result<Message, axum::Error>>,
+{
+    let resp = GameResponse::WaitingFor {
+        players: game.disconnected_players(),
+    };
+    for player in game.players.iter_mut().filter(|p| p.is_connected()) {
+        let _ = player.send_resp(&resp).await;
+    }
+    game.send_spectators(&resp).await;
+}
+
+/// Sends the current State to all the Players and continues with the given next State.
+///
+/// Players that could not be reached are marked as disconnected, but the Game only waits for them
+/// to reconnect once it is their Turn
+async fn send_state_then<R, SI, ST>(game: &mut Game<R, SI, ST>, next: GameState) -> GameState
+where
+    R: Rng,
+    SI: Sink<Message>,
+    <SI as futures::Sink<Message>>::Error: Debug,
+    ST: Stream<Item = Result<Message, axum::Error>>,
+{
+    if let Err(e) = game.send_state().await {
+        tracing::warn!("Could not send State to all Players: {:?}", e);
+    }
+
+    next
+}
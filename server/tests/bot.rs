@@ -0,0 +1,169 @@
+use axum::extract::ws::Message;
+use futures::StreamExt;
+use rand::SeedableRng;
+use server::{
+    bot::{BotPlayer, Seat, Strategy},
+    statemachine::GameState,
+    Board, Figure, Game, GameEndReason, GamePlayer, GameRequest, GameResponse, RejoinKey,
+};
+
+use mocks::MockSocket;
+use tracing_test::traced_test;
+
+mod common;
+use common::responses;
+
+#[tokio::test]
+#[traced_test]
+async fn bot_plays_to_completion() {
+    let (tx1, mut rx1) = tokio::sync::mpsc::unbounded_channel();
+
+    let mut players = vec![
+        GamePlayer::new(
+            "human".to_string(),
+            Seat::Remote(MockSocket::new(
+                tx1,
+                vec![Message::Text(
+                    serde_json::to_string(&GameRequest::Ready).unwrap(),
+                )],
+            ))
+            .split(),
+        ),
+        GamePlayer::new(
+            "bot".to_string(),
+            Seat::Bot(BotPlayer::new(Board::for_players(2))).split(),
+        ),
+    ];
+
+    assert_eq!(Ok(()), server::lobby::wait_for_ready(&mut players).await);
+
+    // The human Player already finished, so the rest of the Game is only driven by the Bot
+    let in_house = [
+        Figure::InHouse { pos: 0 },
+        Figure::InHouse { pos: 1 },
+        Figure::InHouse { pos: 2 },
+        Figure::InHouse { pos: 3 },
+    ];
+    let figures = [
+        in_house,
+        [
+            Figure::InStart,
+            Figure::InStart,
+            Figure::InStart,
+            Figure::InStart,
+        ],
+    ];
+    let mut game: Game<_, Seat<MockSocket<Message>>, Seat<MockSocket<Message>>> = Game::from_state(
+        uuid::Uuid::new_v4(),
+        players.into_iter().zip(figures),
+        1,
+        vec![0],
+        rand::rngs::SmallRng::seed_from_u64(42),
+    );
+
+    let mut distr = rand::distributions::Uniform::new_inclusive(1, 6);
+    let mut state = GameState::StartTurn { attempt: 0 };
+
+    let (_rejointx, mut rejoinrx) = tokio::sync::mpsc::unbounded_channel();
+
+    for _ in 0..10_000 {
+        state = match server::statemachine::step(state, &mut game, &mut rejoinrx, &mut distr).await
+        {
+            Some(s) => s,
+            None => break,
+        };
+    }
+
+    assert!(game.is_done());
+    assert_eq!(vec![0, 1], game.ranking);
+    assert!(game.players[1]
+        .figures
+        .iter()
+        .all(|f| matches!(f, Figure::InHouse { .. })));
+
+    let received = responses(&mut rx1);
+    assert_eq!(GameResponse::AllReady, received[0]);
+    assert_eq!(
+        Some(&GameResponse::GameDone {
+            ranking: vec![0, 1],
+            reason: GameEndReason::AllFinished,
+        }),
+        received.last()
+    );
+}
+
+#[tokio::test]
+#[traced_test]
+async fn bot_replaces_kicked_player() {
+    let (tx1, _rx1) = tokio::sync::mpsc::unbounded_channel();
+    let (tx2, _rx2) = tokio::sync::mpsc::unbounded_channel();
+
+    let players = vec![
+        GamePlayer::new(
+            "human".to_string(),
+            Seat::Remote(MockSocket::new(tx1, vec![])).split(),
+        ),
+        GamePlayer::new(
+            "gone".to_string(),
+            Seat::Remote(MockSocket::new_failing(tx2, vec![])).split(),
+        ),
+    ];
+
+    let in_house = [
+        Figure::InHouse { pos: 0 },
+        Figure::InHouse { pos: 1 },
+        Figure::InHouse { pos: 2 },
+        Figure::InHouse { pos: 3 },
+    ];
+    let figures = [
+        in_house,
+        [
+            Figure::InStart,
+            Figure::InStart,
+            Figure::InStart,
+            Figure::InStart,
+        ],
+    ];
+    let mut game: Game<_, Seat<MockSocket<Message>>, Seat<MockSocket<Message>>> = Game::from_state(
+        uuid::Uuid::new_v4(),
+        players.into_iter().zip(figures),
+        1,
+        vec![0],
+        rand::rngs::SmallRng::seed_from_u64(42),
+    );
+
+    let mut distr = rand::distributions::Uniform::new_inclusive(1, 6);
+    let (rejointx, mut rejoinrx) = tokio::sync::mpsc::unbounded_channel();
+
+    let mut state = server::statemachine::step(
+        GameState::StartTurn { attempt: 0 },
+        &mut game,
+        &mut rejoinrx,
+        &mut distr,
+    )
+    .await
+    .expect("");
+    assert!(game.is_paused());
+
+    rejointx
+        .send((
+            RejoinKey::Kick(1),
+            Seat::Bot(BotPlayer::substitute(
+                Strategy::default(),
+                Board::for_players(2),
+            ))
+            .split(),
+        ))
+        .unwrap();
+
+    for _ in 0..10_000 {
+        state = match server::statemachine::step(state, &mut game, &mut rejoinrx, &mut distr).await
+        {
+            Some(s) => s,
+            None => break,
+        };
+    }
+
+    assert!(game.is_done());
+    assert_eq!(vec![0, 1], game.ranking);
+}
@@ -0,0 +1,427 @@
+use axum::extract::ws::Message;
+use futures::StreamExt;
+use server::{
+    statemachine::GameState, CapturePenalty, Figure, Game, GamePlayer, GameRequest, GameResponse,
+};
+
+use mocks::{MockRand, MockSocket, SequenceDistr};
+use tracing_test::traced_test;
+
+mod common;
+use common::{responses, two_player_game};
+
+#[test]
+#[traced_test]
+fn capture_on_shared_field() {
+    let (mut game, _rx) = two_player_game(vec![], vec![0]);
+
+    game.players[0].figures[0] = Figure::OnField { moved: 15 };
+    game.players[1].figures[0] = Figure::OnField { moved: 35 };
+
+    assert_eq!(vec![(1, 0)], game.check_move(0, 0));
+
+    assert_eq!(Figure::OnField { moved: 15 }, game.players[0].figures[0]);
+    assert_eq!(Figure::InStart, game.players[1].figures[0]);
+}
+
+#[test]
+#[traced_test]
+fn only_landed_on_field_captures() {
+    let (mut game, _rx) = two_player_game(vec![], vec![0]);
+
+    game.players[0].figures[0] = Figure::OnField { moved: 15 };
+    game.players[0].figures[1] = Figure::OnField { moved: 22 };
+    game.players[1].figures[0] = Figure::OnField { moved: 35 };
+    game.players[1].figures[1] = Figure::OnField { moved: 2 };
+
+    assert_eq!(vec![(1, 1)], game.check_move(0, 1));
+
+    assert_eq!(Figure::OnField { moved: 35 }, game.players[1].figures[0]);
+    assert_eq!(Figure::InStart, game.players[1].figures[1]);
+}
+
+#[test]
+#[traced_test]
+fn safe_start_field_protects() {
+    let (mut game, _rx) = two_player_game(vec![], vec![0]);
+    game.safe_starts = true;
+
+    game.players[0].figures[0] = Figure::OnField { moved: 20 };
+    game.players[1].figures[0] = Figure::OnField { moved: 0 };
+
+    assert!(game.check_move(0, 0).is_empty());
+
+    assert_eq!(Figure::OnField { moved: 20 }, game.players[0].figures[0]);
+    assert_eq!(Figure::OnField { moved: 0 }, game.players[1].figures[0]);
+}
+
+#[test]
+#[traced_test]
+fn unsafe_start_field_captures() {
+    let (mut game, _rx) = two_player_game(vec![], vec![0]);
+    game.safe_starts = false;
+
+    game.players[0].figures[0] = Figure::OnField { moved: 20 };
+    game.players[1].figures[0] = Figure::OnField { moved: 0 };
+
+    assert_eq!(vec![(1, 0)], game.check_move(0, 0));
+
+    assert_eq!(Figure::OnField { moved: 20 }, game.players[0].figures[0]);
+    assert_eq!(Figure::InStart, game.players[1].figures[0]);
+}
+
+#[test]
+#[traced_test]
+fn capture_from_start_field() {
+    let (mut game, _rx) = two_player_game(vec![], vec![0]);
+
+    game.players[0].figures[0] = Figure::OnField { moved: 0 };
+    game.players[1].figures[0] = Figure::OnField { moved: 20 };
+
+    assert_eq!(vec![(1, 0)], game.check_move(0, 0));
+
+    assert_eq!(Figure::OnField { moved: 0 }, game.players[0].figures[0]);
+    assert_eq!(Figure::InStart, game.players[1].figures[0]);
+}
+
+#[test]
+#[traced_test]
+fn house_figures_are_not_compared() {
+    let (mut game, _rx) = two_player_game(vec![], vec![0]);
+
+    game.players[0].figures[0] = Figure::InHouse { pos: 0 };
+    game.players[0].figures[1] = Figure::InHouse { pos: 3 };
+    game.players[1].figures[0] = Figure::OnField { moved: 30 };
+    game.players[1].figures[1] = Figure::OnField { moved: 33 };
+    game.players[1].figures[2] = Figure::InHouse { pos: 0 };
+
+    assert!(game.check_move(0, 0).is_empty());
+    assert!(game.check_move(1, 0).is_empty());
+
+    assert_eq!(Figure::InHouse { pos: 0 }, game.players[0].figures[0]);
+    assert_eq!(Figure::InHouse { pos: 3 }, game.players[0].figures[1]);
+    assert_eq!(Figure::OnField { moved: 30 }, game.players[1].figures[0]);
+    assert_eq!(Figure::OnField { moved: 33 }, game.players[1].figures[1]);
+    assert_eq!(Figure::InHouse { pos: 0 }, game.players[1].figures[2]);
+}
+
+#[test]
+#[traced_test]
+fn two_players_start_opposite() {
+    let (game, _rx) = two_player_game(vec![], vec![0]);
+
+    assert_eq!(40, game.board.fields);
+    assert_eq!(vec![0, 20], game.board.player_starts);
+    assert_eq!(5, game.absolute_position(1, 25));
+}
+
+#[test]
+#[traced_test]
+fn six_player_board() {
+    let players: Vec<_> = (0..6)
+        .map(|i| {
+            let (tx, _rx) = tokio::sync::mpsc::unbounded_channel();
+            GamePlayer::new(format!("test{}", i), MockSocket::new(tx, vec![]).split())
+        })
+        .collect();
+    let mut game: Game<_, MockSocket<Message>, MockSocket<Message>> =
+        Game::new_with_rng(uuid::Uuid::new_v4(), players, MockRand::new(vec![0]));
+
+    assert_eq!(60, game.board.fields);
+    assert_eq!(10, game.board.player_starts[1]);
+    assert_eq!(52, game.absolute_position(5, 2));
+
+    game.players[1].figures[0] = Figure::OnField { moved: 2 };
+    game.players[4].figures[0] = Figure::OnField { moved: 12 };
+    game.players[5].figures[0] = Figure::OnField { moved: 2 };
+    game.players[0].figures[0] = Figure::OnField { moved: 52 };
+
+    assert_eq!(vec![(4, 0), (5, 0)], game.check_move(0, 0));
+
+    assert_eq!(Figure::OnField { moved: 2 }, game.players[1].figures[0]);
+    assert_eq!(Figure::InStart, game.players[4].figures[0]);
+    assert_eq!(Figure::InStart, game.players[5].figures[0]);
+    assert_eq!(Figure::OnField { moved: 52 }, game.players[0].figures[0]);
+}
+
+#[tokio::test]
+#[traced_test]
+async fn capture_is_announced() {
+    let (tx1, mut rx1) = tokio::sync::mpsc::unbounded_channel();
+    let (tx2, mut rx2) = tokio::sync::mpsc::unbounded_channel();
+
+    let mut game: Game<_, MockSocket<Message>, MockSocket<Message>> = Game::new_with_rng(
+        uuid::Uuid::new_v4(),
+        vec![
+            GamePlayer::new(
+                "test".to_string(),
+                MockSocket::new(
+                    tx1,
+                    vec![Message::Text(
+                        serde_json::to_string(&GameRequest::Move { figure: 1 }).unwrap(),
+                    )],
+                )
+                .split(),
+            ),
+            GamePlayer::new("test2".to_string(), MockSocket::new(tx2, vec![]).split()),
+        ],
+        MockRand::new(vec![0, 0]),
+    );
+
+    game.next_player = 0;
+    game.players[0].figures[1] = Figure::OnField { moved: 12 };
+    game.players[1].figures[2] = Figure::OnField { moved: 35 };
+
+    let mut distr = SequenceDistr::new(vec![]);
+    let state = GameState::Rolled { value: 3 };
+
+    let (_rejointx, mut rejoinrx) = tokio::sync::mpsc::unbounded_channel();
+
+    let n_state = server::statemachine::step(state, &mut game, &mut rejoinrx, &mut distr)
+        .await
+        .expect("");
+
+    assert_eq!(GameState::MoveToNextTurn, n_state);
+    assert_eq!(Figure::InStart, game.players[1].figures[2]);
+
+    let expected = GameResponse::Captured {
+        by: 0,
+        player: 1,
+        figure: 2,
+    };
+    for received in [responses(&mut rx1), responses(&mut rx2)] {
+        assert_eq!(3, received.len());
+        assert_eq!(expected, received[0]);
+        assert!(matches!(received[1], GameResponse::State { .. }));
+        assert!(matches!(received[2], GameResponse::Board { .. }));
+    }
+}
+
+#[test]
+#[traced_test]
+fn board_uses_absolute_positions() {
+    let (mut game, _rx) = two_player_game(vec![], vec![0]);
+
+    game.players[0].figures[0] = Figure::OnField { moved: 15 };
+    game.players[0].figures[1] = Figure::InHouse { pos: 2 };
+    game.players[1].figures[3] = Figure::OnField { moved: 25 };
+
+    let (cells, houses, starts) = match game.board() {
+        GameResponse::Board {
+            cells,
+            houses,
+            starts,
+        } => (cells, houses, starts),
+        other => panic!("Expected Board but got {:?}", other),
+    };
+
+    assert_eq!(40, cells.len());
+    assert_eq!(Some((0, 0)), cells[15]);
+    assert_eq!(Some((1, 3)), cells[5]);
+    assert_eq!(2, cells.iter().filter(|c| c.is_some()).count());
+
+    assert_eq!(vec![vec![None, None, Some(1), None], vec![None; 4]], houses);
+    assert_eq!(vec![vec![2, 3], vec![0, 1, 2]], starts);
+}
+
+#[test]
+#[traced_test]
+fn ascii_board_shows_markers() {
+    let (mut game, _rx) = two_player_game(vec![], vec![0]);
+
+    game.players[0].figures[0] = Figure::OnField { moved: 15 };
+    game.players[0].figures[1] = Figure::InHouse { pos: 2 };
+    game.players[1].figures[3] = Figure::OnField { moved: 25 };
+
+    let rendered = game.to_ascii_board();
+    let lines: Vec<_> = rendered.lines().collect();
+    assert_eq!(5, lines.len());
+
+    let track: Vec<_> = lines[0]
+        .trim_start_matches("Track:")
+        .split_whitespace()
+        .collect();
+    assert_eq!(40, track.len());
+    assert_eq!("A0", track[15]);
+    assert_eq!("B3", track[5]);
+    assert_eq!(38, track.iter().filter(|c| **c == "..").count());
+
+    assert_eq!("House A: .. .. A1 ..", lines[1]);
+    assert_eq!("Start A: A2 A3", lines[2]);
+    assert_eq!("House B: .. .. .. ..", lines[3]);
+    assert_eq!("Start B: B0 B1 B2", lines[4]);
+}
+
+#[test]
+#[traced_test]
+fn state_contains_absolute_positions() {
+    let (mut game, _rx) = two_player_game(vec![], vec![0]);
+
+    game.players[0].figures[0] = Figure::OnField { moved: 15 };
+    game.players[0].figures[1] = Figure::InHouse { pos: 2 };
+    game.players[1].figures[3] = Figure::OnField { moved: 25 };
+
+    let positions = match game.state() {
+        GameResponse::State { positions, .. } => positions,
+        other => panic!("Expected State but got {:?}", other),
+    };
+
+    assert_eq!(
+        vec![[Some(15), None, None, None], [None, None, None, Some(5)]],
+        positions
+    );
+}
+
+#[tokio::test]
+#[traced_test]
+async fn capture_grants_extra_turn() {
+    let (tx1, _rx1) = tokio::sync::mpsc::unbounded_channel();
+    let (tx2, _rx2) = tokio::sync::mpsc::unbounded_channel();
+
+    let mut game: Game<_, MockSocket<Message>, MockSocket<Message>> = Game::new_with_rng(
+        uuid::Uuid::new_v4(),
+        vec![
+            GamePlayer::new(
+                "test".to_string(),
+                MockSocket::new(
+                    tx1,
+                    vec![
+                        Message::Text(
+                            serde_json::to_string(&GameRequest::Move { figure: 1 }).unwrap(),
+                        ),
+                        Message::Text(
+                            serde_json::to_string(&GameRequest::Move { figure: 1 }).unwrap(),
+                        ),
+                    ],
+                )
+                .split(),
+            ),
+            GamePlayer::new("test2".to_string(), MockSocket::new(tx2, vec![]).split()),
+        ],
+        MockRand::new(vec![0, 0]),
+    );
+    game.extra_turn_on_capture = true;
+
+    game.next_player = 0;
+    game.players[0].figures[1] = Figure::OnField { moved: 12 };
+    game.players[1].figures[2] = Figure::OnField { moved: 35 };
+
+    let mut distr = SequenceDistr::new(vec![]);
+    let (_rejointx, mut rejoinrx) = tokio::sync::mpsc::unbounded_channel();
+
+    let n_state = server::statemachine::step(
+        GameState::Rolled { value: 3 },
+        &mut game,
+        &mut rejoinrx,
+        &mut distr,
+    )
+    .await
+    .expect("");
+
+    assert_eq!(GameState::StartTurn { attempt: 0 }, n_state);
+    assert_eq!(Figure::InStart, game.players[1].figures[2]);
+    assert_eq!(0, game.next_player);
+
+    // Moves without a Capture still end the Turn as usual
+    let n_state = server::statemachine::step(
+        GameState::Rolled { value: 3 },
+        &mut game,
+        &mut rejoinrx,
+        &mut distr,
+    )
+    .await
+    .expect("");
+
+    assert_eq!(GameState::MoveToNextTurn, n_state);
+    assert_eq!(Figure::OnField { moved: 18 }, game.players[0].figures[1]);
+}
+
+#[tokio::test]
+#[traced_test]
+async fn preview_does_not_capture() {
+    let (tx1, mut rx1) = tokio::sync::mpsc::unbounded_channel();
+    let (tx2, mut rx2) = tokio::sync::mpsc::unbounded_channel();
+
+    let mut game: Game<_, MockSocket<Message>, MockSocket<Message>> = Game::new_with_rng(
+        uuid::Uuid::new_v4(),
+        vec![
+            GamePlayer::new(
+                "test".to_string(),
+                MockSocket::new(
+                    tx1,
+                    vec![Message::Text(
+                        serde_json::to_string(&GameRequest::PreviewMove { figure: 1 }).unwrap(),
+                    )],
+                )
+                .split(),
+            ),
+            GamePlayer::new("test2".to_string(), MockSocket::new(tx2, vec![]).split()),
+        ],
+        MockRand::new(vec![0, 0]),
+    );
+
+    game.next_player = 0;
+    game.players[0].figures[1] = Figure::OnField { moved: 12 };
+    game.players[1].figures[2] = Figure::OnField { moved: 35 };
+
+    let mut distr = SequenceDistr::new(vec![]);
+    let state = GameState::Rolled { value: 3 };
+
+    let (_rejointx, mut rejoinrx) = tokio::sync::mpsc::unbounded_channel();
+
+    let n_state = server::statemachine::step(state, &mut game, &mut rejoinrx, &mut distr)
+        .await
+        .expect("");
+
+    assert_eq!(GameState::Rolled { value: 3 }, n_state);
+    assert_eq!(Figure::OnField { moved: 12 }, game.players[0].figures[1]);
+    assert_eq!(Figure::OnField { moved: 35 }, game.players[1].figures[2]);
+    assert!(game.history().is_empty());
+
+    assert_eq!(
+        vec![GameResponse::MovePreview {
+            to: Figure::OnField { moved: 15 },
+            captures: vec![(1, 2)],
+        }],
+        responses(&mut rx1)
+    );
+    assert!(responses(&mut rx2).is_empty());
+}
+
+#[test]
+#[traced_test]
+fn capture_penalty_to_start() {
+    let (mut game, _rx) = two_player_game(vec![], vec![0]);
+    assert_eq!(CapturePenalty::ToStart, game.capture_penalty);
+
+    game.players[0].figures[0] = Figure::OnField { moved: 25 };
+    game.players[1].figures[0] = Figure::OnField { moved: 5 };
+
+    assert_eq!(vec![(1, 0)], game.check_move(0, 0));
+    assert_eq!(Figure::InStart, game.players[1].figures[0]);
+}
+
+#[test]
+#[traced_test]
+fn capture_penalty_back_steps() {
+    let (mut game, _rx) = two_player_game(vec![], vec![0]);
+    game.capture_penalty = CapturePenalty::BackSteps(4);
+
+    game.players[0].figures[0] = Figure::OnField { moved: 25 };
+    game.players[1].figures[0] = Figure::OnField { moved: 5 };
+
+    assert_eq!(vec![(1, 0)], game.check_move(0, 0));
+    assert_eq!(Figure::OnField { moved: 1 }, game.players[1].figures[0]);
+
+    // Moving back past the Start-Field or onto an own Figure sends the Figure back to the Start
+    game.players[0].figures[1] = Figure::OnField { moved: 22 };
+    game.players[1].figures[1] = Figure::OnField { moved: 2 };
+    assert_eq!(vec![(1, 1)], game.check_move(0, 1));
+    assert_eq!(Figure::InStart, game.players[1].figures[1]);
+
+    game.players[0].figures[0] = Figure::OnField { moved: 25 };
+    game.players[1].figures[0] = Figure::OnField { moved: 5 };
+    game.players[1].figures[2] = Figure::OnField { moved: 1 };
+    assert_eq!(vec![(1, 0)], game.check_move(0, 0));
+    assert_eq!(Figure::InStart, game.players[1].figures[0]);
+}
@@ -0,0 +1,158 @@
+use axum::extract::ws::Message;
+use futures::StreamExt;
+use server::{
+    statemachine::GameState, Game, GamePlayer, GameRequest, GameResponse, MAX_CHAT_LENGTH,
+};
+
+use mocks::{MockRand, MockSocket, SequenceDistr};
+use tracing_test::traced_test;
+
+mod common;
+use common::responses;
+
+#[tokio::test]
+#[traced_test]
+async fn chat_is_broadcast() {
+    let (tx1, mut rx1) = tokio::sync::mpsc::unbounded_channel();
+    let (tx2, mut rx2) = tokio::sync::mpsc::unbounded_channel();
+
+    let mut game: Game<_, MockSocket<Message>, MockSocket<Message>> = Game::new_with_rng(
+        uuid::Uuid::new_v4(),
+        vec![
+            GamePlayer::new(
+                "test".to_string(),
+                MockSocket::new(
+                    tx1,
+                    vec![
+                        Message::Text(
+                            serde_json::to_string(&GameRequest::Chat {
+                                text: "hello".to_string(),
+                            })
+                            .unwrap(),
+                        ),
+                        Message::Text(serde_json::to_string(&GameRequest::Roll).unwrap()),
+                    ],
+                )
+                .split(),
+            ),
+            GamePlayer::new("test2".to_string(), MockSocket::new(tx2, vec![]).split()),
+        ],
+        MockRand::new(vec![0, 0]),
+    );
+
+    game.next_player = 0;
+
+    let mut distr = SequenceDistr::new(vec![3]);
+    let state = GameState::StartTurn { attempt: 0 };
+
+    let (_rejointx, mut rejoinrx) = tokio::sync::mpsc::unbounded_channel();
+
+    let n_state = server::statemachine::step(state, &mut game, &mut rejoinrx, &mut distr)
+        .await
+        .expect("");
+
+    assert_eq!(GameState::StartTurn { attempt: 1 }, n_state);
+
+    let expected = GameResponse::Chat {
+        player: 0,
+        name: "test".to_string(),
+        text: "hello".to_string(),
+    };
+    assert!(responses(&mut rx1).contains(&expected));
+    assert_eq!(vec![expected], responses(&mut rx2));
+}
+
+#[tokio::test]
+#[traced_test]
+async fn chat_too_long() {
+    let (tx1, mut rx1) = tokio::sync::mpsc::unbounded_channel();
+    let (tx2, mut rx2) = tokio::sync::mpsc::unbounded_channel();
+
+    let mut game: Game<_, MockSocket<Message>, MockSocket<Message>> = Game::new_with_rng(
+        uuid::Uuid::new_v4(),
+        vec![
+            GamePlayer::new("test".to_string(), MockSocket::new(tx1, vec![]).split()),
+            GamePlayer::new("test2".to_string(), MockSocket::new(tx2, vec![]).split()),
+        ],
+        MockRand::new(vec![0, 0]),
+    );
+
+    game.chat(1, "a".repeat(MAX_CHAT_LENGTH + 1)).await;
+
+    assert!(responses(&mut rx1).is_empty());
+    let received = responses(&mut rx2);
+    assert_eq!(1, received.len());
+    assert!(matches!(received[0], GameResponse::InvalidRequest { .. }));
+}
+
+#[tokio::test]
+#[traced_test]
+async fn out_of_turn_requests_are_answered() {
+    let (tx1, _rx1) = tokio::sync::mpsc::unbounded_channel();
+    let (tx2, mut rx2) = tokio::sync::mpsc::unbounded_channel();
+
+    let request = |req: GameRequest| Message::Text(serde_json::to_string(&req).unwrap());
+    let first = MockSocket::new(tx1, vec![]);
+
+    let mut game: Game<_, MockSocket<Message>, MockSocket<Message>> = Game::new_with_rng(
+        uuid::Uuid::new_v4(),
+        vec![
+            GamePlayer::new("test".to_string(), first.clone().split()),
+            GamePlayer::new(
+                "test2".to_string(),
+                MockSocket::new(
+                    tx2,
+                    vec![
+                        request(GameRequest::ListPlayers),
+                        request(GameRequest::Roll),
+                        request(GameRequest::RollHistory),
+                    ],
+                )
+                .split(),
+            ),
+        ],
+        MockRand::new(vec![0, 0]),
+    );
+    game.next_player = 0;
+
+    let mut distr = SequenceDistr::new(vec![3, 3]);
+    let (_rejointx, mut rejoinrx) = tokio::sync::mpsc::unbounded_channel();
+
+    // The second Player is answered while the Game is still waiting for the first one to roll
+    let (n_state, received) = tokio::join!(
+        server::statemachine::step(
+            GameState::StartTurn { attempt: 0 },
+            &mut game,
+            &mut rejoinrx,
+            &mut distr,
+        ),
+        async {
+            let received = loop {
+                let received = responses(&mut rx2);
+                if !received.is_empty() {
+                    break received;
+                }
+                tokio::time::sleep(std::time::Duration::from_millis(5)).await;
+            };
+            first.push(request(GameRequest::Roll));
+            received
+        }
+    );
+
+    assert_eq!(Some(GameState::StartTurn { attempt: 1 }), n_state);
+    assert!(matches!(received[0], GameResponse::Roster { .. }));
+    assert!(received.contains(&GameResponse::RollHistory { rolls: vec![] }));
+
+    // The Roll of the second Player waits for their Turn
+    game.next_player = 1;
+    let n_state = server::statemachine::step(
+        GameState::StartTurn { attempt: 0 },
+        &mut game,
+        &mut rejoinrx,
+        &mut distr,
+    )
+    .await
+    .expect("");
+    assert_eq!(GameState::StartTurn { attempt: 1 }, n_state);
+    assert_eq!(vec![3], game.players[1].recent_rolls());
+}
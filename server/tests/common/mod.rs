@@ -0,0 +1,107 @@
+#![allow(dead_code)]
+
+use axum::extract::ws::Message;
+use futures::{
+    stream::{SplitSink, SplitStream},
+    StreamExt,
+};
+use server::{Figure, Game, GamePlayer, GameResponse};
+
+use mocks::{MockRand, MockSocket};
+
+/// A Game played over [`MockSocket`]s
+pub type TestGame = Game<MockRand, MockSocket<Message>, MockSocket<Message>>;
+
+/// A Player of a [`TestGame`]
+pub type TestPlayer =
+    GamePlayer<SplitSink<MockSocket<Message>, Message>, SplitStream<MockSocket<Message>>>;
+
+/// Collects all the Responses that have been sent to the Receiver so far, where the Responses of
+/// a Batch are collected as if they were sent one after another
+pub fn responses(rx: &mut tokio::sync::mpsc::UnboundedReceiver<Message>) -> Vec<GameResponse> {
+    let mut result = Vec::new();
+    while let Ok(msg) = rx.try_recv() {
+        if let Message::Text(content) = msg {
+            match serde_json::from_str(&content).unwrap() {
+                GameResponse::Batch { responses } => result.extend(responses),
+                resp => result.push(resp),
+            }
+        }
+    }
+    result
+}
+
+/// Creates a Game with two Players, where the first Player is next and receives the given
+/// Messages and the Game draws from a [`MockRand`] with the given Values.
+///
+/// # Returns
+/// The Game together with the Receiver for everything sent to the first Player
+pub fn two_player_game(
+    msgs: Vec<Message>,
+    rng: Vec<u64>,
+) -> (
+    Game<MockRand, MockSocket<Message>, MockSocket<Message>>,
+    tokio::sync::mpsc::UnboundedReceiver<Message>,
+) {
+    let (tx1, rx1) = tokio::sync::mpsc::unbounded_channel();
+    let (tx2, _rx2) = tokio::sync::mpsc::unbounded_channel();
+
+    let mut game = Game::new_with_rng(
+        uuid::Uuid::new_v4(),
+        vec![
+            GamePlayer::new("test".to_string(), MockSocket::new(tx1, msgs).split()),
+            GamePlayer::new("test2".to_string(), MockSocket::new(tx2, vec![]).split()),
+        ],
+        MockRand::new(rng),
+    );
+    game.next_player = 0;
+
+    (game, rx1)
+}
+
+/// Creates a Player with the given Name, whose Socket never receives anything
+pub fn player(name: &str) -> TestPlayer {
+    let (tx, _rx) = tokio::sync::mpsc::unbounded_channel();
+    GamePlayer::new(name.to_string(), MockSocket::new(tx, vec![]).split())
+}
+
+/// Creates a Player with the given Figures, whose Socket never receives anything
+pub fn player_with_figures(figures: [Figure; 4]) -> TestPlayer {
+    let mut player = player("test");
+    player.figures = figures;
+    player
+}
+
+/// Creates a Game with a Player for every given Name, where the first Player is next
+pub fn game_with_names(names: &[&str]) -> TestGame {
+    let players: Vec<_> = names.iter().map(|name| player(name)).collect();
+
+    let mut game = Game::new_with_rng(uuid::Uuid::new_v4(), players, MockRand::new(vec![0]));
+    game.next_player = 0;
+    game
+}
+
+/// Creates a Game with the given Number of Players, which are named `test0`, `test1` and so on
+/// and where the first Player is next
+pub fn game_with_players(count: usize) -> TestGame {
+    let names: Vec<_> = (0..count).map(|i| format!("test{}", i)).collect();
+    game_with_names(&names.iter().map(String::as_str).collect::<Vec<_>>())
+}
+
+/// Creates a Game with a Player for every given Set of Figures, which are named like for
+/// [`game_with_players`], and the given Ranking of the Players that are already done
+pub fn game_with_figures(figures: Vec<[Figure; 4]>, ranking: Vec<usize>) -> TestGame {
+    let players: Vec<_> = figures
+        .into_iter()
+        .enumerate()
+        .map(|(i, figures)| (player(&format!("test{}", i)), figures))
+        .collect();
+
+    Game::from_state(
+        uuid::Uuid::new_v4(),
+        players,
+        0,
+        ranking,
+        MockRand::new(vec![0]),
+    )
+}
@@ -0,0 +1,721 @@
+use axum::extract::ws::Message;
+use futures::StreamExt;
+use server::{
+    statemachine::GameState, Figure, Game, GameEndReason, GameError, GamePlayer, GameRequest,
+    GameResponse,
+};
+
+use mocks::{DumbDistr, MockRand, MockSocket};
+use tracing_test::traced_test;
+
+mod common;
+use common::responses;
+
+#[tokio::test]
+#[traced_test]
+async fn broadcast_continues_after_failure() {
+    let (tx1, _rx1) = tokio::sync::mpsc::unbounded_channel();
+    let (tx2, mut rx2) = tokio::sync::mpsc::unbounded_channel();
+
+    let mut game: Game<_, MockSocket<Message>, MockSocket<Message>> = Game::new_with_rng(
+        uuid::Uuid::new_v4(),
+        vec![
+            GamePlayer::new(
+                "test".to_string(),
+                MockSocket::new_failing(tx1, vec![]).split(),
+            ),
+            GamePlayer::new("test2".to_string(), MockSocket::new(tx2, vec![]).split()),
+        ],
+        MockRand::new(vec![0]),
+    );
+
+    assert_eq!(Err(GameError::Send { player: 0 }), game.send_state().await);
+
+    assert!(!game.players[0].is_connected());
+    assert!(game.players[1].is_connected());
+
+    let received = responses(&mut rx2);
+    assert_eq!(2, received.len());
+    assert!(matches!(received[0], GameResponse::State { .. }));
+    assert!(matches!(received[1], GameResponse::Board { .. }));
+}
+
+#[tokio::test]
+#[traced_test]
+async fn send_failures_name_the_player() {
+    let (tx1, mut rx1) = tokio::sync::mpsc::unbounded_channel();
+    let (tx2, _rx2) = tokio::sync::mpsc::unbounded_channel();
+
+    let mut game: Game<_, MockSocket<Message>, MockSocket<Message>> = Game::new_with_rng(
+        uuid::Uuid::new_v4(),
+        vec![
+            GamePlayer::new("test".to_string(), MockSocket::new(tx1, vec![]).split()),
+            GamePlayer::new(
+                "test2".to_string(),
+                MockSocket::new_failing(tx2, vec![]).split(),
+            ),
+        ],
+        MockRand::new(vec![0]),
+    );
+
+    // A single Player only knows that they can not be reached anymore
+    assert_eq!(
+        Err(GameError::Disconnect),
+        game.players[1].send_resp(&GameResponse::Turn).await
+    );
+
+    assert_eq!(
+        Err(GameError::Send { player: 1 }),
+        game.broadcast(&GameResponse::Turn).await
+    );
+    assert_eq!(
+        Err(GameError::Send { player: 1 }),
+        game.indicate_players().await
+    );
+    assert_eq!(
+        Err(GameError::Send { player: 1 }),
+        game.send_rejoin_codes().await
+    );
+    assert_eq!(
+        Err(GameError::Send { player: 1 }),
+        game.resync_player(1).await
+    );
+    assert_eq!(Ok(()), game.resync_player(0).await);
+
+    assert!(game.players[0].is_connected());
+    assert!(!game.players[1].is_connected());
+    assert!(!responses(&mut rx1).is_empty());
+}
+
+#[tokio::test]
+#[traced_test]
+async fn failed_state_continues_game() {
+    let (tx1, mut rx1) = tokio::sync::mpsc::unbounded_channel();
+    let (tx2, mut rx2) = tokio::sync::mpsc::unbounded_channel();
+
+    let mut game: Game<_, MockSocket<Message>, MockSocket<Message>> = Game::new_with_rng(
+        uuid::Uuid::new_v4(),
+        vec![
+            GamePlayer::new(
+                "test".to_string(),
+                MockSocket::new(
+                    tx1,
+                    vec![Message::Text(
+                        serde_json::to_string(&GameRequest::Move { figure: 0 }).unwrap(),
+                    )],
+                )
+                .split(),
+            ),
+            GamePlayer::new(
+                "test2".to_string(),
+                MockSocket::new_failing(tx2, vec![]).split(),
+            ),
+        ],
+        MockRand::new(vec![0]),
+    );
+
+    game.next_player = 0;
+    game.players[0].figures[0] = Figure::OnField { moved: 5 };
+
+    let mut distr = DumbDistr {};
+    let state = GameState::Rolled { value: 3 };
+
+    let (_rejointx, mut rejoinrx) = tokio::sync::mpsc::unbounded_channel();
+
+    let n_state = server::statemachine::step(state, &mut game, &mut rejoinrx, &mut distr)
+        .await
+        .expect("");
+
+    assert_eq!(Figure::OnField { moved: 8 }, game.players[0].figures[0]);
+    assert_eq!(GameState::MoveToNextTurn, n_state);
+    assert!(game.players[0].is_connected());
+    assert!(!game.players[1].is_connected());
+    assert!(responses(&mut rx2).is_empty());
+
+    let received = responses(&mut rx1);
+    assert_eq!(2, received.len());
+    assert!(matches!(received[0], GameResponse::State { .. }));
+    assert!(matches!(received[1], GameResponse::Board { .. }));
+}
+
+#[tokio::test]
+#[traced_test]
+async fn rejoin_while_not_on_turn() {
+    let (tx1, mut rx1) = tokio::sync::mpsc::unbounded_channel();
+    let (tx2, _rx2) = tokio::sync::mpsc::unbounded_channel();
+
+    let mut game: Game<_, MockSocket<Message>, MockSocket<Message>> = Game::new_with_rng(
+        uuid::Uuid::new_v4(),
+        vec![
+            GamePlayer::new(
+                "test".to_string(),
+                MockSocket::new(
+                    tx1,
+                    vec![Message::Text(
+                        serde_json::to_string(&GameRequest::Roll).unwrap(),
+                    )],
+                )
+                .split(),
+            ),
+            GamePlayer::new(
+                "test2".to_string(),
+                MockSocket::new_failing(tx2, vec![]).split(),
+            ),
+        ],
+        MockRand::new(vec![0, 2]),
+    );
+
+    game.next_player = 0;
+    game.players[0].figures[0] = Figure::OnField { moved: 5 };
+
+    let mut distr = DumbDistr {};
+    let (rejointx, mut rejoinrx) = tokio::sync::mpsc::unbounded_channel();
+
+    // Player 1 drops while the State is sent during the Turn of Player 0
+    assert!(game.send_state().await.is_err());
+    assert!(!game.players[1].is_connected());
+    let _ = responses(&mut rx1);
+
+    let (tx3, mut rx3) = tokio::sync::mpsc::unbounded_channel();
+    rejointx
+        .send((
+            server::RejoinKey::Name("test2".to_string()),
+            MockSocket::new(tx3, vec![]).split(),
+        ))
+        .unwrap();
+
+    let n_state = server::statemachine::step(
+        GameState::StartTurn { attempt: 0 },
+        &mut game,
+        &mut rejoinrx,
+        &mut distr,
+    )
+    .await
+    .expect("");
+
+    assert_eq!(GameState::Rolled { value: 3 }, n_state);
+    assert!(game.players[1].is_connected());
+
+    let received = responses(&mut rx3);
+    assert!(matches!(received[0], GameResponse::State { .. }));
+    assert!(matches!(received[1], GameResponse::Board { .. }));
+    assert!(received.iter().any(|r| matches!(
+        r,
+        GameResponse::IndicatePlayer {
+            you: true,
+            player: 1,
+            ..
+        }
+    )));
+}
+
+#[tokio::test]
+#[traced_test]
+async fn disconnect_after_roll_keeps_the_roll() {
+    let (tx1, mut rx1) = tokio::sync::mpsc::unbounded_channel();
+    let (tx2, _rx2) = tokio::sync::mpsc::unbounded_channel();
+
+    let first = MockSocket::new(tx1, vec![]);
+    let mut game: Game<_, MockSocket<Message>, MockSocket<Message>> = Game::new_with_rng(
+        uuid::Uuid::new_v4(),
+        vec![
+            GamePlayer::new("test".to_string(), first.clone().split()),
+            GamePlayer::new("test2".to_string(), MockSocket::new(tx2, vec![]).split()),
+        ],
+        MockRand::new(vec![2]),
+    );
+
+    game.next_player = 0;
+    game.players[0].figures[0] = Figure::OnField { moved: 5 };
+
+    let mut distr = DumbDistr {};
+    let (rejointx, mut rejoinrx) = tokio::sync::mpsc::unbounded_channel();
+
+    // The Connection of Player 0 drops right after they asked to roll
+    let (n_state, _) = tokio::join!(
+        server::statemachine::step(
+            GameState::StartTurn { attempt: 0 },
+            &mut game,
+            &mut rejoinrx,
+            &mut distr,
+        ),
+        async {
+            while responses(&mut rx1).is_empty() {
+                tokio::time::sleep(std::time::Duration::from_millis(5)).await;
+            }
+            first.fail();
+            first.push(Message::Text(
+                serde_json::to_string(&GameRequest::Roll).unwrap(),
+            ));
+        }
+    );
+
+    assert_eq!(
+        Some(GameState::WaitingForReconnect {
+            prev_state: Box::new(GameState::Rolled { value: 3 }),
+        }),
+        n_state
+    );
+    assert!(!game.players[0].is_connected());
+    assert_eq!(vec![3], game.players[0].recent_rolls());
+
+    // After rejoining, the Player can only move with the Value they already rolled
+    let (tx3, mut rx3) = tokio::sync::mpsc::unbounded_channel();
+    rejointx
+        .send((
+            server::RejoinKey::Name("test".to_string()),
+            MockSocket::new(tx3, vec![]).split(),
+        ))
+        .unwrap();
+
+    let n_state =
+        server::statemachine::step(n_state.unwrap(), &mut game, &mut rejoinrx, &mut distr)
+            .await
+            .expect("");
+
+    assert_eq!(GameState::Rolled { value: 3 }, n_state);
+    assert_eq!(vec![3], game.players[0].recent_rolls());
+    let received = responses(&mut rx3);
+    assert!(received.contains(&GameResponse::Rolled {
+        value: 3,
+        can_move: true,
+        attempts_left: None,
+    }));
+    assert!(received
+        .iter()
+        .any(|r| matches!(r, GameResponse::MoveOptions { value: 3, .. })));
+}
+
+#[tokio::test]
+#[traced_test]
+async fn missed_pong_disconnects() {
+    let (tx1, mut rx1) = tokio::sync::mpsc::unbounded_channel();
+    let (tx2, _rx2) = tokio::sync::mpsc::unbounded_channel();
+
+    let mut game: Game<_, MockSocket<Message>, MockSocket<Message>> = Game::new_with_rng(
+        uuid::Uuid::new_v4(),
+        vec![
+            GamePlayer::new("test".to_string(), MockSocket::new(tx1, vec![]).split()),
+            GamePlayer::new("test2".to_string(), MockSocket::new(tx2, vec![]).split()),
+        ],
+        MockRand::new(vec![0]),
+    );
+
+    game.next_player = 0;
+    game.ping_interval = std::time::Duration::from_millis(10);
+    game.pong_timeout = std::time::Duration::from_millis(50);
+
+    let mut distr = DumbDistr {};
+    let state = GameState::StartTurn { attempt: 0 };
+
+    let (_rejointx, mut rejoinrx) = tokio::sync::mpsc::unbounded_channel();
+
+    let n_state = server::statemachine::step(state, &mut game, &mut rejoinrx, &mut distr)
+        .await
+        .expect("");
+
+    assert_eq!(
+        GameState::WaitingForReconnect {
+            prev_state: Box::new(GameState::StartTurn { attempt: 0 })
+        },
+        n_state
+    );
+    assert!(!game.players[0].is_connected());
+
+    let mut pings = 0;
+    while let Ok(msg) = rx1.try_recv() {
+        if matches!(msg, Message::Ping(_)) {
+            pings += 1;
+        }
+    }
+    assert!(pings > 0);
+}
+
+#[tokio::test]
+#[traced_test]
+async fn pong_updates_last_seen() {
+    let (tx1, _rx1) = tokio::sync::mpsc::unbounded_channel();
+    let (tx2, _rx2) = tokio::sync::mpsc::unbounded_channel();
+
+    let mut game: Game<_, MockSocket<Message>, MockSocket<Message>> = Game::new_with_rng(
+        uuid::Uuid::new_v4(),
+        vec![
+            GamePlayer::new(
+                "test".to_string(),
+                MockSocket::new(
+                    tx1,
+                    vec![
+                        Message::Pong(Vec::new()),
+                        Message::Text(serde_json::to_string(&GameRequest::Roll).unwrap()),
+                    ],
+                )
+                .split(),
+            ),
+            GamePlayer::new("test2".to_string(), MockSocket::new(tx2, vec![]).split()),
+        ],
+        MockRand::new(vec![0, 0]),
+    );
+
+    game.next_player = 0;
+    let before = game.players[0].last_seen;
+
+    let mut distr = DumbDistr {};
+    let state = GameState::StartTurn { attempt: 0 };
+
+    let (_rejointx, mut rejoinrx) = tokio::sync::mpsc::unbounded_channel();
+
+    let n_state = server::statemachine::step(state, &mut game, &mut rejoinrx, &mut distr)
+        .await
+        .expect("");
+
+    assert_eq!(GameState::StartTurn { attempt: 1 }, n_state);
+    assert!(game.players[0].last_seen > before);
+    assert!(game.players[0].is_connected());
+}
+
+#[tokio::test]
+#[traced_test]
+async fn others_notified_of_leave_and_rejoin() {
+    let (tx1, _rx1) = tokio::sync::mpsc::unbounded_channel();
+    let (tx2, mut rx2) = tokio::sync::mpsc::unbounded_channel();
+
+    let mut game: Game<_, MockSocket<Message>, MockSocket<Message>> = Game::new_with_rng(
+        uuid::Uuid::new_v4(),
+        vec![
+            GamePlayer::new(
+                "test".to_string(),
+                MockSocket::new_failing(tx1, vec![]).split(),
+            ),
+            GamePlayer::new("test2".to_string(), MockSocket::new(tx2, vec![]).split()),
+        ],
+        MockRand::new(vec![0]),
+    );
+
+    game.next_player = 0;
+
+    let mut distr = DumbDistr {};
+    let (rejointx, mut rejoinrx) = tokio::sync::mpsc::unbounded_channel();
+
+    let n_state = server::statemachine::step(
+        GameState::StartTurn { attempt: 0 },
+        &mut game,
+        &mut rejoinrx,
+        &mut distr,
+    )
+    .await
+    .expect("");
+
+    assert_eq!(
+        GameState::WaitingForReconnect {
+            prev_state: Box::new(GameState::StartTurn { attempt: 0 })
+        },
+        n_state
+    );
+    assert_eq!(
+        vec![
+            GameResponse::PlayerLeft { player: 0 },
+            GameResponse::WaitingFor { players: vec![0] }
+        ],
+        responses(&mut rx2)
+    );
+
+    let (tx3, _rx3) = tokio::sync::mpsc::unbounded_channel();
+    rejointx
+        .send((
+            server::RejoinKey::Name("test".to_string()),
+            MockSocket::new(tx3, vec![]).split(),
+        ))
+        .unwrap();
+
+    let n_state = server::statemachine::step(n_state, &mut game, &mut rejoinrx, &mut distr)
+        .await
+        .expect("");
+
+    assert_eq!(GameState::StartTurn { attempt: 0 }, n_state);
+    assert!(game.players[0].is_connected());
+
+    let received = responses(&mut rx2);
+    assert_eq!(GameResponse::PlayerRejoined { player: 0 }, received[0]);
+    assert!(matches!(received[1], GameResponse::State { .. }));
+}
+
+#[tokio::test]
+#[traced_test]
+async fn paused_while_waiting_for_reconnect() {
+    let (tx1, _rx1) = tokio::sync::mpsc::unbounded_channel();
+    let (tx2, _rx2) = tokio::sync::mpsc::unbounded_channel();
+
+    let mut game: Game<_, MockSocket<Message>, MockSocket<Message>> = Game::new_with_rng(
+        uuid::Uuid::new_v4(),
+        vec![
+            GamePlayer::new("test".to_string(), MockSocket::new(tx1, vec![]).split()),
+            GamePlayer::new(
+                "test2".to_string(),
+                MockSocket::new_failing(tx2, vec![]).split(),
+            ),
+        ],
+        MockRand::new(vec![0]),
+    );
+
+    game.next_player = 1;
+    assert_eq!("test2", game.active_player().name);
+    assert!(!game.is_paused());
+
+    let mut distr = DumbDistr {};
+    let (rejointx, mut rejoinrx) = tokio::sync::mpsc::unbounded_channel();
+
+    let n_state = server::statemachine::step(
+        GameState::StartTurn { attempt: 0 },
+        &mut game,
+        &mut rejoinrx,
+        &mut distr,
+    )
+    .await
+    .expect("");
+    assert!(game.is_paused());
+    assert!(!game.active_player().is_connected());
+
+    let (tx3, _rx3) = tokio::sync::mpsc::unbounded_channel();
+    rejointx
+        .send((
+            server::RejoinKey::Name("test2".to_string()),
+            MockSocket::new(tx3, vec![]).split(),
+        ))
+        .unwrap();
+
+    server::statemachine::step(n_state, &mut game, &mut rejoinrx, &mut distr)
+        .await
+        .expect("");
+    assert!(!game.is_paused());
+    assert!(game.active_player().is_connected());
+}
+
+#[tokio::test]
+#[traced_test]
+async fn aborted_without_rejoins() {
+    let (tx1, mut rx1) = tokio::sync::mpsc::unbounded_channel();
+    let (tx2, _rx2) = tokio::sync::mpsc::unbounded_channel();
+
+    let mut game: Game<_, MockSocket<Message>, MockSocket<Message>> = Game::new_with_rng(
+        uuid::Uuid::new_v4(),
+        vec![
+            GamePlayer::new("test".to_string(), MockSocket::new(tx1, vec![]).split()),
+            GamePlayer::new(
+                "test2".to_string(),
+                MockSocket::new_failing(tx2, vec![]).split(),
+            ),
+        ],
+        MockRand::new(vec![0]),
+    );
+    game.players[0].figures[0] = Figure::InHouse { pos: 3 };
+
+    let mut distr = DumbDistr {};
+    let (rejointx, mut rejoinrx) = tokio::sync::mpsc::unbounded_channel();
+    drop(rejointx);
+
+    let n_state = server::statemachine::step(
+        GameState::WaitingForReconnect {
+            prev_state: Box::new(GameState::StartTurn { attempt: 0 }),
+        },
+        &mut game,
+        &mut rejoinrx,
+        &mut distr,
+    )
+    .await
+    .expect("");
+
+    assert_eq!(GameState::Done, n_state);
+    assert!(game.is_done());
+    assert_eq!(
+        Some(&GameResponse::GameDone {
+            ranking: vec![0, 1],
+            reason: GameEndReason::Aborted,
+        }),
+        responses(&mut rx1).last()
+    );
+}
+
+#[tokio::test]
+#[traced_test]
+async fn waiting_for_shrinks_with_rejoins() {
+    let (tx1, _rx1) = tokio::sync::mpsc::unbounded_channel();
+    let (tx2, _rx2) = tokio::sync::mpsc::unbounded_channel();
+    let (tx3, mut rx3) = tokio::sync::mpsc::unbounded_channel();
+
+    let mut game: Game<_, MockSocket<Message>, MockSocket<Message>> = Game::new_with_rng(
+        uuid::Uuid::new_v4(),
+        vec![
+            GamePlayer::new(
+                "test".to_string(),
+                MockSocket::new_failing(tx1, vec![]).split(),
+            ),
+            GamePlayer::new(
+                "test2".to_string(),
+                MockSocket::new_failing(tx2, vec![]).split(),
+            ),
+            GamePlayer::new("test3".to_string(), MockSocket::new(tx3, vec![]).split()),
+        ],
+        MockRand::new(vec![0]),
+    );
+
+    game.next_player = 0;
+
+    let mut distr = DumbDistr {};
+    let (rejointx, mut rejoinrx) = tokio::sync::mpsc::unbounded_channel();
+
+    let n_state = server::statemachine::step(
+        GameState::StartTurn { attempt: 0 },
+        &mut game,
+        &mut rejoinrx,
+        &mut distr,
+    )
+    .await
+    .expect("");
+
+    assert_eq!(vec![0, 1], game.disconnected_players());
+    assert!(responses(&mut rx3).contains(&GameResponse::WaitingFor {
+        players: vec![0, 1]
+    }));
+
+    // The Game keeps waiting for the active Player, when someone else rejoins
+    let (tx4, _rx4) = tokio::sync::mpsc::unbounded_channel();
+    rejointx
+        .send((
+            server::RejoinKey::Name("test2".to_string()),
+            MockSocket::new(tx4, vec![]).split(),
+        ))
+        .unwrap();
+
+    let n_state = server::statemachine::step(n_state, &mut game, &mut rejoinrx, &mut distr)
+        .await
+        .expect("");
+
+    assert_eq!(
+        GameState::WaitingForReconnect {
+            prev_state: Box::new(GameState::StartTurn { attempt: 0 })
+        },
+        n_state
+    );
+    assert!(responses(&mut rx3).contains(&GameResponse::WaitingFor { players: vec![0] }));
+
+    let (tx5, _rx5) = tokio::sync::mpsc::unbounded_channel();
+    rejointx
+        .send((
+            server::RejoinKey::Name("test".to_string()),
+            MockSocket::new(tx5, vec![]).split(),
+        ))
+        .unwrap();
+
+    let n_state = server::statemachine::step(n_state, &mut game, &mut rejoinrx, &mut distr)
+        .await
+        .expect("");
+
+    assert_eq!(GameState::StartTurn { attempt: 0 }, n_state);
+    assert!(responses(&mut rx3).contains(&GameResponse::WaitingFor { players: vec![] }));
+}
+
+#[tokio::test]
+#[traced_test]
+async fn quick_reconnect_is_not_announced() {
+    let (tx1, _rx1) = tokio::sync::mpsc::unbounded_channel();
+    let (tx2, mut rx2) = tokio::sync::mpsc::unbounded_channel();
+
+    let mut game: Game<_, MockSocket<Message>, MockSocket<Message>> = Game::new_with_rng(
+        uuid::Uuid::new_v4(),
+        vec![
+            GamePlayer::new(
+                "test".to_string(),
+                MockSocket::new_failing(tx1, vec![]).split(),
+            ),
+            GamePlayer::new("test2".to_string(), MockSocket::new(tx2, vec![]).split()),
+        ],
+        MockRand::new(vec![0]),
+    );
+    game.next_player = 0;
+    game.reconnect_grace = std::time::Duration::from_secs(60);
+
+    let mut distr = DumbDistr {};
+    let (rejointx, mut rejoinrx) = tokio::sync::mpsc::unbounded_channel();
+
+    let n_state = server::statemachine::step(
+        GameState::StartTurn { attempt: 0 },
+        &mut game,
+        &mut rejoinrx,
+        &mut distr,
+    )
+    .await
+    .expect("");
+    assert!(matches!(n_state, GameState::WaitingForReconnect { .. }));
+
+    let (tx3, _rx3) = tokio::sync::mpsc::unbounded_channel();
+    rejointx
+        .send((
+            server::RejoinKey::Name("test".to_string()),
+            MockSocket::new(tx3, vec![]).split(),
+        ))
+        .unwrap();
+
+    let n_state = server::statemachine::step(n_state, &mut game, &mut rejoinrx, &mut distr)
+        .await
+        .expect("");
+    assert_eq!(GameState::StartTurn { attempt: 0 }, n_state);
+
+    let received = responses(&mut rx2);
+    assert!(!received.iter().any(|r| matches!(
+        r,
+        GameResponse::PlayerLeft { .. }
+            | GameResponse::PlayerRejoined { .. }
+            | GameResponse::WaitingFor { .. }
+    )));
+}
+
+#[tokio::test]
+#[traced_test]
+async fn leave_announced_after_grace() {
+    let (tx1, _rx1) = tokio::sync::mpsc::unbounded_channel();
+    let (tx2, mut rx2) = tokio::sync::mpsc::unbounded_channel();
+
+    let mut game: Game<_, MockSocket<Message>, MockSocket<Message>> = Game::new_with_rng(
+        uuid::Uuid::new_v4(),
+        vec![
+            GamePlayer::new(
+                "test".to_string(),
+                MockSocket::new_failing(tx1, vec![]).split(),
+            ),
+            GamePlayer::new("test2".to_string(), MockSocket::new(tx2, vec![]).split()),
+        ],
+        MockRand::new(vec![0]),
+    );
+    game.next_player = 0;
+    game.reconnect_grace = std::time::Duration::from_millis(10);
+
+    let mut distr = DumbDistr {};
+    let (_rejointx, mut rejoinrx) = tokio::sync::mpsc::unbounded_channel();
+
+    let n_state = server::statemachine::step(
+        GameState::StartTurn { attempt: 0 },
+        &mut game,
+        &mut rejoinrx,
+        &mut distr,
+    )
+    .await
+    .expect("");
+    assert!(responses(&mut rx2).is_empty());
+
+    let n_state = server::statemachine::step(n_state, &mut game, &mut rejoinrx, &mut distr)
+        .await
+        .expect("");
+    assert_eq!(
+        GameState::WaitingForReconnect {
+            prev_state: Box::new(GameState::StartTurn { attempt: 0 })
+        },
+        n_state
+    );
+    assert_eq!(
+        vec![
+            GameResponse::PlayerLeft { player: 0 },
+            GameResponse::WaitingFor { players: vec![0] }
+        ],
+        responses(&mut rx2)
+    );
+}
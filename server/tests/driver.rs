@@ -0,0 +1,66 @@
+use axum::extract::ws::Message;
+use futures::StreamExt;
+use server::{Figure, Game, GameEndReason, GamePlayer, GameRequest, GameResponse};
+
+use mocks::{MockRand, MockSocket, SequenceDistr};
+use tracing_test::traced_test;
+
+mod common;
+use common::responses;
+
+#[tokio::test]
+#[traced_test]
+async fn scripted_game() {
+    let (tx1, mut rx1) = tokio::sync::mpsc::unbounded_channel();
+    let (tx2, mut rx2) = tokio::sync::mpsc::unbounded_channel();
+
+    let requests = || {
+        vec![
+            Message::Text(serde_json::to_string(&GameRequest::Roll).unwrap()),
+            Message::Text(serde_json::to_string(&GameRequest::Move { figure: 3 }).unwrap()),
+        ]
+    };
+    let almost_done = |moved: usize| {
+        [
+            Figure::InHouse { pos: 1 },
+            Figure::InHouse { pos: 2 },
+            Figure::InHouse { pos: 3 },
+            Figure::OnField { moved },
+        ]
+    };
+
+    let mut game: Game<_, MockSocket<Message>, MockSocket<Message>> = Game::from_state(
+        uuid::Uuid::new_v4(),
+        vec![
+            (
+                GamePlayer::new("test".to_string(), MockSocket::new(tx1, requests()).split()),
+                almost_done(38),
+            ),
+            (
+                GamePlayer::new(
+                    "test2".to_string(),
+                    MockSocket::new(tx2, requests()).split(),
+                ),
+                almost_done(37),
+            ),
+        ],
+        0,
+        Vec::new(),
+        MockRand::new(vec![0]),
+    );
+
+    let mut distr = SequenceDistr::new(vec![2, 3]);
+    let (_rejointx, mut rejoinrx) = tokio::sync::mpsc::unbounded_channel();
+
+    server::run_game(&mut game, &mut rejoinrx, &mut distr).await;
+
+    assert!(game.is_done());
+    assert_eq!(vec![0, 1], game.ranking);
+
+    let done = GameResponse::GameDone {
+        ranking: vec![0, 1],
+        reason: GameEndReason::AllFinished,
+    };
+    assert_eq!(Some(&done), responses(&mut rx1).last());
+    assert_eq!(Some(&done), responses(&mut rx2).last());
+}
@@ -0,0 +1,328 @@
+use axum::extract::ws::Message;
+use futures::StreamExt;
+use server::{
+    statemachine::GameState, Figure, Game, GameEndReason, GamePlayer, GameRequest, GameResponse,
+};
+
+use mocks::{DumbDistr, MockRand, MockSocket, SequenceDistr};
+use tracing_test::traced_test;
+
+mod common;
+use common::responses;
+
+#[tokio::test]
+#[traced_test]
+async fn finish_from_state() {
+    let (tx1, mut rx1) = tokio::sync::mpsc::unbounded_channel();
+    let (tx2, mut rx2) = tokio::sync::mpsc::unbounded_channel();
+
+    let mut game: Game<_, MockSocket<Message>, MockSocket<Message>> = Game::from_state(
+        uuid::Uuid::new_v4(),
+        vec![
+            (
+                GamePlayer::new(
+                    "test".to_string(),
+                    MockSocket::new(
+                        tx1,
+                        vec![
+                            Message::Text(serde_json::to_string(&GameRequest::Roll).unwrap()),
+                            Message::Text(
+                                serde_json::to_string(&GameRequest::Move { figure: 0 }).unwrap(),
+                            ),
+                        ],
+                    )
+                    .split(),
+                ),
+                [
+                    Figure::OnField { moved: 39 },
+                    Figure::InHouse { pos: 1 },
+                    Figure::InHouse { pos: 2 },
+                    Figure::InHouse { pos: 3 },
+                ],
+            ),
+            (
+                GamePlayer::new("test2".to_string(), MockSocket::new(tx2, vec![]).split()),
+                [
+                    Figure::InHouse { pos: 0 },
+                    Figure::InHouse { pos: 1 },
+                    Figure::InHouse { pos: 2 },
+                    Figure::InHouse { pos: 3 },
+                ],
+            ),
+        ],
+        0,
+        vec![1],
+        MockRand::new(vec![0]),
+    );
+
+    assert!(!game.players[0].is_done());
+    assert!(game.players[1].is_done());
+
+    let mut distr = DumbDistr {};
+    let (_rejointx, mut rejoinrx) = tokio::sync::mpsc::unbounded_channel();
+
+    let mut state = GameState::StartTurn { attempt: 0 };
+    let mut states = Vec::new();
+    while let Some(n_state) =
+        server::statemachine::step(state, &mut game, &mut rejoinrx, &mut distr).await
+    {
+        states.push(format!("{:?}", n_state));
+        state = n_state;
+    }
+
+    assert_eq!(
+        vec!["Rolled { value: 1 }", "MoveToNextTurn", "Done"],
+        states
+    );
+    assert_eq!(Figure::InHouse { pos: 0 }, game.players[0].figures[0]);
+    assert_eq!(vec![1, 0], game.ranking);
+    assert!(game.is_done());
+
+    let expected_done = GameResponse::GameDone {
+        ranking: vec![1, 0],
+        reason: GameEndReason::AllFinished,
+    };
+    assert_eq!(Some(&expected_done), responses(&mut rx1).last());
+    assert_eq!(Some(&expected_done), responses(&mut rx2).last());
+}
+
+#[tokio::test]
+#[traced_test]
+async fn two_players_finish_back_to_back() {
+    let (tx1, _rx1) = tokio::sync::mpsc::unbounded_channel();
+    let (tx2, mut rx2) = tokio::sync::mpsc::unbounded_channel();
+    let (tx3, _rx3) = tokio::sync::mpsc::unbounded_channel();
+
+    let almost_done = [
+        Figure::OnField { moved: 39 },
+        Figure::InHouse { pos: 1 },
+        Figure::InHouse { pos: 2 },
+        Figure::InHouse { pos: 3 },
+    ];
+    let move_msg = || {
+        vec![Message::Text(
+            serde_json::to_string(&GameRequest::Move { figure: 0 }).unwrap(),
+        )]
+    };
+
+    let mut game: Game<_, MockSocket<Message>, MockSocket<Message>> = Game::from_state(
+        uuid::Uuid::new_v4(),
+        vec![
+            (
+                GamePlayer::new("test".to_string(), MockSocket::new(tx1, move_msg()).split()),
+                almost_done.clone(),
+            ),
+            (
+                GamePlayer::new(
+                    "test2".to_string(),
+                    MockSocket::new(tx2, move_msg()).split(),
+                ),
+                almost_done.clone(),
+            ),
+            (
+                GamePlayer::new("test3".to_string(), MockSocket::new(tx3, vec![]).split()),
+                [
+                    Figure::InStart,
+                    Figure::InStart,
+                    Figure::InStart,
+                    Figure::InStart,
+                ],
+            ),
+        ],
+        0,
+        Vec::new(),
+        MockRand::new(vec![0]),
+    );
+
+    let mut distr = DumbDistr {};
+    let (_rejointx, mut rejoinrx) = tokio::sync::mpsc::unbounded_channel();
+
+    for player in 0..2 {
+        assert_eq!(player, game.next_player);
+
+        let state = GameState::Rolled { value: 1 };
+        let n_state = server::statemachine::step(state, &mut game, &mut rejoinrx, &mut distr)
+            .await
+            .expect("");
+        assert_eq!(GameState::MoveToNextTurn, n_state);
+
+        let n_state = server::statemachine::step(n_state, &mut game, &mut rejoinrx, &mut distr)
+            .await
+            .expect("");
+        assert_eq!(GameState::StartTurn { attempt: 0 }, n_state);
+    }
+
+    assert_eq!(vec![0, 1], game.ranking);
+    assert_eq!(2, game.next_player);
+    let _ = responses(&mut rx2);
+
+    // Even if a finished Player ends up as the next Player, they are not asked to take a Turn
+    game.next_player = 1;
+    let n_state = server::statemachine::step(
+        GameState::StartTurn { attempt: 0 },
+        &mut game,
+        &mut rejoinrx,
+        &mut distr,
+    )
+    .await
+    .expect("");
+    assert_eq!(GameState::MoveToNextTurn, n_state);
+    assert!(responses(&mut rx2).is_empty());
+
+    let n_state = server::statemachine::step(n_state, &mut game, &mut rejoinrx, &mut distr)
+        .await
+        .expect("");
+    assert_eq!(GameState::StartTurn { attempt: 0 }, n_state);
+    assert_eq!(2, game.next_player);
+    assert_eq!(vec![0, 1], game.ranking);
+}
+
+#[tokio::test]
+#[traced_test]
+async fn max_turns_ends_game() {
+    let (tx1, mut rx1) = tokio::sync::mpsc::unbounded_channel();
+    let (tx2, _rx2) = tokio::sync::mpsc::unbounded_channel();
+
+    let roll = || {
+        vec![Message::Text(
+            serde_json::to_string(&GameRequest::Roll).unwrap(),
+        )]
+    };
+
+    let mut game: Game<_, MockSocket<Message>, MockSocket<Message>> = Game::from_state(
+        uuid::Uuid::new_v4(),
+        vec![
+            (
+                GamePlayer::new("test".to_string(), MockSocket::new(tx1, roll()).split()),
+                [
+                    Figure::InStart,
+                    Figure::InStart,
+                    Figure::InStart,
+                    Figure::InStart,
+                ],
+            ),
+            (
+                GamePlayer::new("test2".to_string(), MockSocket::new(tx2, roll()).split()),
+                [
+                    Figure::InStart,
+                    Figure::InStart,
+                    Figure::InStart,
+                    Figure::InHouse { pos: 3 },
+                ],
+            ),
+        ],
+        0,
+        Vec::new(),
+        MockRand::new(vec![0]),
+    );
+    game.start_attempts = 0;
+    game.max_turns = Some(2);
+
+    let mut distr = SequenceDistr::new(vec![1, 1]);
+    let (_rejointx, mut rejoinrx) = tokio::sync::mpsc::unbounded_channel();
+
+    let mut state = GameState::StartTurn { attempt: 0 };
+    let mut states = Vec::new();
+    while let Some(n_state) =
+        server::statemachine::step(state, &mut game, &mut rejoinrx, &mut distr).await
+    {
+        states.push(format!("{:?}", n_state));
+        state = n_state;
+    }
+
+    assert_eq!(
+        vec![
+            "MoveToNextTurn",
+            "StartTurn { attempt: 0 }",
+            "MoveToNextTurn",
+            "Done"
+        ],
+        states
+    );
+    assert_eq!(2, game.turns());
+    assert_eq!(vec![1, 0], game.ranking);
+    assert!(game.is_done());
+    assert_eq!(
+        Some(&GameResponse::GameDone {
+            ranking: vec![1, 0],
+            reason: GameEndReason::TurnCapReached,
+        }),
+        responses(&mut rx1).last()
+    );
+}
+
+#[tokio::test]
+#[traced_test]
+async fn winner_is_announced_once_first_player_finishes() {
+    let (tx1, mut rx1) = tokio::sync::mpsc::unbounded_channel();
+    let (tx2, mut rx2) = tokio::sync::mpsc::unbounded_channel();
+
+    let mut game: Game<_, MockSocket<Message>, MockSocket<Message>> = Game::from_state(
+        uuid::Uuid::new_v4(),
+        vec![
+            (
+                GamePlayer::new(
+                    "test".to_string(),
+                    MockSocket::new(
+                        tx1,
+                        vec![Message::Text(
+                            serde_json::to_string(&GameRequest::Move { figure: 0 }).unwrap(),
+                        )],
+                    )
+                    .split(),
+                ),
+                [
+                    Figure::OnField { moved: 39 },
+                    Figure::InHouse { pos: 1 },
+                    Figure::InHouse { pos: 2 },
+                    Figure::InHouse { pos: 3 },
+                ],
+            ),
+            (
+                GamePlayer::new("test2".to_string(), MockSocket::new(tx2, vec![]).split()),
+                [
+                    Figure::OnField { moved: 5 },
+                    Figure::InStart,
+                    Figure::InStart,
+                    Figure::InStart,
+                ],
+            ),
+        ],
+        0,
+        Vec::new(),
+        MockRand::new(vec![0]),
+    );
+
+    let mut distr = DumbDistr {};
+    let (_rejointx, mut rejoinrx) = tokio::sync::mpsc::unbounded_channel();
+
+    let n_state = server::statemachine::step(
+        GameState::Rolled { value: 1 },
+        &mut game,
+        &mut rejoinrx,
+        &mut distr,
+    )
+    .await
+    .expect("");
+    assert_eq!(GameState::MoveToNextTurn, n_state);
+
+    let n_state = server::statemachine::step(n_state, &mut game, &mut rejoinrx, &mut distr)
+        .await
+        .expect("");
+
+    // The other Player plays on for the remaining Place
+    assert_eq!(GameState::StartTurn { attempt: 0 }, n_state);
+    assert_eq!(1, game.next_player);
+    assert_eq!(vec![0], game.ranking);
+
+    for received in [responses(&mut rx1), responses(&mut rx2)] {
+        let done = received
+            .iter()
+            .position(|r| r == &GameResponse::PlayerDone { player: 0 })
+            .expect("The Player is done");
+        assert_eq!(GameResponse::Winner { player: 0 }, received[done + 1]);
+        assert!(!received
+            .iter()
+            .any(|r| matches!(r, GameResponse::GameDone { .. })));
+    }
+}
@@ -0,0 +1,377 @@
+use axum::extract::ws::Message;
+use futures::StreamExt;
+use server::{
+    statemachine::GameState, Figure, Game, GameEvent, GamePlayer, GameRequest, GameResponse,
+    ReplayError, RollAuditLog, GENESIS_HASH,
+};
+
+use mocks::{MockRand, MockSocket, SequenceDistr};
+use tracing_test::traced_test;
+
+mod common;
+use common::{game_with_players, responses};
+
+#[tokio::test]
+#[traced_test]
+async fn scripted_game_history() {
+    let (tx1, _rx1) = tokio::sync::mpsc::unbounded_channel();
+    let (tx2, _rx2) = tokio::sync::mpsc::unbounded_channel();
+
+    let roll = || Message::Text(serde_json::to_string(&GameRequest::Roll).unwrap());
+
+    let mut game: Game<_, MockSocket<Message>, MockSocket<Message>> = Game::new_with_rng(
+        uuid::Uuid::new_v4(),
+        vec![
+            GamePlayer::new(
+                "test".to_string(),
+                MockSocket::new(tx1, vec![roll(), roll()]).split(),
+            ),
+            GamePlayer::new(
+                "test2".to_string(),
+                MockSocket::new(tx2, vec![roll(), roll(), roll()]).split(),
+            ),
+        ],
+        MockRand::new(vec![0, 0]),
+    );
+
+    game.next_player = 0;
+    game.players[1].figures[1] = Figure::OnField { moved: 23 };
+
+    let mut distr = SequenceDistr::new(vec![6, 3, 2, 2, 2]);
+    let mut state = GameState::StartTurn { attempt: 0 };
+
+    let (_rejointx, mut rejoinrx) = tokio::sync::mpsc::unbounded_channel();
+
+    for _ in 0..6 {
+        state = server::statemachine::step(state, &mut game, &mut rejoinrx, &mut distr)
+            .await
+            .expect("");
+    }
+
+    assert_eq!(GameState::MoveToNextTurn, state);
+
+    let expected = vec![
+        GameEvent::Rolled {
+            player: 0,
+            value: 6,
+        },
+        GameEvent::Moved {
+            player: 0,
+            figure: 0,
+            from: Figure::InStart,
+            to: Figure::OnField { moved: 0 },
+        },
+        GameEvent::Rolled {
+            player: 0,
+            value: 3,
+        },
+        GameEvent::Moved {
+            player: 0,
+            figure: 0,
+            from: Figure::OnField { moved: 0 },
+            to: Figure::OnField { moved: 3 },
+        },
+        GameEvent::Captured {
+            by: 0,
+            player: 1,
+            figure: 1,
+        },
+        GameEvent::Rolled {
+            player: 1,
+            value: 2,
+        },
+        GameEvent::Rolled {
+            player: 1,
+            value: 2,
+        },
+        GameEvent::Rolled {
+            player: 1,
+            value: 2,
+        },
+        GameEvent::TurnSkipped { player: 1 },
+    ];
+    assert_eq!(expected, game.history());
+
+    let serialized = serde_json::to_string(game.history()).unwrap();
+    let deserialized: Vec<GameEvent> = serde_json::from_str(&serialized).unwrap();
+    assert_eq!(expected, deserialized);
+}
+
+#[tokio::test]
+#[traced_test]
+async fn roll_history_on_request() {
+    let (tx1, mut rx1) = tokio::sync::mpsc::unbounded_channel();
+    let (tx2, _rx2) = tokio::sync::mpsc::unbounded_channel();
+
+    let roll = || Message::Text(serde_json::to_string(&GameRequest::Roll).unwrap());
+    let history = Message::Text(serde_json::to_string(&GameRequest::RollHistory).unwrap());
+
+    let mut game: Game<_, MockSocket<Message>, MockSocket<Message>> = Game::new_with_rng(
+        uuid::Uuid::new_v4(),
+        vec![
+            GamePlayer::new(
+                "test".to_string(),
+                MockSocket::new(tx1, vec![roll(), roll(), history, roll()]).split(),
+            ),
+            GamePlayer::new("test2".to_string(), MockSocket::new(tx2, vec![]).split()),
+        ],
+        MockRand::new(vec![0, 0]),
+    );
+    game.next_player = 0;
+
+    let mut distr = SequenceDistr::new(vec![1, 2, 3]);
+    let (_rejointx, mut rejoinrx) = tokio::sync::mpsc::unbounded_channel();
+
+    let mut state = GameState::StartTurn { attempt: 0 };
+    for _ in 0..3 {
+        state = server::statemachine::step(state, &mut game, &mut rejoinrx, &mut distr)
+            .await
+            .expect("");
+    }
+    assert_eq!(GameState::MoveToNextTurn, state);
+
+    assert_eq!(vec![1, 2, 3], game.players[0].recent_rolls());
+    assert!(game.players[1].recent_rolls().is_empty());
+    assert!(responses(&mut rx1).contains(&GameResponse::RollHistory { rolls: vec![1, 2] }));
+}
+
+#[tokio::test]
+#[traced_test]
+async fn roll_stats_count_every_roll() {
+    let (tx1, _rx1) = tokio::sync::mpsc::unbounded_channel();
+    let (tx2, _rx2) = tokio::sync::mpsc::unbounded_channel();
+
+    let roll = || Message::Text(serde_json::to_string(&GameRequest::Roll).unwrap());
+
+    let mut game: Game<_, MockSocket<Message>, MockSocket<Message>> = Game::new_with_rng(
+        uuid::Uuid::new_v4(),
+        vec![
+            GamePlayer::new(
+                "test".to_string(),
+                MockSocket::new(tx1, vec![roll(), roll(), roll()]).split(),
+            ),
+            GamePlayer::new(
+                "test2".to_string(),
+                MockSocket::new(tx2, vec![roll(), roll(), roll()]).split(),
+            ),
+        ],
+        MockRand::new(vec![0]),
+    );
+    game.next_player = 0;
+    assert_eq!([0; 6], game.roll_stats());
+
+    let mut distr = SequenceDistr::new(vec![1, 5, 1, 2, 4, 2]);
+    let (_rejointx, mut rejoinrx) = tokio::sync::mpsc::unbounded_channel();
+
+    let mut state = GameState::StartTurn { attempt: 0 };
+    for _ in 0..7 {
+        state = server::statemachine::step(state, &mut game, &mut rejoinrx, &mut distr)
+            .await
+            .expect("");
+    }
+    assert_eq!(GameState::MoveToNextTurn, state);
+
+    assert_eq!([2, 2, 0, 1, 1, 0], game.roll_stats());
+}
+
+#[tokio::test]
+#[traced_test]
+async fn roll_audit_log_is_chained() {
+    let (tx1, _rx1) = tokio::sync::mpsc::unbounded_channel();
+    let (tx2, _rx2) = tokio::sync::mpsc::unbounded_channel();
+
+    let roll = || Message::Text(serde_json::to_string(&GameRequest::Roll).unwrap());
+
+    let mut game: Game<_, MockSocket<Message>, MockSocket<Message>> = Game::new_with_rng(
+        uuid::Uuid::new_v4(),
+        vec![
+            GamePlayer::new(
+                "test".to_string(),
+                MockSocket::new(tx1, vec![roll(), roll(), roll()]).split(),
+            ),
+            GamePlayer::new(
+                "test2".to_string(),
+                MockSocket::new(tx2, vec![roll()]).split(),
+            ),
+        ],
+        MockRand::new(vec![0]),
+    );
+    game.next_player = 0;
+    game.audit_rolls = true;
+
+    let mut distr = SequenceDistr::new(vec![1, 5, 1, 2]);
+    let (_rejointx, mut rejoinrx) = tokio::sync::mpsc::unbounded_channel();
+
+    let mut state = GameState::StartTurn { attempt: 0 };
+    for _ in 0..5 {
+        state = server::statemachine::step(state, &mut game, &mut rejoinrx, &mut distr)
+            .await
+            .expect("");
+    }
+
+    let log = game.roll_audit_log();
+    let rolls: Vec<_> = log
+        .entries
+        .iter()
+        .map(|e| (e.turn, e.player, e.roll))
+        .collect();
+    assert_eq!(vec![(0, 0, 1), (0, 0, 5), (0, 0, 1), (1, 1, 2)], rolls);
+    assert_eq!(Ok(()), log.verify());
+    assert_eq!(log.entries[3].hash, log.head());
+
+    let mut tampered = log.clone();
+    tampered.entries[1].roll = 6;
+    assert_eq!(Err(1), tampered.verify());
+
+    // Recomputing the Hash of the changed Entry still breaks the Link to the next one
+    let mut relinked = RollAuditLog::new();
+    for entry in tampered.entries.iter().take(2) {
+        relinked.record(entry.turn, entry.player, entry.roll);
+    }
+    tampered.entries[1].hash = relinked.head();
+    assert_eq!(Err(2), tampered.verify());
+}
+
+#[test]
+fn roll_audit_hash_is_stable() {
+    let mut log = RollAuditLog::new();
+    assert_eq!("0".repeat(64), GENESIS_HASH);
+    assert_eq!(GENESIS_HASH, log.head());
+
+    // The SHA-256 Digest of `["000…000",0,1,6]`, which does not depend on the Build
+    log.record(0, 1, 6);
+    assert_eq!(
+        "b5660947b514cb2b0f7321487079da04c89ec8d6f232b5688214cc211bee5efd",
+        log.head()
+    );
+}
+
+#[tokio::test]
+#[traced_test]
+async fn replay_history_to_same_board() {
+    let (tx1, _rx1) = tokio::sync::mpsc::unbounded_channel();
+    let (tx2, _rx2) = tokio::sync::mpsc::unbounded_channel();
+
+    let roll = || Message::Text(serde_json::to_string(&GameRequest::Roll).unwrap());
+    let move_figure =
+        |figure| Message::Text(serde_json::to_string(&GameRequest::Move { figure }).unwrap());
+
+    let mut game: Game<_, MockSocket<Message>, MockSocket<Message>> = Game::new_with_rng(
+        uuid::Uuid::new_v4(),
+        vec![
+            GamePlayer::new(
+                "test".to_string(),
+                MockSocket::new(
+                    tx1,
+                    vec![
+                        roll(),
+                        roll(),
+                        roll(),
+                        move_figure(0),
+                        roll(),
+                        move_figure(0),
+                        roll(),
+                        move_figure(0),
+                    ],
+                )
+                .split(),
+            ),
+            GamePlayer::new(
+                "test2".to_string(),
+                MockSocket::new(tx2, vec![roll(), roll()]).split(),
+            ),
+        ],
+        MockRand::new(vec![0]),
+    );
+    game.next_player = 0;
+
+    // Both Players bring out a Figure, before the first one catches up to the second one and
+    // captures it
+    let mut distr = SequenceDistr::new(vec![6, 5, 6, 1, 6, 6, 4]);
+    let (_rejointx, mut rejoinrx) = tokio::sync::mpsc::unbounded_channel();
+
+    let mut state = GameState::StartTurn { attempt: 0 };
+    while !game
+        .history()
+        .iter()
+        .any(|e| matches!(e, GameEvent::Captured { .. }))
+    {
+        state = server::statemachine::step(state, &mut game, &mut rejoinrx, &mut distr)
+            .await
+            .expect("");
+    }
+
+    let fresh = || game_with_players(2);
+
+    let replayed = fresh().replay(game.history()).unwrap();
+    for (original, replayed) in game.players.iter().zip(replayed.players.iter()) {
+        assert_eq!(original.figures, replayed.figures);
+    }
+    assert_eq!(game.board(), replayed.board());
+    assert_eq!(game.history(), replayed.history());
+    assert_eq!(game.roll_stats(), replayed.roll_stats());
+    assert!(game.turns() > 0);
+    assert_eq!(game.turns(), replayed.turns());
+
+    // Moving a Figure further than rolled is detected
+    let mut tampered = game.history().to_vec();
+    assert_eq!(
+        GameEvent::Rolled {
+            player: 0,
+            value: 5
+        },
+        tampered[2]
+    );
+    tampered[2] = GameEvent::Rolled {
+        player: 0,
+        value: 4,
+    };
+    assert_eq!(
+        Err(ReplayError::WrongTarget { index: 3 }),
+        fresh().replay(&tampered).map(|_| ())
+    );
+}
+
+#[test]
+#[traced_test]
+fn replay_counts_consecutive_turns() {
+    let game = game_with_players(2);
+
+    // The first Player takes three Turns in a Row, as if the second one was skipped
+    let moved = |from, to| GameEvent::Moved {
+        player: 0,
+        figure: 0,
+        from,
+        to,
+    };
+    let events = [
+        GameEvent::Rolled {
+            player: 0,
+            value: 6,
+        },
+        moved(Figure::InStart, Figure::OnField { moved: 0 }),
+        GameEvent::Rolled {
+            player: 0,
+            value: 2,
+        },
+        moved(Figure::OnField { moved: 0 }, Figure::OnField { moved: 2 }),
+        GameEvent::Rolled {
+            player: 0,
+            value: 1,
+        },
+        GameEvent::TurnSkipped { player: 0 },
+        GameEvent::Rolled {
+            player: 0,
+            value: 4,
+        },
+        moved(Figure::OnField { moved: 2 }, Figure::OnField { moved: 6 }),
+        GameEvent::Rolled {
+            player: 1,
+            value: 3,
+        },
+    ];
+
+    let replayed = game.replay(&events).unwrap();
+    assert_eq!(3, replayed.turns());
+    assert_eq!(1, replayed.next_player);
+}
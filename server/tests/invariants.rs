@@ -0,0 +1,75 @@
+use server::{statemachine::GameState, Figure};
+
+use mocks::SequenceDistr;
+use tracing_test::traced_test;
+
+mod common;
+use common::two_player_game;
+
+#[test]
+#[traced_test]
+fn consistent_state() {
+    let (mut game, _rx) = two_player_game(vec![], vec![0]);
+    game.players[0].figures[0] = Figure::OnField { moved: 39 };
+    game.players[0].figures[1] = Figure::InHouse { pos: 3 };
+    game.players[1].figures[0] = Figure::OnField { moved: 39 };
+
+    game.assert_invariants();
+
+    game.allow_blockades = true;
+    game.players[0].figures[2] = Figure::OnField { moved: 39 };
+    game.assert_invariants();
+}
+
+#[test]
+#[traced_test]
+#[should_panic(expected = "Figures 0 and 2 of Player 1 are both at OnField { moved: 12 }")]
+fn shared_field() {
+    let (mut game, _rx) = two_player_game(vec![], vec![0]);
+    game.players[1].figures[0] = Figure::OnField { moved: 12 };
+    game.players[1].figures[2] = Figure::OnField { moved: 12 };
+
+    game.assert_invariants();
+}
+
+#[test]
+#[traced_test]
+#[should_panic(expected = "Figure 1 of Player 0 moved 40 Fields on a Track with 40 Fields")]
+fn beyond_track() {
+    let (mut game, _rx) = two_player_game(vec![], vec![0]);
+    game.players[0].figures[1] = Figure::OnField { moved: 40 };
+
+    game.assert_invariants();
+}
+
+#[test]
+#[traced_test]
+#[should_panic(expected = "Figure 3 of Player 1 is at Position 4 in a House of Size 4")]
+fn beyond_house() {
+    let (mut game, _rx) = two_player_game(vec![], vec![0]);
+    game.players[1].figures[3] = Figure::InHouse { pos: 4 };
+
+    game.assert_invariants();
+}
+
+// The Invariants are only checked after every Step in Builds with Debug-Assertions
+#[cfg(debug_assertions)]
+#[tokio::test]
+#[traced_test]
+#[should_panic(expected = "are both at")]
+async fn checked_after_step() {
+    let (mut game, _rx) = two_player_game(vec![], vec![0]);
+    game.players[1].figures[0] = Figure::InHouse { pos: 2 };
+    game.players[1].figures[1] = Figure::InHouse { pos: 2 };
+
+    let mut distr = SequenceDistr::new(vec![]);
+    let (_rejointx, mut rejoinrx) = tokio::sync::mpsc::unbounded_channel();
+
+    server::statemachine::step(
+        GameState::MoveToNextTurn,
+        &mut game,
+        &mut rejoinrx,
+        &mut distr,
+    )
+    .await;
+}
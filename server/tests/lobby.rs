@@ -0,0 +1,328 @@
+use axum::extract::ws::Message;
+use futures::StreamExt;
+use rand::SeedableRng;
+use server::{
+    lobby::LobbyEvent, Game, GameError, GamePlayer, GameRequest, GameResponse, PlayerColor,
+};
+
+use mocks::{MockRand, MockSocket};
+use tracing_test::traced_test;
+
+mod common;
+use common::responses;
+
+#[tokio::test]
+#[traced_test]
+async fn all_players_ready() {
+    let (tx1, mut rx1) = tokio::sync::mpsc::unbounded_channel();
+    let (tx2, mut rx2) = tokio::sync::mpsc::unbounded_channel();
+
+    let mut players = vec![
+        GamePlayer::new(
+            "test".to_string(),
+            MockSocket::new(
+                tx1,
+                vec![Message::Text(
+                    serde_json::to_string(&GameRequest::Ready).unwrap(),
+                )],
+            )
+            .split(),
+        ),
+        GamePlayer::new(
+            "test2".to_string(),
+            MockSocket::new(
+                tx2,
+                vec![
+                    Message::Text(serde_json::to_string(&GameRequest::Roll).unwrap()),
+                    Message::Text(serde_json::to_string(&GameRequest::Ready).unwrap()),
+                ],
+            )
+            .split(),
+        ),
+    ];
+
+    assert_eq!(Ok(()), server::lobby::wait_for_ready(&mut players).await);
+
+    assert_eq!(vec![GameResponse::AllReady], responses(&mut rx1));
+    assert_eq!(vec![GameResponse::AllReady], responses(&mut rx2));
+}
+
+#[tokio::test]
+#[traced_test]
+async fn player_leaves_before_ready() {
+    let (tx1, mut rx1) = tokio::sync::mpsc::unbounded_channel();
+    let (tx2, _rx2) = tokio::sync::mpsc::unbounded_channel();
+
+    let mut players = vec![
+        GamePlayer::new(
+            "test".to_string(),
+            MockSocket::new(
+                tx1,
+                vec![Message::Text(
+                    serde_json::to_string(&GameRequest::Ready).unwrap(),
+                )],
+            )
+            .split(),
+        ),
+        GamePlayer::new(
+            "test2".to_string(),
+            MockSocket::new(tx2, vec![Message::Close(None)]).split(),
+        ),
+    ];
+
+    assert_eq!(
+        Err(GameError::Disconnect),
+        server::lobby::wait_for_ready(&mut players).await
+    );
+    assert!(responses(&mut rx1).is_empty());
+}
+
+#[tokio::test]
+#[traced_test]
+async fn game_validates_player_count() {
+    let players = |count: usize| {
+        (0..count)
+            .map(|i| {
+                let (tx, _rx) = tokio::sync::mpsc::unbounded_channel();
+                GamePlayer::new(format!("test{}", i), MockSocket::new(tx, vec![]).split())
+            })
+            .collect::<Vec<_>>()
+    };
+
+    for count in [0, 1, 5] {
+        let game: Result<Game<_, MockSocket<Message>, MockSocket<Message>>, _> =
+            Game::new(uuid::Uuid::new_v4(), players(count));
+        assert!(
+            matches!(game, Err(GameError::Protocol { .. })),
+            "{} Players",
+            count
+        );
+    }
+
+    let game: Result<Game<_, MockSocket<Message>, MockSocket<Message>>, _> =
+        Game::new(uuid::Uuid::new_v4(), players(2));
+    assert!(game.is_ok());
+}
+
+#[tokio::test]
+#[traced_test]
+async fn duplicate_names_get_suffix() {
+    let (tx1, _rx1) = tokio::sync::mpsc::unbounded_channel();
+    let (tx2, mut rx2) = tokio::sync::mpsc::unbounded_channel();
+
+    let mut players = Vec::new();
+    server::lobby::join_player(
+        &mut players,
+        "test".to_string(),
+        MockSocket::new(tx1, vec![]).split(),
+    )
+    .await
+    .unwrap();
+    server::lobby::join_player(
+        &mut players,
+        "test".to_string(),
+        MockSocket::new(tx2, vec![]).split(),
+    )
+    .await
+    .unwrap();
+
+    assert_eq!("test", players[0].name);
+    assert_eq!("test (2)", players[1].name);
+
+    let received = responses(&mut rx2);
+    assert!(received.contains(&GameResponse::IndicatePlayer {
+        player: 1,
+        name: "test (2)".to_string(),
+        you: true,
+        team: None,
+        color: PlayerColor::Blue,
+    }));
+}
+
+#[tokio::test]
+#[traced_test]
+async fn invalid_names_are_rejected() {
+    for name in [
+        "".to_string(),
+        "x".repeat(server::lobby::MAX_NAME_LENGTH + 1),
+    ] {
+        let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+
+        let mut players = Vec::new();
+        let result =
+            server::lobby::join_player(&mut players, name, MockSocket::new(tx, vec![]).split())
+                .await;
+
+        assert!(matches!(result, Err(GameError::Protocol { .. })));
+        assert!(players.is_empty());
+        assert!(matches!(rx.try_recv(), Ok(Message::Close(Some(_)))));
+    }
+}
+
+#[tokio::test]
+#[traced_test]
+async fn seeded_starting_player() {
+    let players = || {
+        (0..4)
+            .map(|i| {
+                let (tx, _rx) = tokio::sync::mpsc::unbounded_channel();
+                GamePlayer::new(format!("test{}", i), MockSocket::new(tx, vec![]).split())
+            })
+            .collect::<Vec<_>>()
+    };
+    let game = |seed: u64| -> Game<_, MockSocket<Message>, MockSocket<Message>> {
+        Game::new_with_rng(
+            uuid::Uuid::new_v4(),
+            players(),
+            rand::rngs::SmallRng::seed_from_u64(seed),
+        )
+    };
+
+    let starting: Vec<_> = (0..8).map(|seed| game(seed).next_player).collect();
+    assert_eq!(
+        starting,
+        (0..8)
+            .map(|seed| game(seed).next_player)
+            .collect::<Vec<_>>()
+    );
+
+    // The starting Player is the first Value drawn from the given rng
+    let drawn: Game<_, MockSocket<Message>, MockSocket<Message>> = Game::new_with_rng(
+        uuid::Uuid::new_v4(),
+        players(),
+        MockRand::new(vec![3 << 62]),
+    );
+    assert_eq!(3, drawn.next_player);
+
+    assert_eq!(2, game(0).with_starting_player(2).next_player);
+}
+
+#[tokio::test]
+#[traced_test]
+async fn rename_before_start() {
+    let (tx1, mut rx1) = tokio::sync::mpsc::unbounded_channel();
+    let (tx2, mut rx2) = tokio::sync::mpsc::unbounded_channel();
+
+    let set_name = |name: &str| {
+        Message::Text(
+            serde_json::to_string(&GameRequest::SetName {
+                name: name.to_string(),
+            })
+            .unwrap(),
+        )
+    };
+    let ready = || Message::Text(serde_json::to_string(&GameRequest::Ready).unwrap());
+
+    let mut players = vec![
+        GamePlayer::new(
+            "test".to_string(),
+            MockSocket::new(tx1, vec![ready()]).split(),
+        ),
+        GamePlayer::new(
+            "placeholder".to_string(),
+            MockSocket::new(tx2, vec![set_name(""), set_name("test"), ready()]).split(),
+        ),
+    ];
+
+    assert_eq!(Ok(()), server::lobby::wait_for_ready(&mut players).await);
+
+    // The empty Name is rejected and the taken one gets a Suffix
+    assert_eq!("test", players[0].name);
+    assert_eq!("test (2)", players[1].name);
+
+    let received = responses(&mut rx1);
+    assert!(received.contains(&GameResponse::IndicatePlayer {
+        player: 1,
+        name: "test (2)".to_string(),
+        you: false,
+        team: None,
+        color: PlayerColor::Blue,
+    }));
+    assert_eq!(Some(&GameResponse::AllReady), received.last());
+
+    let received = responses(&mut rx2);
+    assert!(matches!(received[0], GameResponse::InvalidRequest { .. }));
+    assert!(received.contains(&GameResponse::IndicatePlayer {
+        player: 1,
+        name: "test (2)".to_string(),
+        you: true,
+        team: None,
+        color: PlayerColor::Blue,
+    }));
+}
+
+#[tokio::test]
+#[traced_test]
+async fn rename_keeps_own_name() {
+    let (tx, _rx) = tokio::sync::mpsc::unbounded_channel();
+
+    let mut players = vec![GamePlayer::new(
+        "test".to_string(),
+        MockSocket::new(tx, vec![]).split(),
+    )];
+
+    assert_eq!(
+        Ok(()),
+        server::lobby::rename_player(&mut players, 0, "test".to_string()).await
+    );
+    assert_eq!("test", players[0].name);
+}
+
+#[tokio::test]
+#[traced_test]
+async fn rename_while_seats_are_open() {
+    let (tx1, _rx1) = tokio::sync::mpsc::unbounded_channel();
+    let (tx2, _rx2) = tokio::sync::mpsc::unbounded_channel();
+    let (tx3, mut rx3) = tokio::sync::mpsc::unbounded_channel();
+
+    let mut players = Vec::new();
+    server::lobby::join_player(
+        &mut players,
+        "first".to_string(),
+        MockSocket::new(tx1, vec![]).split(),
+    )
+    .await
+    .unwrap();
+    server::lobby::join_player(
+        &mut players,
+        "placeholder".to_string(),
+        MockSocket::new(
+            tx2,
+            vec![
+                Message::Text(
+                    serde_json::to_string(&GameRequest::SetName {
+                        name: "second".to_string(),
+                    })
+                    .unwrap(),
+                ),
+                Message::Text(serde_json::to_string(&GameRequest::Ready).unwrap()),
+            ],
+        )
+        .split(),
+    )
+    .await
+    .unwrap();
+
+    // The second Seat is handled, even though the first Player did not get ready yet
+    assert_eq!(
+        LobbyEvent::Ready(1),
+        server::lobby::next_lobby_event(&mut players).await
+    );
+    assert_eq!("second", players[1].name);
+
+    // Players joining afterwards already see the new Name
+    server::lobby::join_player(
+        &mut players,
+        "third".to_string(),
+        MockSocket::new(tx3, vec![]).split(),
+    )
+    .await
+    .unwrap();
+    assert!(responses(&mut rx3).contains(&GameResponse::IndicatePlayer {
+        player: 1,
+        name: "second".to_string(),
+        you: false,
+        team: None,
+        color: PlayerColor::Blue,
+    }));
+}
@@ -0,0 +1,658 @@
+use axum::extract::ws::Message;
+use futures::StreamExt;
+use server::{
+    statemachine::GameState, Board, Figure, Game, GamePlayer, GameRequest, GameResponse,
+    HouseEntry, MoveError,
+};
+
+use mocks::{DumbDistr, MockRand, MockSocket, SequenceDistr};
+use tracing_test::traced_test;
+
+mod common;
+use common::{player_with_figures, responses};
+
+#[tokio::test]
+#[traced_test]
+async fn overshoot_in_house_can_choose_again() {
+    let (tx1, mut rx1) = tokio::sync::mpsc::unbounded_channel();
+    let (tx2, _rx2) = tokio::sync::mpsc::unbounded_channel();
+
+    let mut game: Game<_, MockSocket<Message>, MockSocket<Message>> = Game::new_with_rng(
+        uuid::Uuid::new_v4(),
+        vec![
+            GamePlayer::new(
+                "test".to_string(),
+                MockSocket::new(
+                    tx1,
+                    vec![
+                        Message::Text(
+                            serde_json::to_string(&GameRequest::Move { figure: 0 }).unwrap(),
+                        ),
+                        Message::Text(
+                            serde_json::to_string(&GameRequest::Move { figure: 1 }).unwrap(),
+                        ),
+                    ],
+                )
+                .split(),
+            ),
+            GamePlayer::new("test2".to_string(), MockSocket::new(tx2, vec![]).split()),
+        ],
+        MockRand::new(vec![0]),
+    );
+
+    game.next_player = 0;
+    game.players[0].figures[0] = Figure::InHouse { pos: 2 };
+    game.players[0].figures[1] = Figure::OnField { moved: 5 };
+
+    let mut distr = DumbDistr {};
+    let state = GameState::Rolled { value: 4 };
+
+    let (_rejointx, mut rejoinrx) = tokio::sync::mpsc::unbounded_channel();
+
+    let n_state = server::statemachine::step(state, &mut game, &mut rejoinrx, &mut distr)
+        .await
+        .expect("");
+
+    assert_eq!(Figure::InHouse { pos: 2 }, game.players[0].figures[0]);
+    assert_eq!(GameState::Rolled { value: 4 }, n_state);
+    assert_eq!(
+        vec![GameResponse::ChooseAnotherFigure { value: 4 }],
+        responses(&mut rx1)
+    );
+
+    let n_state = server::statemachine::step(n_state, &mut game, &mut rejoinrx, &mut distr)
+        .await
+        .expect("");
+
+    assert_eq!(Figure::InHouse { pos: 2 }, game.players[0].figures[0]);
+    assert_eq!(Figure::OnField { moved: 9 }, game.players[0].figures[1]);
+    assert_eq!(GameState::MoveToNextTurn, n_state);
+}
+
+#[tokio::test]
+#[traced_test]
+async fn blocked_figure_choose_another() {
+    let (tx1, mut rx1) = tokio::sync::mpsc::unbounded_channel();
+    let (tx2, _rx2) = tokio::sync::mpsc::unbounded_channel();
+
+    let mut game: Game<_, MockSocket<Message>, MockSocket<Message>> = Game::new_with_rng(
+        uuid::Uuid::new_v4(),
+        vec![
+            GamePlayer::new(
+                "test".to_string(),
+                MockSocket::new(
+                    tx1,
+                    vec![
+                        Message::Text(
+                            serde_json::to_string(&GameRequest::Move { figure: 0 }).unwrap(),
+                        ),
+                        Message::Text(
+                            serde_json::to_string(&GameRequest::Move { figure: 1 }).unwrap(),
+                        ),
+                    ],
+                )
+                .split(),
+            ),
+            GamePlayer::new("test2".to_string(), MockSocket::new(tx2, vec![]).split()),
+        ],
+        MockRand::new(vec![0]),
+    );
+
+    game.next_player = 0;
+    game.players[0].figures[0] = Figure::OnField { moved: 5 };
+    game.players[0].figures[1] = Figure::OnField { moved: 8 };
+
+    let mut distr = DumbDistr {};
+    let state = GameState::Rolled { value: 3 };
+
+    let (_rejointx, mut rejoinrx) = tokio::sync::mpsc::unbounded_channel();
+
+    let n_state = server::statemachine::step(state, &mut game, &mut rejoinrx, &mut distr)
+        .await
+        .expect("");
+
+    assert_eq!(Figure::OnField { moved: 5 }, game.players[0].figures[0]);
+    assert_eq!(GameState::Rolled { value: 3 }, n_state);
+    assert_eq!(
+        vec![GameResponse::ChooseAnotherFigure { value: 3 }],
+        responses(&mut rx1)
+    );
+
+    let n_state = server::statemachine::step(n_state, &mut game, &mut rejoinrx, &mut distr)
+        .await
+        .expect("");
+
+    assert_eq!(Figure::OnField { moved: 5 }, game.players[0].figures[0]);
+    assert_eq!(Figure::OnField { moved: 11 }, game.players[0].figures[1]);
+    assert_eq!(GameState::MoveToNextTurn, n_state);
+}
+
+#[tokio::test]
+#[traced_test]
+async fn blocked_figure_no_other_move() {
+    let (tx1, _rx1) = tokio::sync::mpsc::unbounded_channel();
+    let (tx2, _rx2) = tokio::sync::mpsc::unbounded_channel();
+
+    let mut game: Game<_, MockSocket<Message>, MockSocket<Message>> = Game::new_with_rng(
+        uuid::Uuid::new_v4(),
+        vec![
+            GamePlayer::new(
+                "test".to_string(),
+                MockSocket::new(
+                    tx1,
+                    vec![Message::Text(
+                        serde_json::to_string(&GameRequest::Move { figure: 0 }).unwrap(),
+                    )],
+                )
+                .split(),
+            ),
+            GamePlayer::new("test2".to_string(), MockSocket::new(tx2, vec![]).split()),
+        ],
+        MockRand::new(vec![0]),
+    );
+
+    game.next_player = 0;
+    game.players[0].figures[0] = Figure::InHouse { pos: 2 };
+    game.players[0].figures[1] = Figure::InHouse { pos: 3 };
+
+    let mut distr = DumbDistr {};
+    let state = GameState::Rolled { value: 4 };
+
+    let (_rejointx, mut rejoinrx) = tokio::sync::mpsc::unbounded_channel();
+
+    let n_state = server::statemachine::step(state, &mut game, &mut rejoinrx, &mut distr)
+        .await
+        .expect("");
+
+    assert_eq!(Figure::InHouse { pos: 2 }, game.players[0].figures[0]);
+    assert_eq!(GameState::MoveToNextTurn, n_state);
+}
+
+#[test]
+#[traced_test]
+fn six_player_board_house_entry() {
+    let (tx, _rx) = tokio::sync::mpsc::unbounded_channel();
+    let mut player: GamePlayer<_, _> =
+        GamePlayer::new("test".to_string(), MockSocket::new(tx, vec![]).split());
+    player.figures[0] = Figure::OnField { moved: 38 };
+
+    assert_eq!(
+        Ok(&Figure::OnField { moved: 42 }),
+        player.move_figure(0, 4, &Board::for_players(6), HouseEntry::Loose, None)
+    );
+    assert_eq!(
+        Ok(&Figure::InHouse { pos: 1 }),
+        player.move_figure(0, 19, &Board::for_players(6), HouseEntry::Loose, None)
+    );
+}
+
+#[tokio::test]
+#[traced_test]
+async fn move_options_after_roll() {
+    let (tx1, mut rx1) = tokio::sync::mpsc::unbounded_channel();
+    let (tx2, _rx2) = tokio::sync::mpsc::unbounded_channel();
+
+    let mut game: Game<_, MockSocket<Message>, MockSocket<Message>> = Game::new_with_rng(
+        uuid::Uuid::new_v4(),
+        vec![
+            GamePlayer::new(
+                "test".to_string(),
+                MockSocket::new(
+                    tx1,
+                    vec![
+                        Message::Text(serde_json::to_string(&GameRequest::Roll).unwrap()),
+                        Message::Text(serde_json::to_string(&GameRequest::Roll).unwrap()),
+                    ],
+                )
+                .split(),
+            ),
+            GamePlayer::new("test2".to_string(), MockSocket::new(tx2, vec![]).split()),
+        ],
+        MockRand::new(vec![0, 0]),
+    );
+
+    game.next_player = 0;
+    game.players[0].figures[1] = Figure::OnField { moved: 10 };
+    game.players[0].figures[2] = Figure::OnField { moved: 11 };
+    game.players[0].figures[3] = Figure::OnField { moved: 12 };
+    game.six_forces_start_exit = true;
+
+    let mut distr = SequenceDistr::new(vec![6, 4]);
+    let state = GameState::StartTurn { attempt: 0 };
+
+    let (_rejointx, mut rejoinrx) = tokio::sync::mpsc::unbounded_channel();
+
+    let n_state = server::statemachine::step(state, &mut game, &mut rejoinrx, &mut distr)
+        .await
+        .expect("");
+
+    assert_eq!(GameState::StartTurn { attempt: 0 }, n_state);
+    let received = responses(&mut rx1);
+    assert_eq!(
+        GameResponse::MoveOptions {
+            value: 6,
+            figures: vec![]
+        },
+        received[2]
+    );
+
+    let n_state = server::statemachine::step(n_state, &mut game, &mut rejoinrx, &mut distr)
+        .await
+        .expect("");
+
+    assert_eq!(GameState::Rolled { value: 4 }, n_state);
+    let received = responses(&mut rx1);
+    assert_eq!(
+        GameResponse::Rolled {
+            value: 4,
+            can_move: true,
+            attempts_left: None,
+        },
+        received[1]
+    );
+    assert_eq!(
+        GameResponse::MoveOptions {
+            value: 4,
+            figures: vec![0, 1, 2, 3]
+        },
+        received[2]
+    );
+}
+
+/// A Figure that is 2 Fields away from the House of a Board with 40 Fields
+const NEAR_HOUSE: [Figure; 4] = [
+    Figure::OnField { moved: 38 },
+    Figure::InStart,
+    Figure::InStart,
+    Figure::InStart,
+];
+
+#[test]
+#[traced_test]
+fn loose_house_entry() {
+    let mut player = player_with_figures(NEAR_HOUSE);
+
+    assert_eq!(
+        Err(MoveError::Overshoot),
+        player.move_figure(0, 6, &Board::default(), HouseEntry::Loose, None)
+    );
+    assert_eq!(
+        Ok(&Figure::InHouse { pos: 0 }),
+        player.move_figure(0, 2, &Board::default(), HouseEntry::Loose, None)
+    );
+}
+
+#[test]
+#[traced_test]
+fn exact_house_entry() {
+    let mut player = player_with_figures(NEAR_HOUSE);
+    player.figures[1] = Figure::InHouse { pos: 3 };
+
+    assert_eq!(
+        Err(MoveError::Overshoot),
+        player.move_figure(0, 6, &Board::default(), HouseEntry::Exact, None)
+    );
+    assert_eq!(
+        Err(MoveError::Inexact),
+        player.move_figure(0, 2, &Board::default(), HouseEntry::Exact, None)
+    );
+    assert_eq!(
+        Ok(&Figure::OnField { moved: 39 }),
+        player.move_figure(0, 1, &Board::default(), HouseEntry::Exact, None)
+    );
+    assert_eq!(
+        Ok(&Figure::InHouse { pos: 2 }),
+        player.move_figure(0, 3, &Board::default(), HouseEntry::Exact, None)
+    );
+    assert_eq!(
+        vec![2, 3],
+        player.legal_moves(6, &Board::default(), HouseEntry::Exact, None)
+    );
+}
+
+#[test]
+#[traced_test]
+fn bounce_house_entry() {
+    let mut player = player_with_figures(NEAR_HOUSE);
+
+    assert_eq!(
+        Ok(&Figure::InHouse { pos: 2 }),
+        player.move_figure(0, 6, &Board::default(), HouseEntry::Bounce, None)
+    );
+    assert_eq!(
+        Ok(&Figure::InHouse { pos: 1 }),
+        player.move_figure(0, 3, &Board::default(), HouseEntry::Bounce, None)
+    );
+
+    player.figures[1] = Figure::InHouse { pos: 2 };
+    assert_eq!(
+        Err(MoveError::Occupied),
+        player.move_figure(0, 3, &Board::default(), HouseEntry::Bounce, None)
+    );
+}
+
+#[test]
+#[traced_test]
+fn no_jumping_in_house() {
+    let mut player = player_with_figures(NEAR_HOUSE);
+    player.figures[0] = Figure::InHouse { pos: 0 };
+    player.figures[1] = Figure::InHouse { pos: 2 };
+
+    assert_eq!(
+        Err(MoveError::Blocked),
+        player.move_figure(0, 3, &Board::default(), HouseEntry::Loose, None)
+    );
+    assert_eq!(Figure::InHouse { pos: 0 }, player.figures[0]);
+    assert_eq!(
+        Ok(&Figure::InHouse { pos: 1 }),
+        player.move_figure(0, 1, &Board::default(), HouseEntry::Loose, None)
+    );
+
+    // Figures entering the House can not jump over the Figures in it either
+    player.figures[2] = Figure::OnField { moved: 39 };
+    assert_eq!(
+        Err(MoveError::Blocked),
+        player.move_figure(2, 4, &Board::default(), HouseEntry::Loose, None)
+    );
+}
+
+#[tokio::test]
+#[traced_test]
+async fn blocked_start_field_with_six() {
+    let (tx1, mut rx1) = tokio::sync::mpsc::unbounded_channel();
+    let (tx2, _rx2) = tokio::sync::mpsc::unbounded_channel();
+
+    let mut game: Game<_, MockSocket<Message>, MockSocket<Message>> = Game::new_with_rng(
+        uuid::Uuid::new_v4(),
+        vec![
+            GamePlayer::new(
+                "test".to_string(),
+                MockSocket::new(
+                    tx1,
+                    vec![
+                        Message::Text(serde_json::to_string(&GameRequest::Roll).unwrap()),
+                        Message::Text(
+                            serde_json::to_string(&GameRequest::Move { figure: 1 }).unwrap(),
+                        ),
+                    ],
+                )
+                .split(),
+            ),
+            GamePlayer::new("test2".to_string(), MockSocket::new(tx2, vec![]).split()),
+        ],
+        MockRand::new(vec![0, 0]),
+    );
+
+    game.next_player = 0;
+    game.players[0].figures[0] = Figure::OnField { moved: 0 };
+    game.players[0].figures[1] = Figure::OnField { moved: 6 };
+
+    let mut distr = SequenceDistr::new(vec![6]);
+    let state = GameState::StartTurn { attempt: 0 };
+
+    let (_rejointx, mut rejoinrx) = tokio::sync::mpsc::unbounded_channel();
+
+    let n_state = server::statemachine::step(state, &mut game, &mut rejoinrx, &mut distr)
+        .await
+        .expect("");
+
+    // Neither can the Figure on the Start-Field move nor can another Figure leave the Start
+    assert_eq!(GameState::Rolled { value: 6 }, n_state);
+    assert_eq!(Figure::OnField { moved: 0 }, game.players[0].figures[0]);
+    assert_eq!(Figure::InStart, game.players[0].figures[2]);
+    assert_eq!(Figure::InStart, game.players[0].figures[3]);
+
+    let received = responses(&mut rx1);
+    assert!(received.contains(&GameResponse::Rolled {
+        value: 6,
+        can_move: true,
+        attempts_left: None,
+    }));
+    assert!(received.contains(&GameResponse::MoveOptions {
+        value: 6,
+        figures: vec![1]
+    }));
+
+    let n_state = server::statemachine::step(n_state, &mut game, &mut rejoinrx, &mut distr)
+        .await
+        .expect("");
+
+    assert_eq!(GameState::StartTurn { attempt: 0 }, n_state);
+    assert_eq!(Figure::OnField { moved: 12 }, game.players[0].figures[1]);
+    assert_eq!(
+        1,
+        game.players[0]
+            .figures
+            .iter()
+            .filter(|f| **f == Figure::OnField { moved: 0 })
+            .count()
+    );
+}
+
+#[tokio::test]
+#[traced_test]
+async fn figure_out_of_range_is_rejected() {
+    let (tx1, mut rx1) = tokio::sync::mpsc::unbounded_channel();
+    let (tx2, _rx2) = tokio::sync::mpsc::unbounded_channel();
+
+    let mut game: Game<_, MockSocket<Message>, MockSocket<Message>> = Game::new_with_rng(
+        uuid::Uuid::new_v4(),
+        vec![
+            GamePlayer::new(
+                "test".to_string(),
+                MockSocket::new(
+                    tx1,
+                    vec![Message::Text(
+                        serde_json::to_string(&GameRequest::Move { figure: 7 }).unwrap(),
+                    )],
+                )
+                .split(),
+            ),
+            GamePlayer::new("test2".to_string(), MockSocket::new(tx2, vec![]).split()),
+        ],
+        MockRand::new(vec![0]),
+    );
+    game.players[0].figures[0] = Figure::OnField { moved: 5 };
+
+    let mut distr = DumbDistr {};
+    let (_rejointx, mut rejoinrx) = tokio::sync::mpsc::unbounded_channel();
+
+    let n_state = server::statemachine::step(
+        GameState::Rolled { value: 3 },
+        &mut game,
+        &mut rejoinrx,
+        &mut distr,
+    )
+    .await
+    .expect("");
+
+    assert_eq!(GameState::Rolled { value: 3 }, n_state);
+    assert_eq!(Figure::OnField { moved: 5 }, game.players[0].figures[0]);
+    assert_eq!(
+        vec![GameResponse::InvalidRequest {
+            reason: "figure index out of range".to_string()
+        }],
+        responses(&mut rx1)
+    );
+}
+
+#[test]
+#[traced_test]
+fn forming_a_blockade() {
+    let (tx, _rx) = tokio::sync::mpsc::unbounded_channel();
+    let mut player: GamePlayer<_, _> =
+        GamePlayer::new("test".to_string(), MockSocket::new(tx, vec![]).split());
+    player.figures[0] = Figure::OnField { moved: 5 };
+    player.figures[1] = Figure::OnField { moved: 8 };
+
+    assert_eq!(
+        Err(MoveError::Occupied),
+        player.move_figure(0, 3, &Board::default(), HouseEntry::Loose, None)
+    );
+    assert_eq!(
+        Ok(&Figure::OnField { moved: 8 }),
+        player.move_figure(0, 3, &Board::default(), HouseEntry::Loose, Some(&[]))
+    );
+
+    // Not even the Owner can pass their own Blockade
+    player.figures[2] = Figure::OnField { moved: 6 };
+    assert_eq!(
+        Err(MoveError::Blockade),
+        player.move_figure(2, 4, &Board::default(), HouseEntry::Loose, Some(&[8]))
+    );
+    assert_eq!(
+        Err(MoveError::Blockade),
+        player.move_figure(2, 2, &Board::default(), HouseEntry::Loose, Some(&[8]))
+    );
+    assert_eq!(
+        Ok(&Figure::OnField { moved: 7 }),
+        player.move_figure(2, 1, &Board::default(), HouseEntry::Loose, Some(&[8]))
+    );
+}
+
+#[tokio::test]
+#[traced_test]
+async fn blockade_can_not_be_passed() {
+    let (tx1, _rx1) = tokio::sync::mpsc::unbounded_channel();
+    let (tx2, _rx2) = tokio::sync::mpsc::unbounded_channel();
+
+    let mut game: Game<_, MockSocket<Message>, MockSocket<Message>> = Game::new_with_rng(
+        uuid::Uuid::new_v4(),
+        vec![
+            GamePlayer::new("test".to_string(), MockSocket::new(tx1, vec![]).split()),
+            GamePlayer::new("test2".to_string(), MockSocket::new(tx2, vec![]).split()),
+        ],
+        MockRand::new(vec![0]),
+    );
+    game.allow_blockades = true;
+    game.next_player = 0;
+    game.players[0].figures[0] = Figure::OnField { moved: 10 };
+    game.players[1].figures[0] = Figure::OnField { moved: 32 };
+    game.players[1].figures[1] = Figure::OnField { moved: 32 };
+
+    assert_eq!(vec![12], game.blockades());
+
+    let mut distr = DumbDistr {};
+    let (_rejointx, mut rejoinrx) = tokio::sync::mpsc::unbounded_channel();
+
+    let n_state = server::statemachine::step(
+        GameState::Rolled { value: 3 },
+        &mut game,
+        &mut rejoinrx,
+        &mut distr,
+    )
+    .await
+    .expect("");
+
+    assert_eq!(GameState::MoveToNextTurn, n_state);
+    assert_eq!(Figure::OnField { moved: 10 }, game.players[0].figures[0]);
+}
+
+#[tokio::test]
+#[traced_test]
+async fn small_board_house_entry() {
+    let (tx1, _rx1) = tokio::sync::mpsc::unbounded_channel();
+    let (tx2, _rx2) = tokio::sync::mpsc::unbounded_channel();
+
+    let mut game: Game<_, MockSocket<Message>, MockSocket<Message>> = Game::new_with_rng(
+        uuid::Uuid::new_v4(),
+        vec![
+            GamePlayer::new(
+                "test".to_string(),
+                MockSocket::new(
+                    tx1,
+                    vec![Message::Text(
+                        serde_json::to_string(&GameRequest::Move { figure: 0 }).unwrap(),
+                    )],
+                )
+                .split(),
+            ),
+            GamePlayer::new("test2".to_string(), MockSocket::new(tx2, vec![]).split()),
+        ],
+        MockRand::new(vec![0]),
+    );
+    game.board = Board {
+        fields: 20,
+        house_size: 4,
+        player_starts: vec![0, 10],
+    };
+    game.next_player = 0;
+    game.players[0].figures[0] = Figure::OnField { moved: 18 };
+    game.players[1].figures[0] = Figure::OnField { moved: 9 };
+
+    assert_eq!(
+        Some(19),
+        game.field_position(1, &game.players[1].figures[0])
+    );
+    assert_eq!(None, game.field_position(0, &Figure::OnField { moved: 20 }));
+
+    let mut distr = DumbDistr {};
+    let (_rejointx, mut rejoinrx) = tokio::sync::mpsc::unbounded_channel();
+
+    let n_state = server::statemachine::step(
+        GameState::Rolled { value: 3 },
+        &mut game,
+        &mut rejoinrx,
+        &mut distr,
+    )
+    .await
+    .expect("");
+
+    assert_eq!(GameState::MoveToNextTurn, n_state);
+    assert_eq!(Figure::InHouse { pos: 1 }, game.players[0].figures[0]);
+    assert_eq!(Figure::OnField { moved: 9 }, game.players[1].figures[0]);
+}
+
+#[tokio::test]
+#[traced_test]
+async fn move_to_target_field() {
+    let (tx1, _rx1) = tokio::sync::mpsc::unbounded_channel();
+    let (tx2, mut rx2) = tokio::sync::mpsc::unbounded_channel();
+
+    let move_to =
+        |absolute| Message::Text(serde_json::to_string(&GameRequest::MoveTo { absolute }).unwrap());
+    let mut game: Game<_, MockSocket<Message>, MockSocket<Message>> = Game::new_with_rng(
+        uuid::Uuid::new_v4(),
+        vec![
+            GamePlayer::new("test".to_string(), MockSocket::new(tx1, vec![]).split()),
+            GamePlayer::new(
+                "test2".to_string(),
+                MockSocket::new(tx2, vec![move_to(30), move_to(28)]).split(),
+            ),
+        ],
+        MockRand::new(vec![0]),
+    );
+    game.next_player = 1;
+    game.players[1].figures[0] = Figure::OnField { moved: 5 };
+    game.players[1].figures[1] = Figure::OnField { moved: 9 };
+    assert_eq!(vec![0], game.figures_moving_to(28, 3));
+    assert_eq!(vec![1], game.figures_moving_to(32, 3));
+
+    let mut distr = DumbDistr {};
+    let (_rejointx, mut rejoinrx) = tokio::sync::mpsc::unbounded_channel();
+
+    let n_state = server::statemachine::step(
+        GameState::Rolled { value: 3 },
+        &mut game,
+        &mut rejoinrx,
+        &mut distr,
+    )
+    .await
+    .expect("");
+
+    assert_eq!(GameState::Rolled { value: 3 }, n_state);
+    assert_eq!(
+        vec![GameResponse::InvalidRequest {
+            reason: "no figure can move to this field".to_string()
+        }],
+        responses(&mut rx2)
+    );
+
+    let n_state = server::statemachine::step(n_state, &mut game, &mut rejoinrx, &mut distr)
+        .await
+        .expect("");
+
+    assert_eq!(GameState::MoveToNextTurn, n_state);
+    assert_eq!(Figure::OnField { moved: 8 }, game.players[1].figures[0]);
+    assert_eq!(Figure::OnField { moved: 9 }, game.players[1].figures[1]);
+}
@@ -0,0 +1,105 @@
+use axum::extract::ws::Message;
+use futures::StreamExt;
+use server::{statemachine::GameState, Figure, Game, GamePlayer, GameRequest, GameResponse};
+
+use mocks::{DumbDistr, MockRand, MockSocket};
+use tracing_test::traced_test;
+
+mod common;
+use common::responses;
+
+#[tokio::test]
+#[traced_test]
+async fn pause_and_resume_mid_turn() {
+    let (tx1, mut rx1) = tokio::sync::mpsc::unbounded_channel();
+    let (tx2, mut rx2) = tokio::sync::mpsc::unbounded_channel();
+
+    let request = |req: &GameRequest| Message::Text(serde_json::to_string(req).unwrap());
+
+    let mut game: Game<_, MockSocket<Message>, MockSocket<Message>> = Game::new_with_rng(
+        uuid::Uuid::new_v4(),
+        vec![
+            GamePlayer::new(
+                "test".to_string(),
+                MockSocket::new(
+                    tx1,
+                    vec![
+                        request(&GameRequest::Pause),
+                        request(&GameRequest::Roll),
+                        request(&GameRequest::Resume),
+                    ],
+                )
+                .split(),
+            ),
+            GamePlayer::new("test2".to_string(), MockSocket::new(tx2, vec![]).split()),
+        ],
+        MockRand::new(vec![0]),
+    );
+    game.next_player = 0;
+    game.players[0].figures[0] = Figure::OnField { moved: 5 };
+
+    let mut distr = DumbDistr {};
+    let (_rejointx, mut rejoinrx) = tokio::sync::mpsc::unbounded_channel();
+
+    let state = server::statemachine::step(
+        GameState::Rolled { value: 3 },
+        &mut game,
+        &mut rejoinrx,
+        &mut distr,
+    )
+    .await
+    .expect("");
+    assert_eq!(
+        GameState::Paused {
+            prev_state: Box::new(GameState::Rolled { value: 3 })
+        },
+        state
+    );
+    assert!(game.is_paused());
+    assert_eq!(
+        vec![GameResponse::Paused { player: 0 }],
+        responses(&mut rx1)
+    );
+    assert_eq!(
+        vec![GameResponse::Paused { player: 0 }],
+        responses(&mut rx2)
+    );
+
+    // Nothing but resuming the Game is possible while it is paused
+    let state = server::statemachine::step(state, &mut game, &mut rejoinrx, &mut distr)
+        .await
+        .expect("");
+    assert!(matches!(state, GameState::Paused { .. }));
+    assert_eq!(
+        vec![GameResponse::InvalidRequest {
+            reason: "game is paused".to_string()
+        }],
+        responses(&mut rx1)
+    );
+
+    let state = server::statemachine::step(state, &mut game, &mut rejoinrx, &mut distr)
+        .await
+        .expect("");
+    assert_eq!(GameState::Rolled { value: 3 }, state);
+    assert!(!game.is_paused());
+    assert_eq!(Figure::OnField { moved: 5 }, game.players[0].figures[0]);
+    assert_eq!(
+        vec![
+            GameResponse::Resumed { player: 0 },
+            GameResponse::Rolled {
+                value: 3,
+                can_move: true,
+                attempts_left: None,
+            },
+            GameResponse::MoveOptions {
+                value: 3,
+                figures: vec![0]
+            },
+        ],
+        responses(&mut rx1)
+    );
+    assert_eq!(
+        vec![GameResponse::Resumed { player: 0 }],
+        responses(&mut rx2)
+    );
+}
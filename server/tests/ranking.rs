@@ -0,0 +1,120 @@
+use server::Figure;
+
+mod common;
+use common::game_with_figures;
+
+const START: [Figure; 4] = [
+    Figure::InStart,
+    Figure::InStart,
+    Figure::InStart,
+    Figure::InStart,
+];
+
+#[test]
+fn ranking_by_figures_in_house() {
+    let game = game_with_figures(
+        vec![
+            [
+                Figure::InHouse { pos: 3 },
+                Figure::InStart,
+                Figure::InStart,
+                Figure::InStart,
+            ],
+            [
+                Figure::InHouse { pos: 3 },
+                Figure::InHouse { pos: 2 },
+                Figure::InStart,
+                Figure::InStart,
+            ],
+            [
+                Figure::OnField { moved: 39 },
+                Figure::OnField { moved: 38 },
+                Figure::InStart,
+                Figure::InStart,
+            ],
+        ],
+        Vec::new(),
+    );
+
+    assert_eq!(vec![1, 0, 2], game.compute_ranking());
+}
+
+#[test]
+fn ranking_tie_broken_by_progress() {
+    let game = game_with_figures(
+        vec![
+            [
+                Figure::InHouse { pos: 3 },
+                Figure::OnField { moved: 10 },
+                Figure::InStart,
+                Figure::InStart,
+            ],
+            [
+                Figure::InHouse { pos: 3 },
+                Figure::OnField { moved: 20 },
+                Figure::InStart,
+                Figure::InStart,
+            ],
+            START,
+        ],
+        Vec::new(),
+    );
+
+    assert_eq!(vec![1, 0, 2], game.compute_ranking());
+}
+
+#[test]
+fn ranking_keeps_finished_players() {
+    let game = game_with_figures(
+        vec![
+            [
+                Figure::InHouse { pos: 3 },
+                Figure::InHouse { pos: 2 },
+                Figure::InHouse { pos: 1 },
+                Figure::InStart,
+            ],
+            START,
+            [
+                Figure::InHouse { pos: 0 },
+                Figure::InHouse { pos: 1 },
+                Figure::InHouse { pos: 2 },
+                Figure::InHouse { pos: 3 },
+            ],
+        ],
+        vec![2],
+    );
+
+    assert_eq!(vec![2, 0, 1], game.compute_ranking());
+}
+
+#[test]
+fn ranking_full_tie_keeps_seat_order() {
+    let game = game_with_figures(vec![START, START, START], Vec::new());
+
+    assert_eq!(vec![0, 1, 2], game.compute_ranking());
+}
+
+#[test]
+fn ranking_tie_broken_by_lower_seat() {
+    // Both Players moved 20 Fields in total and have one Figure in the House
+    let game = game_with_figures(
+        vec![
+            START,
+            [
+                Figure::OnField { moved: 5 },
+                Figure::OnField { moved: 15 },
+                Figure::InHouse { pos: 0 },
+                Figure::InStart,
+            ],
+            [
+                Figure::InHouse { pos: 0 },
+                Figure::OnField { moved: 20 },
+                Figure::InStart,
+                Figure::InStart,
+            ],
+        ],
+        Vec::new(),
+    );
+
+    assert_eq!(vec![1, 2, 0], game.compute_ranking());
+}
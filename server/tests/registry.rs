@@ -0,0 +1,110 @@
+use server::{RejoinEntry, RejoinRegistry, MAX_ENTRY_AGE};
+
+use tracing_test::traced_test;
+
+mod common;
+use common::game_with_names;
+
+#[tokio::test]
+#[traced_test]
+async fn registry_round_trip() {
+    let game = game_with_names(&["first", "second"]);
+
+    let path = std::env::temp_dir().join(format!("rejoin-registry-{}.json", uuid::Uuid::new_v4()));
+
+    let mut registry = RejoinRegistry::load(&path).unwrap();
+    assert!(registry.entries().is_empty());
+    registry
+        .register(&game)
+        .expect("The Registry is backed by a File")
+        .write()
+        .await
+        .unwrap();
+
+    let loaded = RejoinRegistry::load(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+
+    let registered = loaded.entries()[0].registered;
+    assert!(registered > 0);
+    assert_eq!(
+        vec![
+            RejoinEntry {
+                game: game.id(),
+                player: 0,
+                code: game.players[0].rejoin_code(),
+                name: "first".to_string(),
+                registered,
+            },
+            RejoinEntry {
+                game: game.id(),
+                player: 1,
+                code: game.players[1].rejoin_code(),
+                name: "second".to_string(),
+                registered,
+            },
+        ],
+        loaded.entries()
+    );
+    assert!(loaded.contains_game(game.id()));
+    assert_eq!(
+        Some(&loaded.entries()[1]),
+        loaded.find(game.id(), game.players[1].rejoin_code())
+    );
+    assert_eq!(None, loaded.find(game.id(), uuid::Uuid::new_v4()));
+}
+
+#[tokio::test]
+#[traced_test]
+async fn finished_game_is_removed() {
+    let game = || game_with_names(&["first", "second"]);
+    let (finished, running) = (game(), game());
+
+    let path = std::env::temp_dir().join(format!("rejoin-registry-{}.json", uuid::Uuid::new_v4()));
+
+    let mut registry = RejoinRegistry::load(&path).unwrap();
+    let first = registry.register(&finished).unwrap();
+    let second = registry.register(&running).unwrap();
+    let removed = registry.remove_game(finished.id()).unwrap();
+    assert!(registry.remove_game(finished.id()).is_none());
+
+    // An older Write never replaces the File of a newer one
+    removed.write().await.unwrap();
+    second.write().await.unwrap();
+    first.write().await.unwrap();
+
+    let loaded = RejoinRegistry::load(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+
+    assert!(!loaded.contains_game(finished.id()));
+    assert!(loaded.contains_game(running.id()));
+    assert_eq!(2, loaded.entries().len());
+}
+
+#[test]
+#[traced_test]
+fn stale_entries_are_dropped_on_load() {
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::SystemTime::UNIX_EPOCH)
+        .unwrap()
+        .as_secs();
+    let entry = |registered| RejoinEntry {
+        game: uuid::Uuid::new_v4(),
+        player: 0,
+        code: uuid::Uuid::new_v4(),
+        name: "first".to_string(),
+        registered,
+    };
+    let (stale, fresh) = (
+        entry(now - MAX_ENTRY_AGE.as_secs() - 1),
+        entry(now - MAX_ENTRY_AGE.as_secs() / 2),
+    );
+
+    let path = std::env::temp_dir().join(format!("rejoin-registry-{}.json", uuid::Uuid::new_v4()));
+    std::fs::write(&path, serde_json::to_vec(&[&stale, &fresh]).unwrap()).unwrap();
+
+    let loaded = RejoinRegistry::load(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+
+    assert!(!loaded.contains_game(stale.game));
+    assert_eq!(&[fresh], loaded.entries());
+}
@@ -0,0 +1,294 @@
+use axum::extract::ws::Message;
+use futures::StreamExt;
+use server::{
+    statemachine::GameState, Figure, Game, GamePlayer, GameRequest, GameResponse, RejoinKey,
+};
+
+use mocks::{DumbDistr, MockRand, MockSocket};
+use tracing_test::traced_test;
+
+mod common;
+use common::{game_with_names, responses, TestGame};
+
+/// Disconnects the Player, by letting the next Send to them fail
+async fn disconnect(game: &mut TestGame, player: usize) {
+    let (tx, _rx) = tokio::sync::mpsc::unbounded_channel();
+    let (send, _) = MockSocket::new_failing(tx, vec![]).split();
+    game.players[player].send = send;
+
+    assert!(game.players[player]
+        .send_resp(&GameResponse::Turn)
+        .await
+        .is_err());
+    assert!(!game.players[player].is_connected());
+}
+
+#[test]
+fn rejoin_code_lookup() {
+    let game = game_with_names(&["first", "second", "third", "fourth"]);
+
+    for (index, player) in game.players.iter().enumerate() {
+        assert_eq!(Some(index), game.player_index_by_code(player.rejoin_code()));
+    }
+    assert_eq!(None, game.player_index_by_code(uuid::Uuid::new_v4()));
+}
+
+#[tokio::test]
+async fn rejoin_key_lookup() {
+    let mut game = game_with_names(&["first", "second", "second"]);
+
+    // Names only identify Players that are disconnected and only those can be kicked
+    assert_eq!(
+        None,
+        game.player_index_by_rejoin_key(&RejoinKey::Name("first".to_string()))
+    );
+    assert_eq!(None, game.player_index_by_rejoin_key(&RejoinKey::Kick(0)));
+    for player in 0..game.players.len() {
+        disconnect(&mut game, player).await;
+    }
+    assert_eq!(
+        Some(0),
+        game.player_index_by_rejoin_key(&RejoinKey::Kick(0))
+    );
+    assert_eq!(None, game.player_index_by_rejoin_key(&RejoinKey::Kick(3)));
+
+    assert_eq!(
+        Some(1),
+        game.player_index_by_rejoin_key(&RejoinKey::Code(game.players[1].rejoin_code()))
+    );
+    assert_eq!(
+        None,
+        game.player_index_by_rejoin_key(&RejoinKey::Code(uuid::Uuid::new_v4()))
+    );
+    assert_eq!(
+        Some(0),
+        game.player_index_by_rejoin_key(&RejoinKey::Name("first".to_string()))
+    );
+    assert_eq!(
+        None,
+        game.player_index_by_rejoin_key(&RejoinKey::Name("second".to_string()))
+    );
+    assert_eq!(
+        None,
+        game.player_index_by_rejoin_key(&RejoinKey::Name("other".to_string()))
+    );
+}
+
+#[tokio::test]
+#[traced_test]
+async fn rejoin_by_unique_name() {
+    let mut game = game_with_names(&["first", "second"]);
+    disconnect(&mut game, 0).await;
+
+    let mut distr = DumbDistr {};
+    let state = GameState::WaitingForReconnect {
+        prev_state: Box::new(GameState::StartTurn { attempt: 0 }),
+    };
+
+    let (rejointx, mut rejoinrx) = tokio::sync::mpsc::unbounded_channel();
+    let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+    rejointx
+        .send((
+            RejoinKey::Name("first".to_string()),
+            MockSocket::new(tx, vec![]).split(),
+        ))
+        .unwrap();
+
+    let n_state = server::statemachine::step(state, &mut game, &mut rejoinrx, &mut distr)
+        .await
+        .expect("");
+
+    assert_eq!(GameState::StartTurn { attempt: 0 }, n_state);
+    assert!(!responses(&mut rx).is_empty());
+}
+
+#[tokio::test]
+#[traced_test]
+async fn rejoin_by_name_of_connected_player() {
+    let (tx1, mut rx1) = tokio::sync::mpsc::unbounded_channel();
+    let (tx2, _rx2) = tokio::sync::mpsc::unbounded_channel();
+    let mut game: Game<_, MockSocket<Message>, MockSocket<Message>> = Game::new_with_rng(
+        uuid::Uuid::new_v4(),
+        vec![
+            GamePlayer::new("first".to_string(), MockSocket::new(tx1, vec![]).split()),
+            GamePlayer::new("second".to_string(), MockSocket::new(tx2, vec![]).split()),
+        ],
+        MockRand::new(vec![0]),
+    );
+
+    let mut distr = DumbDistr {};
+    let state = GameState::WaitingForReconnect {
+        prev_state: Box::new(GameState::StartTurn { attempt: 0 }),
+    };
+
+    let (rejointx, mut rejoinrx) = tokio::sync::mpsc::unbounded_channel();
+    let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+    rejointx
+        .send((
+            RejoinKey::Name("first".to_string()),
+            MockSocket::new(tx, vec![]).split(),
+        ))
+        .unwrap();
+
+    let n_state = server::statemachine::step(state, &mut game, &mut rejoinrx, &mut distr)
+        .await
+        .expect("");
+
+    // The connected Player keeps their Seat and the other Connection is rejected
+    assert_eq!(
+        GameState::WaitingForReconnect {
+            prev_state: Box::new(GameState::StartTurn { attempt: 0 })
+        },
+        n_state
+    );
+    assert!(responses(&mut rx).is_empty());
+    assert!(game.players[0].is_connected());
+    assert!(responses(&mut rx1).is_empty());
+}
+
+#[tokio::test]
+#[traced_test]
+async fn rejoin_by_duplicate_name() {
+    let mut game = game_with_names(&["same", "same"]);
+
+    let mut distr = DumbDistr {};
+    let state = GameState::WaitingForReconnect {
+        prev_state: Box::new(GameState::StartTurn { attempt: 0 }),
+    };
+
+    let (rejointx, mut rejoinrx) = tokio::sync::mpsc::unbounded_channel();
+    let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+    rejointx
+        .send((
+            RejoinKey::Name("same".to_string()),
+            MockSocket::new(tx, vec![]).split(),
+        ))
+        .unwrap();
+
+    let n_state = server::statemachine::step(state, &mut game, &mut rejoinrx, &mut distr)
+        .await
+        .expect("");
+
+    assert_eq!(
+        GameState::WaitingForReconnect {
+            prev_state: Box::new(GameState::StartTurn { attempt: 0 })
+        },
+        n_state
+    );
+    assert!(responses(&mut rx).is_empty());
+}
+
+#[tokio::test]
+#[traced_test]
+async fn rejoin_during_roll() {
+    let (tx1, _rx1) = tokio::sync::mpsc::unbounded_channel();
+    let (tx2, _rx2) = tokio::sync::mpsc::unbounded_channel();
+
+    let mut game: Game<_, MockSocket<Message>, MockSocket<Message>> = Game::new_with_rng(
+        uuid::Uuid::new_v4(),
+        vec![
+            GamePlayer::new(
+                "first".to_string(),
+                MockSocket::new(tx1, vec![Message::Close(None)]).split(),
+            ),
+            GamePlayer::new("second".to_string(), MockSocket::new(tx2, vec![]).split()),
+        ],
+        MockRand::new(vec![0]),
+    );
+    game.next_player = 0;
+    game.players[0].figures[0] = Figure::OnField { moved: 5 };
+
+    let mut distr = DumbDistr {};
+    let (rejointx, mut rejoinrx) = tokio::sync::mpsc::unbounded_channel();
+
+    let n_state = server::statemachine::step(
+        GameState::Rolled { value: 3 },
+        &mut game,
+        &mut rejoinrx,
+        &mut distr,
+    )
+    .await
+    .expect("");
+    assert_eq!(
+        GameState::WaitingForReconnect {
+            prev_state: Box::new(GameState::Rolled { value: 3 })
+        },
+        n_state
+    );
+
+    let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+    rejointx
+        .send((
+            RejoinKey::Code(game.players[0].rejoin_code()),
+            MockSocket::new(tx, vec![]).split(),
+        ))
+        .unwrap();
+
+    let n_state = server::statemachine::step(n_state, &mut game, &mut rejoinrx, &mut distr)
+        .await
+        .expect("");
+    assert_eq!(GameState::Rolled { value: 3 }, n_state);
+
+    let received = responses(&mut rx);
+    assert!(received.contains(&GameResponse::Rolled {
+        value: 3,
+        can_move: true,
+        attempts_left: None,
+    }));
+    assert!(received.contains(&GameResponse::MoveOptions {
+        value: 3,
+        figures: vec![0]
+    }));
+}
+
+#[tokio::test]
+#[traced_test]
+async fn rejoin_code_on_request() {
+    let (tx1, mut rx1) = tokio::sync::mpsc::unbounded_channel();
+    let (tx2, mut rx2) = tokio::sync::mpsc::unbounded_channel();
+
+    let mut game: Game<_, MockSocket<Message>, MockSocket<Message>> = Game::new_with_rng(
+        uuid::Uuid::new_v4(),
+        vec![
+            GamePlayer::new(
+                "test".to_string(),
+                MockSocket::new(
+                    tx1,
+                    vec![
+                        Message::Text(serde_json::to_string(&GameRequest::GetRejoinCode).unwrap()),
+                        Message::Text(serde_json::to_string(&GameRequest::Roll).unwrap()),
+                    ],
+                )
+                .split(),
+            ),
+            GamePlayer::new("test2".to_string(), MockSocket::new(tx2, vec![]).split()),
+        ],
+        MockRand::new(vec![0, 5]),
+    );
+    game.next_player = 0;
+
+    let mut distr = DumbDistr {};
+    let (_rejointx, mut rejoinrx) = tokio::sync::mpsc::unbounded_channel();
+
+    let n_state = server::statemachine::step(
+        GameState::StartTurn { attempt: 0 },
+        &mut game,
+        &mut rejoinrx,
+        &mut distr,
+    )
+    .await
+    .expect("");
+
+    // The Request does not use up the Turn
+    assert_eq!(GameState::StartTurn { attempt: 0 }, n_state);
+    assert_eq!(Figure::OnField { moved: 0 }, game.players[0].figures[0]);
+
+    let expected = GameResponse::RejoinCode {
+        game: game.id(),
+        code: game.players[0].rejoin_code(),
+    };
+    assert!(responses(&mut rx1).contains(&expected));
+    assert!(!responses(&mut rx2)
+        .iter()
+        .any(|r| matches!(r, GameResponse::RejoinCode { .. })));
+}
@@ -0,0 +1,193 @@
+use axum::extract::ws::Message;
+use futures::StreamExt;
+use rand::SeedableRng;
+use server::{
+    bot::{BotPlayer, Seat, Strategy},
+    statemachine::GameState,
+    Board, Figure, Game, GamePlayer, GameRequest,
+};
+
+use mocks::{MockRand, MockSocket, SequenceDistr};
+use tracing_test::traced_test;
+
+#[test]
+#[traced_test]
+fn reset_board() {
+    let (tx1, _rx1) = tokio::sync::mpsc::unbounded_channel();
+    let (tx2, _rx2) = tokio::sync::mpsc::unbounded_channel();
+
+    let in_house = [
+        Figure::InHouse { pos: 0 },
+        Figure::InHouse { pos: 1 },
+        Figure::InHouse { pos: 2 },
+        Figure::InHouse { pos: 3 },
+    ];
+    let mut game: Game<_, MockSocket<Message>, MockSocket<Message>> = Game::from_state(
+        uuid::Uuid::new_v4(),
+        vec![
+            (
+                GamePlayer::new("test".to_string(), MockSocket::new(tx1, vec![]).split()),
+                in_house.clone(),
+            ),
+            (
+                GamePlayer::new("test2".to_string(), MockSocket::new(tx2, vec![]).split()),
+                in_house,
+            ),
+        ],
+        0,
+        vec![1, 0],
+        MockRand::new(vec![1 << 63]),
+    );
+    let codes: Vec<_> = game.players.iter().map(|p| p.rejoin_code()).collect();
+    assert!(game.is_done());
+
+    game.reset();
+
+    assert!(!game.is_done());
+    assert!(game.ranking.is_empty());
+    assert_eq!(1, game.next_player);
+    assert!(game
+        .players
+        .iter()
+        .all(|p| p.figures.iter().all(|f| matches!(f, Figure::InStart))));
+    assert_eq!(
+        vec!["test".to_string(), "test2".to_string()],
+        game.players
+            .iter()
+            .map(|p| p.name.clone())
+            .collect::<Vec<_>>()
+    );
+    assert_eq!(
+        codes,
+        game.players
+            .iter()
+            .map(|p| p.rejoin_code())
+            .collect::<Vec<_>>()
+    );
+}
+
+#[tokio::test]
+#[traced_test]
+async fn reset_clears_the_previous_round() {
+    let (tx1, _rx1) = tokio::sync::mpsc::unbounded_channel();
+    let (tx2, _rx2) = tokio::sync::mpsc::unbounded_channel();
+
+    let request = |req: GameRequest| Message::Text(serde_json::to_string(&req).unwrap());
+    let mut game: Game<_, MockSocket<Message>, MockSocket<Message>> = Game::new_with_rng(
+        uuid::Uuid::new_v4(),
+        vec![
+            GamePlayer::new(
+                "test".to_string(),
+                MockSocket::new(
+                    tx1,
+                    vec![
+                        request(GameRequest::Roll),
+                        request(GameRequest::Move { figure: 0 }),
+                        request(GameRequest::Roll),
+                    ],
+                )
+                .split(),
+            ),
+            GamePlayer::new("test2".to_string(), MockSocket::new(tx2, vec![]).split()),
+        ],
+        MockRand::new(vec![0]),
+    );
+    game.next_player = 0;
+    game.players[0].figures[0] = Figure::OnField { moved: 10 };
+    game.undo_window = Some(std::time::Duration::from_secs(1));
+
+    let mut distr = SequenceDistr::new(vec![6]);
+    let (_rejointx, mut rejoinrx) = tokio::sync::mpsc::unbounded_channel();
+
+    // The Roll sent during the Undo-Window is kept for the next Turn of the Player
+    let mut state = GameState::StartTurn { attempt: 0 };
+    for _ in 0..3 {
+        state = server::statemachine::step(state, &mut game, &mut rejoinrx, &mut distr)
+            .await
+            .expect("");
+    }
+    assert_eq!(GameState::StartTurn { attempt: 0 }, state);
+    assert!(game.seq() > 0);
+
+    game.reset();
+    game.next_player = 0;
+
+    assert_eq!(0, game.seq());
+    assert!(!game.is_paused());
+    assert!(game.history().is_empty());
+
+    // Nothing from the previous Round is handled, so the Player has to roll again
+    let step = server::statemachine::step(state, &mut game, &mut rejoinrx, &mut distr);
+    assert!(
+        tokio::time::timeout(std::time::Duration::from_millis(50), step)
+            .await
+            .is_err()
+    );
+}
+
+#[tokio::test]
+#[traced_test]
+async fn rematch_plays_second_game() {
+    let players = vec![
+        GamePlayer::new(
+            "bot1".to_string(),
+            Seat::Bot(BotPlayer::substitute(
+                Strategy::default(),
+                Board::for_players(2),
+            ))
+            .split(),
+        ),
+        GamePlayer::new(
+            "bot2".to_string(),
+            Seat::Bot(BotPlayer::substitute(
+                Strategy::default(),
+                Board::for_players(2),
+            ))
+            .split(),
+        ),
+    ];
+    let mut game: Game<_, Seat<MockSocket<Message>>, Seat<MockSocket<Message>>> =
+        Game::new_with_rng(
+            uuid::Uuid::new_v4(),
+            players,
+            rand::rngs::SmallRng::seed_from_u64(42),
+        );
+    game.rematch = true;
+
+    let mut distr = rand::distributions::Uniform::new_inclusive(1, 6);
+    let (_rejointx, mut rejoinrx) = tokio::sync::mpsc::unbounded_channel();
+
+    let mut state = GameState::StartTurn { attempt: 0 };
+    let mut games = 0;
+    for _ in 0..100_000 {
+        let prev_done = state == GameState::Done;
+        state = server::statemachine::step(state, &mut game, &mut rejoinrx, &mut distr)
+            .await
+            .expect("The Bots always ask for a Rematch");
+
+        if prev_done {
+            assert_eq!(GameState::StartTurn { attempt: 0 }, state);
+            assert!(game.ranking.is_empty());
+            assert_eq!(0, game.turns());
+            assert!(game.history().is_empty());
+            assert!(!game.is_paused());
+            // Only the State and Board of the new Round were broadcast so far
+            assert_eq!(2, game.seq());
+            assert!(game
+                .players
+                .iter()
+                .all(|p| p.figures.iter().all(|f| matches!(f, Figure::InStart))));
+        }
+
+        if state == GameState::Done {
+            games += 1;
+            assert!(game.is_done());
+
+            if games == 2 {
+                break;
+            }
+        }
+    }
+
+    assert_eq!(2, games);
+}
@@ -0,0 +1,330 @@
+use axum::extract::ws::Message;
+use futures::StreamExt;
+use server::{
+    statemachine::GameState, Figure, Game, GamePlayer, GameRequest, GameResponse, PlayerColor,
+};
+
+use mocks::{DumbDistr, MockRand, MockSocket};
+use tracing_test::traced_test;
+
+mod common;
+use common::responses;
+
+#[tokio::test]
+#[traced_test]
+async fn request_state_during_turn() {
+    let (tx1, mut rx1) = tokio::sync::mpsc::unbounded_channel();
+    let (tx2, mut rx2) = tokio::sync::mpsc::unbounded_channel();
+
+    let mut game: Game<_, MockSocket<Message>, MockSocket<Message>> = Game::new_with_rng(
+        uuid::Uuid::new_v4(),
+        vec![
+            GamePlayer::new(
+                "test".to_string(),
+                MockSocket::new(
+                    tx1,
+                    vec![
+                        Message::Text(serde_json::to_string(&GameRequest::RequestState).unwrap()),
+                        Message::Text(serde_json::to_string(&GameRequest::Roll).unwrap()),
+                    ],
+                )
+                .split(),
+            ),
+            GamePlayer::new("test2".to_string(), MockSocket::new(tx2, vec![]).split()),
+        ],
+        MockRand::new(vec![0, 5]),
+    );
+
+    game.next_player = 0;
+
+    let mut distr = DumbDistr {};
+    let state = GameState::StartTurn { attempt: 0 };
+
+    let (_rejointx, mut rejoinrx) = tokio::sync::mpsc::unbounded_channel();
+
+    let n_state = server::statemachine::step(state, &mut game, &mut rejoinrx, &mut distr)
+        .await
+        .expect("");
+
+    assert_eq!(GameState::StartTurn { attempt: 0 }, n_state);
+    assert_eq!(Figure::OnField { moved: 0 }, game.players[0].figures[0]);
+
+    let received = responses(&mut rx1);
+    assert_eq!(GameResponse::Turn, received[0]);
+    assert!(matches!(received[1], GameResponse::State { .. }));
+    assert!(matches!(received[2], GameResponse::Board { .. }));
+    assert_eq!(
+        GameResponse::IndicatePlayer {
+            player: 0,
+            name: "test".to_string(),
+            you: true,
+            team: None,
+            color: PlayerColor::Red,
+        },
+        received[3]
+    );
+    assert_eq!(
+        GameResponse::IndicatePlayer {
+            player: 1,
+            name: "test2".to_string(),
+            you: false,
+            team: None,
+            color: PlayerColor::Blue,
+        },
+        received[4]
+    );
+    assert_eq!(
+        GameResponse::Rolled {
+            value: 6,
+            can_move: false,
+            attempts_left: Some(2),
+        },
+        received[5]
+    );
+
+    // The other Player only receives the State after the Move
+    let others = responses(&mut rx2);
+    assert!(!others
+        .iter()
+        .any(|r| matches!(r, GameResponse::IndicatePlayer { .. })));
+}
+
+#[tokio::test]
+#[traced_test]
+async fn broadcasts_are_sequenced() {
+    let (tx1, mut rx1) = tokio::sync::mpsc::unbounded_channel();
+    let (tx2, mut rx2) = tokio::sync::mpsc::unbounded_channel();
+
+    let mut game: Game<_, MockSocket<Message>, MockSocket<Message>> = Game::new_with_rng(
+        uuid::Uuid::new_v4(),
+        vec![
+            GamePlayer::new("test".to_string(), MockSocket::new(tx1, vec![]).split()),
+            GamePlayer::new("test2".to_string(), MockSocket::new(tx2, vec![]).split()),
+        ],
+        MockRand::new(vec![0]),
+    );
+    assert_eq!(0, game.seq());
+
+    let seqs = |rx: &mut tokio::sync::mpsc::UnboundedReceiver<Message>| -> Vec<u64> {
+        let mut result = Vec::new();
+        while let Ok(Message::Text(content)) = rx.try_recv() {
+            let value: serde_json::Value = serde_json::from_str(&content).unwrap();
+            result.push(value["seq"].as_u64().unwrap());
+        }
+        result
+    };
+
+    game.broadcast(&GameResponse::ActivePlayer { player: 0 })
+        .await
+        .unwrap();
+    game.send_state().await.unwrap();
+    game.broadcast(&GameResponse::PlayerDone { player: 1 })
+        .await
+        .unwrap();
+
+    assert_eq!(4, game.seq());
+    assert_eq!(vec![1, 2, 3, 4], seqs(&mut rx1));
+    assert_eq!(vec![1, 2, 3, 4], seqs(&mut rx2));
+
+    // A Resync does not count as a new Broadcast, but lets the Player know the latest Number
+    game.resync_player(0).await.unwrap();
+    assert_eq!(4, game.seq());
+    assert_eq!(vec![4, 4, 4, 4], seqs(&mut rx1));
+    assert!(seqs(&mut rx2).is_empty());
+}
+
+#[tokio::test]
+#[traced_test]
+async fn list_players_during_turn() {
+    let (tx1, mut rx1) = tokio::sync::mpsc::unbounded_channel();
+    let (tx2, mut rx2) = tokio::sync::mpsc::unbounded_channel();
+
+    let mut game: Game<_, MockSocket<Message>, MockSocket<Message>> = Game::new_with_rng(
+        uuid::Uuid::new_v4(),
+        vec![
+            GamePlayer::new(
+                "test".to_string(),
+                MockSocket::new(
+                    tx1,
+                    vec![
+                        Message::Text(serde_json::to_string(&GameRequest::ListPlayers).unwrap()),
+                        Message::Text(serde_json::to_string(&GameRequest::Roll).unwrap()),
+                    ],
+                )
+                .split(),
+            ),
+            GamePlayer::new("test2".to_string(), MockSocket::new(tx2, vec![]).split()),
+        ],
+        MockRand::new(vec![0, 5]),
+    );
+
+    game.next_player = 0;
+    game.players[0].figures[0] = Figure::OnField { moved: 12 };
+    game.players[1].figures[0] = Figure::OnField { moved: 3 };
+
+    let mut distr = DumbDistr {};
+    let state = GameState::StartTurn { attempt: 0 };
+
+    let (_rejointx, mut rejoinrx) = tokio::sync::mpsc::unbounded_channel();
+
+    let n_state = server::statemachine::step(state, &mut game, &mut rejoinrx, &mut distr)
+        .await
+        .expect("");
+
+    assert_eq!(GameState::Rolled { value: 6 }, n_state);
+
+    let received = responses(&mut rx1);
+    assert_eq!(GameResponse::Turn, received[0]);
+    assert_eq!(
+        GameResponse::Roster {
+            players: vec![
+                (0, "test".to_string(), true),
+                (1, "test2".to_string(), true)
+            ],
+        },
+        received[1]
+    );
+    assert!(matches!(received[2], GameResponse::Rolled { value: 6, .. }));
+
+    // Only the asking Player receives the Roster
+    assert!(!responses(&mut rx2)
+        .iter()
+        .any(|r| matches!(r, GameResponse::Roster { .. })));
+}
+
+#[tokio::test]
+#[traced_test]
+async fn rename_after_start_is_rejected() {
+    let (tx1, mut rx1) = tokio::sync::mpsc::unbounded_channel();
+    let (tx2, _rx2) = tokio::sync::mpsc::unbounded_channel();
+
+    let mut game: Game<_, MockSocket<Message>, MockSocket<Message>> = Game::new_with_rng(
+        uuid::Uuid::new_v4(),
+        vec![
+            GamePlayer::new(
+                "test".to_string(),
+                MockSocket::new(
+                    tx1,
+                    vec![
+                        Message::Text(
+                            serde_json::to_string(&GameRequest::SetName {
+                                name: "other".to_string(),
+                            })
+                            .unwrap(),
+                        ),
+                        Message::Text(serde_json::to_string(&GameRequest::Roll).unwrap()),
+                    ],
+                )
+                .split(),
+            ),
+            GamePlayer::new("test2".to_string(), MockSocket::new(tx2, vec![]).split()),
+        ],
+        MockRand::new(vec![0, 5]),
+    );
+
+    game.next_player = 0;
+    game.players[0].figures[0] = Figure::OnField { moved: 12 };
+
+    let mut distr = DumbDistr {};
+    let (_rejointx, mut rejoinrx) = tokio::sync::mpsc::unbounded_channel();
+
+    let n_state = server::statemachine::step(
+        GameState::StartTurn { attempt: 0 },
+        &mut game,
+        &mut rejoinrx,
+        &mut distr,
+    )
+    .await
+    .expect("");
+
+    assert_eq!(GameState::Rolled { value: 6 }, n_state);
+    assert_eq!("test", game.players[0].name);
+
+    let received = responses(&mut rx1);
+    assert!(matches!(received[1], GameResponse::InvalidRequest { .. }));
+    assert!(matches!(received[2], GameResponse::Rolled { value: 6, .. }));
+}
+
+#[tokio::test]
+#[traced_test]
+async fn colors_are_assigned_by_seat() {
+    let mut receivers = Vec::new();
+    let players: Vec<_> = (0..4)
+        .map(|i| {
+            let (tx, rx) = tokio::sync::mpsc::unbounded_channel();
+            receivers.push(rx);
+            GamePlayer::new(format!("test{}", i), MockSocket::new(tx, vec![]).split())
+        })
+        .collect();
+    let mut game: Game<_, MockSocket<Message>, MockSocket<Message>> =
+        Game::new_with_rng(uuid::Uuid::new_v4(), players, MockRand::new(vec![0]));
+
+    game.indicate_players().await.unwrap();
+
+    for rx in receivers.iter_mut() {
+        let colors: Vec<_> = responses(rx)
+            .into_iter()
+            .filter_map(|r| match r {
+                GameResponse::IndicatePlayer { player, color, .. } => Some((player, color)),
+                _ => None,
+            })
+            .collect();
+
+        assert_eq!(
+            vec![
+                (0, PlayerColor::Red),
+                (1, PlayerColor::Blue),
+                (2, PlayerColor::Green),
+                (3, PlayerColor::Yellow),
+            ],
+            colors
+        );
+    }
+
+    // Larger Games continue with the rest of the Palette
+    assert_eq!(PlayerColor::Purple, PlayerColor::for_seat(4));
+}
+
+#[tokio::test]
+#[traced_test]
+async fn indications_are_batched() {
+    let mut receivers = Vec::new();
+    let players: Vec<_> = (0..4)
+        .map(|i| {
+            let (tx, rx) = tokio::sync::mpsc::unbounded_channel();
+            receivers.push(rx);
+            GamePlayer::new(format!("test{}", i), MockSocket::new(tx, vec![]).split())
+        })
+        .collect();
+    let mut game: Game<_, MockSocket<Message>, MockSocket<Message>> =
+        Game::new_with_rng(uuid::Uuid::new_v4(), players, MockRand::new(vec![0]));
+
+    game.indicate_players().await.unwrap();
+
+    for (index, rx) in receivers.iter_mut().enumerate() {
+        let mut frames = Vec::new();
+        while let Ok(msg) = rx.try_recv() {
+            frames.push(msg);
+        }
+        assert_eq!(1, frames.len());
+
+        let responses = match &frames[0] {
+            Message::Text(content) => match serde_json::from_str(content).unwrap() {
+                GameResponse::Batch { responses } => responses,
+                other => panic!("Expected a Batch but got {:?}", other),
+            },
+            other => panic!("Expected a Text-Frame but got {:?}", other),
+        };
+        assert_eq!(4, responses.len());
+        assert_eq!(
+            GameResponse::IndicatePlayer {
+                player: index,
+                name: format!("test{}", index),
+                you: true,
+                team: None,
+                color: PlayerColor::for_seat(index),
+            },
+            responses[index]
+        );
+    }
+}
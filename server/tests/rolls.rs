@@ -2,11 +2,14 @@ use std::vec;
 
 use axum::extract::ws::Message;
 use futures::StreamExt;
-use server::{statemachine::GameState, Figure, Game, GamePlayer, GameRequest};
+use server::{statemachine::GameState, Figure, Game, GamePlayer, GameRequest, GameResponse};
 
-use mocks::{DumbDistr, MockRand, MockSocket};
+use mocks::{DumbDistr, MockRand, MockSocket, SequenceDistr};
 use tracing_test::traced_test;
 
+mod common;
+use common::{responses, two_player_game};
+
 #[tokio::test]
 #[traced_test]
 async fn roll6_with_multiple_instart() {
@@ -29,7 +32,7 @@ async fn roll6_with_multiple_instart() {
             ),
             GamePlayer::new("test2".to_string(), MockSocket::new(tx2, vec![]).split()),
         ],
-        MockRand::new(vec![5, 3]),
+        MockRand::new(vec![0, 5, 3]),
     );
 
     game.next_player = 0;
@@ -78,7 +81,7 @@ async fn roll6_with_one_instart() {
             ),
             GamePlayer::new("test2".to_string(), MockSocket::new(tx2, vec![]).split()),
         ],
-        MockRand::new(vec![5, 3]),
+        MockRand::new(vec![0, 5, 3]),
     );
 
     game.next_player = 0;
@@ -89,6 +92,7 @@ async fn roll6_with_one_instart() {
     game.players[0].figures[1] = Figure::OnField { moved: 10 };
     game.players[0].figures[2] = Figure::OnField { moved: 11 };
     game.players[0].figures[3] = Figure::OnField { moved: 12 };
+    game.six_forces_start_exit = true;
 
     let (_rejointx, mut rejoinrx) = tokio::sync::mpsc::unbounded_channel();
 
@@ -134,7 +138,7 @@ async fn roll6_with_only_field() {
             ),
             GamePlayer::new("test2".to_string(), MockSocket::new(tx2, vec![]).split()),
         ],
-        MockRand::new(vec![5, 3]),
+        MockRand::new(vec![0, 5, 3]),
     );
 
     game.next_player = 0;
@@ -190,10 +194,11 @@ async fn use3_attempts_no_onfield() {
             ),
             GamePlayer::new("test2".to_string(), MockSocket::new(tx2, vec![]).split()),
         ],
-        MockRand::new(vec![0, 2, 3, 0]),
+        MockRand::new(vec![0, 0, 2, 3, 0]),
     );
 
     game.next_player = 0;
+    game.start_attempts = 2;
 
     let mut distr = DumbDistr {};
     let state = GameState::StartTurn { attempt: 0 };
@@ -225,6 +230,52 @@ async fn use3_attempts_no_onfield() {
     assert_eq!(0, game.next_player);
 }
 
+#[tokio::test]
+#[traced_test]
+async fn attempts_left_decrease() {
+    let (tx1, mut rx1) = tokio::sync::mpsc::unbounded_channel();
+    let (tx2, _rx2) = tokio::sync::mpsc::unbounded_channel();
+
+    let mut game: Game<_, MockSocket<Message>, MockSocket<Message>> = Game::new_with_rng(
+        uuid::Uuid::new_v4(),
+        vec![
+            GamePlayer::new(
+                "test".to_string(),
+                MockSocket::new(
+                    tx1,
+                    vec![Message::Text(serde_json::to_string(&GameRequest::Roll).unwrap()); 3],
+                )
+                .split(),
+            ),
+            GamePlayer::new("test2".to_string(), MockSocket::new(tx2, vec![]).split()),
+        ],
+        MockRand::new(vec![0]),
+    );
+
+    game.next_player = 0;
+    game.start_attempts = 2;
+
+    let mut distr = SequenceDistr::new(vec![1, 2, 3]);
+    let mut state = GameState::StartTurn { attempt: 0 };
+
+    let (_rejointx, mut rejoinrx) = tokio::sync::mpsc::unbounded_channel();
+
+    let mut attempts_left = Vec::new();
+    for _ in 0..3 {
+        state = server::statemachine::step(state, &mut game, &mut rejoinrx, &mut distr)
+            .await
+            .expect("");
+
+        attempts_left.extend(responses(&mut rx1).into_iter().filter_map(|r| match r {
+            GameResponse::Rolled { attempts_left, .. } => Some(attempts_left),
+            _ => None,
+        }));
+    }
+
+    assert_eq!(GameState::MoveToNextTurn, state);
+    assert_eq!(vec![Some(2), Some(1), Some(0)], attempts_left);
+}
+
 #[tokio::test]
 #[traced_test]
 async fn use3_attempts_already_done_inhouse() {
@@ -249,10 +300,11 @@ async fn use3_attempts_already_done_inhouse() {
             ),
             GamePlayer::new("test2".to_string(), MockSocket::new(tx2, vec![]).split()),
         ],
-        MockRand::new(vec![0, 2, 3, 0]),
+        MockRand::new(vec![0, 0, 2, 3, 0]),
     );
 
     game.next_player = 0;
+    game.start_attempts = 2;
     game.players[0].figures[1] = Figure::InHouse { pos: 3 };
 
     let mut distr = DumbDistr {};
@@ -309,10 +361,11 @@ async fn use3_attempts_not_done_inhouse() {
             ),
             GamePlayer::new("test2".to_string(), MockSocket::new(tx2, vec![]).split()),
         ],
-        MockRand::new(vec![0, 2, 3, 0]),
+        MockRand::new(vec![0, 0, 2, 3, 0]),
     );
 
     game.next_player = 0;
+    game.start_attempts = 2;
     game.players[0].figures[1] = Figure::InHouse { pos: 2 };
 
     let mut distr = DumbDistr {};
@@ -328,3 +381,578 @@ async fn use3_attempts_not_done_inhouse() {
     assert_eq!(GameState::Rolled { value: 1 }, n_state);
     assert_eq!(0, game.next_player);
 }
+
+#[tokio::test]
+#[traced_test]
+async fn six_forces_start_exit() {
+    let (mut game, _rx) = two_player_game(
+        vec![Message::Text(
+            serde_json::to_string(&GameRequest::Roll).unwrap(),
+        )],
+        vec![0, 5],
+    );
+    game.players[0].figures[0] = Figure::OnField { moved: 10 };
+    game.six_forces_start_exit = true;
+
+    let mut distr = DumbDistr {};
+    let state = GameState::StartTurn { attempt: 0 };
+
+    let (_rejointx, mut rejoinrx) = tokio::sync::mpsc::unbounded_channel();
+
+    let n_state = server::statemachine::step(state, &mut game, &mut rejoinrx, &mut distr)
+        .await
+        .expect("");
+
+    assert_eq!(GameState::StartTurn { attempt: 0 }, n_state);
+    assert_eq!(Figure::OnField { moved: 10 }, game.players[0].figures[0]);
+    assert_eq!(Figure::OnField { moved: 0 }, game.players[0].figures[1]);
+}
+
+#[tokio::test]
+#[traced_test]
+async fn six_allows_free_choice() {
+    let (mut game, mut rx) = two_player_game(
+        vec![
+            Message::Text(serde_json::to_string(&GameRequest::Roll).unwrap()),
+            Message::Text(serde_json::to_string(&GameRequest::Move { figure: 0 }).unwrap()),
+        ],
+        vec![0, 5],
+    );
+    game.players[0].figures[0] = Figure::OnField { moved: 10 };
+    assert!(!game.six_forces_start_exit);
+
+    let mut distr = DumbDistr {};
+    let state = GameState::StartTurn { attempt: 0 };
+
+    let (_rejointx, mut rejoinrx) = tokio::sync::mpsc::unbounded_channel();
+
+    let n_state = server::statemachine::step(state, &mut game, &mut rejoinrx, &mut distr)
+        .await
+        .expect("");
+
+    assert_eq!(GameState::Rolled { value: 6 }, n_state);
+    assert_eq!(Figure::InStart, game.players[0].figures[1]);
+
+    let n_state = server::statemachine::step(n_state, &mut game, &mut rejoinrx, &mut distr)
+        .await
+        .expect("");
+
+    assert_eq!(GameState::StartTurn { attempt: 0 }, n_state);
+    assert_eq!(Figure::OnField { moved: 16 }, game.players[0].figures[0]);
+    assert_eq!(Figure::InStart, game.players[0].figures[1]);
+
+    assert!(responses(&mut rx).contains(&GameResponse::MoveOptions {
+        value: 6,
+        figures: vec![0, 1, 2, 3]
+    }));
+}
+
+#[tokio::test]
+#[traced_test]
+async fn six_chooses_start_exit() {
+    let (mut game, mut rx) = two_player_game(
+        vec![
+            Message::Text(serde_json::to_string(&GameRequest::Roll).unwrap()),
+            Message::Text(serde_json::to_string(&GameRequest::Move { figure: 1 }).unwrap()),
+        ],
+        vec![0, 5],
+    );
+    game.players[0].figures[0] = Figure::OnField { moved: 10 };
+
+    let mut distr = DumbDistr {};
+    let state = GameState::StartTurn { attempt: 0 };
+
+    let (_rejointx, mut rejoinrx) = tokio::sync::mpsc::unbounded_channel();
+
+    let n_state = server::statemachine::step(state, &mut game, &mut rejoinrx, &mut distr)
+        .await
+        .expect("");
+
+    assert_eq!(GameState::Rolled { value: 6 }, n_state);
+
+    let n_state = server::statemachine::step(n_state, &mut game, &mut rejoinrx, &mut distr)
+        .await
+        .expect("");
+
+    assert_eq!(GameState::StartTurn { attempt: 0 }, n_state);
+    assert_eq!(Figure::OnField { moved: 10 }, game.players[0].figures[0]);
+    assert_eq!(Figure::OnField { moved: 0 }, game.players[0].figures[1]);
+    assert_eq!(Figure::InStart, game.players[0].figures[2]);
+
+    assert!(responses(&mut rx).contains(&GameResponse::Rolled {
+        value: 6,
+        can_move: true,
+        attempts_left: None,
+    }));
+}
+
+#[tokio::test]
+#[traced_test]
+async fn use1_attempt_no_onfield() {
+    let (tx1, _rx1) = tokio::sync::mpsc::unbounded_channel();
+    let (tx2, _rx2) = tokio::sync::mpsc::unbounded_channel();
+
+    let mut game: Game<_, MockSocket<Message>, MockSocket<Message>> = Game::new_with_rng(
+        uuid::Uuid::new_v4(),
+        vec![
+            GamePlayer::new(
+                "test".to_string(),
+                MockSocket::new(
+                    tx1,
+                    vec![Message::Text(
+                        serde_json::to_string(&GameRequest::Roll).unwrap(),
+                    )],
+                )
+                .split(),
+            ),
+            GamePlayer::new("test2".to_string(), MockSocket::new(tx2, vec![]).split()),
+        ],
+        MockRand::new(vec![0, 0]),
+    );
+
+    game.next_player = 0;
+    game.start_attempts = 0;
+
+    let mut distr = DumbDistr {};
+    let state = GameState::StartTurn { attempt: 0 };
+
+    let (_rejointx, mut rejoinrx) = tokio::sync::mpsc::unbounded_channel();
+
+    let n_state = server::statemachine::step(state, &mut game, &mut rejoinrx, &mut distr)
+        .await
+        .expect("");
+
+    assert_eq!(Figure::InStart, game.players[0].figures[0]);
+    assert_eq!(GameState::MoveToNextTurn, n_state);
+    assert_eq!(0, game.next_player);
+}
+
+#[tokio::test]
+#[traced_test]
+async fn turn_span_contains_roll() {
+    let (tx1, _rx1) = tokio::sync::mpsc::unbounded_channel();
+    let (tx2, _rx2) = tokio::sync::mpsc::unbounded_channel();
+
+    let mut game: Game<_, MockSocket<Message>, MockSocket<Message>> = Game::new_with_rng(
+        uuid::Uuid::new_v4(),
+        vec![
+            GamePlayer::new("test".to_string(), MockSocket::new(tx1, vec![]).split()),
+            GamePlayer::new(
+                "test2".to_string(),
+                MockSocket::new(
+                    tx2,
+                    vec![Message::Text(
+                        serde_json::to_string(&GameRequest::Roll).unwrap(),
+                    )],
+                )
+                .split(),
+            ),
+        ],
+        MockRand::new(vec![0, 2]),
+    );
+
+    game.next_player = 1;
+
+    let mut distr = DumbDistr {};
+    let state = GameState::StartTurn { attempt: 1 };
+
+    let (_rejointx, mut rejoinrx) = tokio::sync::mpsc::unbounded_channel();
+
+    server::statemachine::step(state, &mut game, &mut rejoinrx, &mut distr)
+        .await
+        .expect("");
+
+    assert!(logs_contain("turn{player=1 attempt=1 value=3}"));
+}
+
+#[tokio::test]
+#[traced_test]
+async fn move_before_roll_is_rejected() {
+    let (tx1, mut rx1) = tokio::sync::mpsc::unbounded_channel();
+    let (tx2, _rx2) = tokio::sync::mpsc::unbounded_channel();
+
+    let mut game: Game<_, MockSocket<Message>, MockSocket<Message>> = Game::new_with_rng(
+        uuid::Uuid::new_v4(),
+        vec![
+            GamePlayer::new(
+                "test".to_string(),
+                MockSocket::new(
+                    tx1,
+                    vec![Message::Text(
+                        serde_json::to_string(&GameRequest::Move { figure: 0 }).unwrap(),
+                    )],
+                )
+                .split(),
+            ),
+            GamePlayer::new("test2".to_string(), MockSocket::new(tx2, vec![]).split()),
+        ],
+        MockRand::new(vec![0]),
+    );
+
+    let mut distr = DumbDistr {};
+    let (_rejointx, mut rejoinrx) = tokio::sync::mpsc::unbounded_channel();
+
+    let n_state = server::statemachine::step(
+        GameState::StartTurn { attempt: 1 },
+        &mut game,
+        &mut rejoinrx,
+        &mut distr,
+    )
+    .await
+    .expect("");
+
+    assert_eq!(GameState::StartTurn { attempt: 1 }, n_state);
+    assert_eq!(
+        vec![
+            GameResponse::Turn,
+            GameResponse::InvalidRequest {
+                reason: "expected Roll".to_string()
+            }
+        ],
+        responses(&mut rx1)
+    );
+}
+
+#[tokio::test]
+#[traced_test]
+async fn malformed_request_is_rejected() {
+    let (tx1, mut rx1) = tokio::sync::mpsc::unbounded_channel();
+    let (tx2, _rx2) = tokio::sync::mpsc::unbounded_channel();
+
+    let mut game: Game<_, MockSocket<Message>, MockSocket<Message>> = Game::new_with_rng(
+        uuid::Uuid::new_v4(),
+        vec![
+            GamePlayer::new(
+                "test".to_string(),
+                MockSocket::new(
+                    tx1,
+                    vec![
+                        Message::Text("{\"type\":\"Roll".to_string()),
+                        Message::Text(serde_json::to_string(&GameRequest::Roll).unwrap()),
+                    ],
+                )
+                .split(),
+            ),
+            GamePlayer::new("test2".to_string(), MockSocket::new(tx2, vec![]).split()),
+        ],
+        MockRand::new(vec![0]),
+    );
+    game.next_player = 0;
+    game.players[0].figures[0] = Figure::OnField { moved: 5 };
+
+    let mut distr = SequenceDistr::new(vec![3]);
+    let (_rejointx, mut rejoinrx) = tokio::sync::mpsc::unbounded_channel();
+
+    let n_state = server::statemachine::step(
+        GameState::StartTurn { attempt: 0 },
+        &mut game,
+        &mut rejoinrx,
+        &mut distr,
+    )
+    .await
+    .expect("");
+
+    // The Player can still roll after the malformed Request
+    assert_eq!(GameState::Rolled { value: 3 }, n_state);
+
+    let received = responses(&mut rx1);
+    assert_eq!(GameResponse::Turn, received[0]);
+    assert!(matches!(received[1], GameResponse::InvalidRequest { .. }));
+    assert!(matches!(received[2], GameResponse::Rolled { value: 3, .. }));
+}
+
+#[tokio::test]
+#[traced_test]
+async fn second_roll_is_rejected() {
+    let (tx1, mut rx1) = tokio::sync::mpsc::unbounded_channel();
+    let (tx2, _rx2) = tokio::sync::mpsc::unbounded_channel();
+
+    let mut game: Game<_, MockSocket<Message>, MockSocket<Message>> = Game::new_with_rng(
+        uuid::Uuid::new_v4(),
+        vec![
+            GamePlayer::new(
+                "test".to_string(),
+                MockSocket::new(
+                    tx1,
+                    vec![Message::Text(
+                        serde_json::to_string(&GameRequest::Roll).unwrap(),
+                    )],
+                )
+                .split(),
+            ),
+            GamePlayer::new("test2".to_string(), MockSocket::new(tx2, vec![]).split()),
+        ],
+        MockRand::new(vec![0]),
+    );
+    game.players[0].figures[0] = Figure::OnField { moved: 5 };
+
+    let mut distr = DumbDistr {};
+    let (_rejointx, mut rejoinrx) = tokio::sync::mpsc::unbounded_channel();
+
+    let n_state = server::statemachine::step(
+        GameState::Rolled { value: 3 },
+        &mut game,
+        &mut rejoinrx,
+        &mut distr,
+    )
+    .await
+    .expect("");
+
+    assert_eq!(GameState::Rolled { value: 3 }, n_state);
+    assert_eq!(Figure::OnField { moved: 5 }, game.players[0].figures[0]);
+    assert_eq!(
+        vec![GameResponse::InvalidRequest {
+            reason: "expected Move".to_string()
+        }],
+        responses(&mut rx1)
+    );
+}
+
+#[test]
+fn rolled_wire_format() {
+    let resp = GameResponse::Rolled {
+        value: 6,
+        can_move: true,
+        attempts_left: None,
+    };
+
+    let content = serde_json::to_string(&resp).unwrap();
+    assert_eq!(r#"{"type":"Rolled","value":6,"can_move":true}"#, content);
+    assert_eq!(resp, serde_json::from_str(&content).unwrap());
+
+    let req: GameRequest = serde_json::from_str(r#"{"type":"Move","figure":2}"#).unwrap();
+    assert!(matches!(req, GameRequest::Move { figure: 2 }));
+}
+
+#[tokio::test]
+#[traced_test]
+async fn roll_pushed_while_waiting() {
+    let (tx1, mut rx1) = tokio::sync::mpsc::unbounded_channel();
+    let (tx2, _rx2) = tokio::sync::mpsc::unbounded_channel();
+
+    let socket = MockSocket::new(tx1, vec![]);
+    let remote = socket.clone();
+
+    let mut game: Game<_, MockSocket<Message>, MockSocket<Message>> = Game::new_with_rng(
+        uuid::Uuid::new_v4(),
+        vec![
+            GamePlayer::new("test".to_string(), socket.split()),
+            GamePlayer::new("test2".to_string(), MockSocket::new(tx2, vec![]).split()),
+        ],
+        MockRand::new(vec![0]),
+    );
+    game.next_player = 0;
+
+    let task = tokio::spawn(async move {
+        let mut distr = DumbDistr {};
+        let (_rejointx, mut rejoinrx) = tokio::sync::mpsc::unbounded_channel();
+
+        let state = server::statemachine::step(
+            GameState::StartTurn { attempt: 0 },
+            &mut game,
+            &mut rejoinrx,
+            &mut distr,
+        )
+        .await;
+        (game, state)
+    });
+
+    // The Turn is only sent once the Step is running and then waits for the Roll
+    assert_eq!(
+        Some(Message::Text(
+            serde_json::to_string(&GameResponse::Turn).unwrap()
+        )),
+        rx1.recv().await
+    );
+    tokio::task::yield_now().await;
+    assert!(!task.is_finished());
+
+    remote.push(Message::Text(
+        serde_json::to_string(&GameRequest::Roll).unwrap(),
+    ));
+
+    let (game, state) = task.await.unwrap();
+    assert_eq!(Some(GameState::StartTurn { attempt: 1 }), state);
+    assert_eq!(vec![1], game.players[0].recent_rolls());
+    assert_eq!(
+        vec![
+            GameResponse::Rolled {
+                value: 1,
+                can_move: false,
+                attempts_left: Some(2),
+            },
+            GameResponse::MoveOptions {
+                value: 1,
+                figures: vec![]
+            }
+        ],
+        responses(&mut rx1)
+    );
+}
+
+#[tokio::test]
+#[traced_test]
+async fn seeded_games_roll_identically() {
+    use rand::distributions::Distribution;
+
+    let new_game = || {
+        let (tx1, _rx1) = tokio::sync::mpsc::unbounded_channel();
+        let (tx2, _rx2) = tokio::sync::mpsc::unbounded_channel();
+
+        let game: Game<_, MockSocket<Message>, MockSocket<Message>> = Game::with_seed(
+            uuid::Uuid::new_v4(),
+            vec![
+                GamePlayer::new("test".to_string(), MockSocket::new(tx1, vec![]).split()),
+                GamePlayer::new("test2".to_string(), MockSocket::new(tx2, vec![]).split()),
+            ],
+            1234,
+        )
+        .unwrap();
+        game
+    };
+    let mut first = new_game();
+    let mut second = new_game();
+
+    assert_eq!(first.next_player, second.next_player);
+
+    let distr = rand::distributions::Uniform::new_inclusive(1, 6);
+    let first_rolls: Vec<usize> = (0..20).map(|_| distr.sample(&mut first.rng)).collect();
+    let second_rolls: Vec<usize> = (0..20).map(|_| distr.sample(&mut second.rng)).collect();
+    assert_eq!(first_rolls, second_rolls);
+}
+
+#[tokio::test]
+#[traced_test]
+async fn control_frames_do_not_consume_turn() {
+    let (tx1, mut rx1) = tokio::sync::mpsc::unbounded_channel();
+    let (tx2, _rx2) = tokio::sync::mpsc::unbounded_channel();
+
+    let mut game: Game<_, MockSocket<Message>, MockSocket<Message>> = Game::new_with_rng(
+        uuid::Uuid::new_v4(),
+        vec![
+            GamePlayer::new(
+                "test".to_string(),
+                MockSocket::new(
+                    tx1,
+                    vec![
+                        Message::Ping(vec![1, 2, 3]),
+                        Message::Pong(Vec::new()),
+                        Message::Binary(vec![4, 5]),
+                        Message::Text(serde_json::to_string(&GameRequest::Roll).unwrap()),
+                    ],
+                )
+                .split(),
+            ),
+            GamePlayer::new("test2".to_string(), MockSocket::new(tx2, vec![]).split()),
+        ],
+        MockRand::new(vec![0]),
+    );
+    game.next_player = 0;
+
+    let mut distr = SequenceDistr::new(vec![6]);
+    let state = GameState::StartTurn { attempt: 0 };
+
+    let (_rejointx, mut rejoinrx) = tokio::sync::mpsc::unbounded_channel();
+
+    let n_state = server::statemachine::step(state, &mut game, &mut rejoinrx, &mut distr)
+        .await
+        .expect("");
+
+    assert_eq!(GameState::StartTurn { attempt: 0 }, n_state);
+    assert_eq!(Figure::OnField { moved: 0 }, game.players[0].figures[0]);
+
+    let mut received = Vec::new();
+    while let Ok(msg) = rx1.try_recv() {
+        received.push(msg);
+    }
+    assert!(received.contains(&Message::Pong(vec![1, 2, 3])));
+    let invalid = serde_json::to_string(&GameResponse::InvalidRequest {
+        reason: "binary messages are not supported".to_string(),
+    })
+    .unwrap();
+    assert!(received.contains(&Message::Text(invalid)));
+}
+
+#[tokio::test]
+#[traced_test]
+async fn too_many_sixes_end_turn() {
+    let roll = || Message::Text(serde_json::to_string(&GameRequest::Roll).unwrap());
+    let move_figure =
+        |figure| Message::Text(serde_json::to_string(&GameRequest::Move { figure }).unwrap());
+    let (mut game, mut rx) = two_player_game(
+        vec![roll(), move_figure(0), roll(), move_figure(1), roll()],
+        vec![0, 5],
+    );
+    game.players[0].figures[0] = Figure::OnField { moved: 10 };
+    game.max_sixes_in_row = Some(2);
+    game.too_many_sixes_send_home = true;
+
+    let mut distr = SequenceDistr::new(vec![6, 6, 6]);
+    let mut state = GameState::StartTurn { attempt: 0 };
+
+    let (_rejointx, mut rejoinrx) = tokio::sync::mpsc::unbounded_channel();
+
+    for _ in 0..4 {
+        state = server::statemachine::step(state, &mut game, &mut rejoinrx, &mut distr)
+            .await
+            .expect("");
+    }
+    assert_eq!(GameState::StartTurn { attempt: 0 }, state);
+    assert_eq!(2, game.players[0].consecutive_sixes());
+    assert_eq!(Figure::OnField { moved: 16 }, game.players[0].figures[0]);
+    assert_eq!(Figure::OnField { moved: 0 }, game.players[0].figures[1]);
+
+    // The third 6 in a Row ends the Turn and sends the last moved Figure back to the Start
+    let state = server::statemachine::step(state, &mut game, &mut rejoinrx, &mut distr)
+        .await
+        .expect("");
+    assert_eq!(GameState::MoveToNextTurn, state);
+    assert_eq!(Figure::OnField { moved: 16 }, game.players[0].figures[0]);
+    assert_eq!(Figure::InStart, game.players[0].figures[1]);
+    assert!(responses(&mut rx).contains(&GameResponse::Rolled {
+        value: 6,
+        can_move: false,
+        attempts_left: None,
+    }));
+
+    let state = server::statemachine::step(state, &mut game, &mut rejoinrx, &mut distr)
+        .await
+        .expect("");
+    assert_eq!(GameState::StartTurn { attempt: 0 }, state);
+    assert_eq!(1, game.next_player);
+    assert_eq!(0, game.players[0].consecutive_sixes());
+}
+
+#[tokio::test]
+#[traced_test]
+async fn too_many_sixes_keep_figures_of_earlier_turns() {
+    let roll = || Message::Text(serde_json::to_string(&GameRequest::Roll).unwrap());
+    let move_figure =
+        |figure| Message::Text(serde_json::to_string(&GameRequest::Move { figure }).unwrap());
+    let (mut game, _rx) = two_player_game(vec![roll(), move_figure(0), roll()], vec![0, 5]);
+    game.players[0].figures[0] = Figure::OnField { moved: 10 };
+    game.max_sixes_in_row = Some(0);
+    game.too_many_sixes_send_home = true;
+    game.should_skip = Box::new(|p| p.name == "test2");
+
+    let mut distr = SequenceDistr::new(vec![2, 6]);
+    let mut state = GameState::StartTurn { attempt: 0 };
+
+    let (_rejointx, mut rejoinrx) = tokio::sync::mpsc::unbounded_channel();
+
+    for _ in 0..3 {
+        state = server::statemachine::step(state, &mut game, &mut rejoinrx, &mut distr)
+            .await
+            .expect("");
+    }
+    assert_eq!(GameState::StartTurn { attempt: 0 }, state);
+    assert_eq!(0, game.next_player);
+    assert_eq!(Figure::OnField { moved: 12 }, game.players[0].figures[0]);
+
+    // The Figure was moved during the previous Turn of the same Player, so it stays on the Field
+    let state = server::statemachine::step(state, &mut game, &mut rejoinrx, &mut distr)
+        .await
+        .expect("");
+    assert_eq!(GameState::MoveToNextTurn, state);
+    assert_eq!(Figure::OnField { moved: 12 }, game.players[0].figures[0]);
+}
@@ -0,0 +1,109 @@
+use server::{statemachine::GameState, Figure, TurnDirection};
+
+use mocks::DumbDistr;
+use tracing_test::traced_test;
+
+mod common;
+use common::{game_with_players, TestGame};
+
+/// Passes the Turn on from the current Player as often as given and returns the Order in which
+/// the Players got their Turn
+async fn rotate(game: &mut TestGame, turns: usize) -> Vec<usize> {
+    let mut distr = DumbDistr {};
+    let (_rejointx, mut rejoinrx) = tokio::sync::mpsc::unbounded_channel();
+
+    let mut order = Vec::new();
+    for _ in 0..turns {
+        let n_state =
+            server::statemachine::step(GameState::MoveToNextTurn, game, &mut rejoinrx, &mut distr)
+                .await
+                .expect("");
+        assert_eq!(GameState::StartTurn { attempt: 0 }, n_state);
+
+        order.push(game.next_player);
+    }
+    order
+}
+
+#[tokio::test]
+#[traced_test]
+async fn clockwise_by_default() {
+    let mut game = game_with_players(3).with_starting_player(0);
+
+    assert_eq!(TurnDirection::Clockwise, game.direction);
+    assert_eq!(vec![1, 2, 0, 1], rotate(&mut game, 4).await);
+}
+
+#[tokio::test]
+#[traced_test]
+async fn counter_clockwise() {
+    let mut game = game_with_players(3).with_starting_player(0);
+    game.direction = TurnDirection::CounterClockwise;
+
+    assert_eq!(vec![2, 1, 0, 2], rotate(&mut game, 4).await);
+}
+
+#[tokio::test]
+#[traced_test]
+async fn counter_clockwise_skips_done_players() {
+    let mut game = game_with_players(3).with_starting_player(0);
+    game.direction = TurnDirection::CounterClockwise;
+
+    game.players[2].figures = [
+        Figure::InHouse { pos: 0 },
+        Figure::InHouse { pos: 1 },
+        Figure::InHouse { pos: 2 },
+        Figure::InHouse { pos: 3 },
+    ];
+    game.players[2].check_done();
+    game.ranking.push(2);
+
+    assert_eq!(vec![1, 0, 1], rotate(&mut game, 3).await);
+}
+
+#[tokio::test]
+#[traced_test]
+async fn custom_skip_predicate() {
+    let mut game = game_with_players(3).with_starting_player(0);
+
+    // Only Players with a Figure outside of the Start get a Turn
+    game.should_skip = Box::new(|p| !p.has_figures_on_field());
+    game.players[0].figures[0] = Figure::OnField { moved: 3 };
+    game.players[2].figures[0] = Figure::OnField { moved: 7 };
+
+    assert_eq!(Some(2), game.next_movable_player(0));
+    assert_eq!(vec![2, 0, 2], rotate(&mut game, 3).await);
+}
+
+#[tokio::test]
+#[traced_test]
+async fn only_remaining_player_keeps_the_turn() {
+    let mut game = game_with_players(3).with_starting_player(1);
+    game.should_skip = Box::new(|p| p.name != "test1");
+
+    assert_eq!(Some(1), game.next_movable_player(1));
+    assert_eq!(vec![1, 1], rotate(&mut game, 2).await);
+}
+
+#[tokio::test]
+#[traced_test]
+async fn everyone_skipped_ends_the_game() {
+    let mut game = game_with_players(3).with_starting_player(1);
+    game.should_skip = Box::new(|_| true);
+
+    assert_eq!(None, game.next_movable_player(1));
+
+    let mut distr = DumbDistr {};
+    let (_rejointx, mut rejoinrx) = tokio::sync::mpsc::unbounded_channel();
+    let n_state = server::statemachine::step(
+        GameState::MoveToNextTurn,
+        &mut game,
+        &mut rejoinrx,
+        &mut distr,
+    )
+    .await
+    .expect("");
+
+    assert_eq!(GameState::Done, n_state);
+    assert!(game.is_done());
+}
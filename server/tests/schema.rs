@@ -0,0 +1,26 @@
+#![cfg(feature = "schema")]
+
+#[test]
+fn schema_contains_variants() {
+    let schema = server::schema::protocol_schema();
+
+    let requests = schema["GameRequest"].to_string();
+    assert!(requests.contains("\"Move\""));
+    assert!(requests.contains("\"figure\""));
+
+    let responses = schema["GameResponse"].to_string();
+    assert!(responses.contains("\"Rolled\""));
+    assert!(responses.contains("\"can_move\""));
+}
+
+#[test]
+fn schema_is_written() {
+    let path = std::env::temp_dir().join(format!("madn-schema-{}.json", uuid::Uuid::new_v4()));
+
+    server::schema::write_protocol_schema(&path).unwrap();
+    let written: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+    std::fs::remove_file(&path).unwrap();
+
+    assert_eq!(server::schema::protocol_schema(), written);
+}
@@ -0,0 +1,118 @@
+use axum::extract::ws::Message;
+use futures::StreamExt;
+use server::{Figure, Game, GamePlayer, GameSnapshot, PlayerView};
+
+use mocks::{MockRand, MockSocket};
+use tracing_test::traced_test;
+
+#[test]
+#[traced_test]
+fn snapshot_roundtrip() {
+    let (tx1, _rx1) = tokio::sync::mpsc::unbounded_channel();
+    let (tx2, _rx2) = tokio::sync::mpsc::unbounded_channel();
+
+    let game: Game<_, MockSocket<Message>, MockSocket<Message>> = Game::from_state(
+        uuid::Uuid::new_v4(),
+        vec![
+            (
+                GamePlayer::new("test".to_string(), MockSocket::new(tx1, vec![]).split()),
+                [
+                    Figure::OnField { moved: 12 },
+                    Figure::InHouse { pos: 3 },
+                    Figure::InStart,
+                    Figure::InStart,
+                ],
+            ),
+            (
+                GamePlayer::new("test2".to_string(), MockSocket::new(tx2, vec![]).split()),
+                [
+                    Figure::InHouse { pos: 0 },
+                    Figure::InHouse { pos: 1 },
+                    Figure::InHouse { pos: 2 },
+                    Figure::InHouse { pos: 3 },
+                ],
+            ),
+        ],
+        0,
+        vec![1],
+        MockRand::new(vec![]),
+    );
+
+    let snapshot = game.snapshot();
+    let serialized = serde_json::to_string(&snapshot).unwrap();
+    let deserialized: GameSnapshot = serde_json::from_str(&serialized).unwrap();
+    assert_eq!(snapshot, deserialized);
+
+    let (tx1, _rx1) = tokio::sync::mpsc::unbounded_channel();
+    let (tx2, _rx2) = tokio::sync::mpsc::unbounded_channel();
+    let restored: Game<_, MockSocket<Message>, MockSocket<Message>> = Game::restore(
+        deserialized,
+        vec![
+            MockSocket::new(tx1, vec![]).split(),
+            MockSocket::new(tx2, vec![]).split(),
+        ],
+        MockRand::new(vec![]),
+    )
+    .unwrap();
+
+    assert_eq!(game.id(), restored.id());
+    assert_eq!(game.next_player, restored.next_player);
+    assert_eq!(game.ranking, restored.ranking);
+    for (original, restored) in game.players.iter().zip(restored.players.iter()) {
+        assert_eq!(original.name, restored.name);
+        assert_eq!(original.figures, restored.figures);
+        assert_eq!(original.rejoin_code(), restored.rejoin_code());
+        assert_eq!(original.is_done(), restored.is_done());
+    }
+}
+
+#[test]
+#[traced_test]
+fn player_views_match_players() {
+    let (tx1, _rx1) = tokio::sync::mpsc::unbounded_channel();
+    let (tx2, _rx2) = tokio::sync::mpsc::unbounded_channel();
+
+    let game: Game<_, MockSocket<Message>, MockSocket<Message>> = Game::from_state(
+        uuid::Uuid::new_v4(),
+        vec![
+            (
+                GamePlayer::new("test".to_string(), MockSocket::new(tx1, vec![]).split()),
+                [
+                    Figure::OnField { moved: 12 },
+                    Figure::InHouse { pos: 3 },
+                    Figure::InStart,
+                    Figure::InStart,
+                ],
+            ),
+            (
+                GamePlayer::new("test2".to_string(), MockSocket::new(tx2, vec![]).split()),
+                [
+                    Figure::InHouse { pos: 0 },
+                    Figure::InHouse { pos: 1 },
+                    Figure::InHouse { pos: 2 },
+                    Figure::InHouse { pos: 3 },
+                ],
+            ),
+        ],
+        0,
+        vec![1],
+        MockRand::new(vec![]),
+    );
+
+    let views = game.player_views();
+    assert_eq!(
+        vec![
+            PlayerView {
+                name: "test".to_string(),
+                figures: game.players[0].figures.clone(),
+                done: false,
+            },
+            PlayerView {
+                name: "test2".to_string(),
+                figures: game.players[1].figures.clone(),
+                done: true,
+            },
+        ],
+        views
+    );
+}
@@ -0,0 +1,298 @@
+use axum::extract::ws::Message;
+use futures::StreamExt;
+use server::{
+    statemachine::GameState, Figure, Game, GamePlayer, GameRequest, GameResponse, RejoinKey,
+};
+
+use mocks::{DumbDistr, MockRand, MockSocket};
+use tracing_test::traced_test;
+
+mod common;
+use common::responses;
+
+#[tokio::test]
+#[traced_test]
+async fn spectator_receives_updates() {
+    let (tx1, _rx1) = tokio::sync::mpsc::unbounded_channel();
+    let (tx2, _rx2) = tokio::sync::mpsc::unbounded_channel();
+    let (spectator_tx, mut spectator_rx) = tokio::sync::mpsc::unbounded_channel();
+
+    let mut game: Game<_, MockSocket<Message>, MockSocket<Message>> = Game::new_with_rng(
+        uuid::Uuid::new_v4(),
+        vec![
+            GamePlayer::new(
+                "test".to_string(),
+                MockSocket::new(
+                    tx1,
+                    vec![Message::Text(
+                        serde_json::to_string(&GameRequest::Roll).unwrap(),
+                    )],
+                )
+                .split(),
+            ),
+            GamePlayer::new("test2".to_string(), MockSocket::new(tx2, vec![]).split()),
+        ],
+        MockRand::new(vec![0, 5]),
+    );
+
+    game.next_player = 0;
+
+    let (spectator_sink, _) = MockSocket::new(spectator_tx, vec![]).split();
+    game.add_spectator(spectator_sink).await;
+
+    let initial = responses(&mut spectator_rx);
+    assert_eq!(4, initial.len());
+    assert!(matches!(initial[0], GameResponse::State { .. }));
+    assert!(matches!(initial[1], GameResponse::Board { .. }));
+    assert_eq!(GameResponse::ActivePlayer { player: 0 }, initial[2]);
+    assert!(matches!(initial[3], GameResponse::RejoinCode { .. }));
+
+    let mut distr = DumbDistr {};
+    let state = GameState::StartTurn { attempt: 0 };
+
+    let (_rejointx, mut rejoinrx) = tokio::sync::mpsc::unbounded_channel();
+
+    let n_state = server::statemachine::step(state, &mut game, &mut rejoinrx, &mut distr)
+        .await
+        .expect("");
+
+    assert_eq!(Figure::OnField { moved: 0 }, game.players[0].figures[0]);
+    assert_eq!(GameState::StartTurn { attempt: 0 }, n_state);
+    assert_eq!(0, game.next_player);
+    assert_eq!(2, game.players.len());
+
+    let updates = responses(&mut spectator_rx);
+    assert_eq!(4, updates.len());
+    assert_eq!(GameResponse::ActivePlayer { player: 0 }, updates[0]);
+    assert_eq!(
+        GameResponse::Rolled {
+            value: 6,
+            can_move: false,
+            attempts_left: Some(2),
+        },
+        updates[1]
+    );
+    match &updates[2] {
+        GameResponse::State { players, .. } => {
+            assert_eq!(Figure::OnField { moved: 0 }, players[0].1[0]);
+        }
+        other => panic!("Expected State but got {:?}", other),
+    };
+    assert!(matches!(updates[3], GameResponse::Board { .. }));
+}
+
+#[tokio::test]
+#[traced_test]
+async fn spectator_rejoins() {
+    let (tx1, _rx1) = tokio::sync::mpsc::unbounded_channel();
+    let (tx2, _rx2) = tokio::sync::mpsc::unbounded_channel();
+    let (spectator_tx, mut spectator_rx) = tokio::sync::mpsc::unbounded_channel();
+
+    let mut game: Game<_, MockSocket<Message>, MockSocket<Message>> = Game::new_with_rng(
+        uuid::Uuid::new_v4(),
+        vec![
+            GamePlayer::new("test".to_string(), MockSocket::new(tx1, vec![]).split()),
+            GamePlayer::new("test2".to_string(), MockSocket::new(tx2, vec![]).split()),
+        ],
+        MockRand::new(vec![0]),
+    );
+    game.next_player = 1;
+
+    let (spectator_sink, _) = MockSocket::new(spectator_tx, vec![]).split();
+    let code = game
+        .add_spectator(spectator_sink)
+        .await
+        .expect("The Spectator is reachable");
+    assert_eq!(
+        Some(&GameResponse::RejoinCode {
+            game: game.id(),
+            code
+        }),
+        responses(&mut spectator_rx).last()
+    );
+
+    // The Connection of the Spectator drops and they reconnect with their Code
+    game.spectators.clear();
+    game.players[1].figures[0] = Figure::OnField { moved: 3 };
+
+    let (unknown_tx, mut unknown_rx) = tokio::sync::mpsc::unbounded_channel();
+    let (unknown_sink, _) = MockSocket::new(unknown_tx, vec![]).split();
+    assert!(
+        !game
+            .rejoin_spectator(uuid::Uuid::new_v4(), unknown_sink)
+            .await
+    );
+    assert!(responses(&mut unknown_rx).is_empty());
+
+    let (rejoin_tx, mut rejoin_rx) = tokio::sync::mpsc::unbounded_channel();
+    let (rejoin_sink, _) = MockSocket::new(rejoin_tx, vec![]).split();
+    assert!(game.rejoin_spectator(code, rejoin_sink).await);
+    assert_eq!(1, game.spectators.len());
+
+    let received = responses(&mut rejoin_rx);
+    assert_eq!(3, received.len());
+    match &received[0] {
+        GameResponse::State { players, .. } => {
+            assert_eq!(Figure::OnField { moved: 3 }, players[1].1[0]);
+        }
+        other => panic!("Expected State, got {:?}", other),
+    };
+    assert!(matches!(received[1], GameResponse::Board { .. }));
+    assert_eq!(GameResponse::ActivePlayer { player: 1 }, received[2]);
+}
+
+#[tokio::test]
+#[traced_test]
+async fn spectator_joins_while_waiting_for_player() {
+    let (tx1, _rx1) = tokio::sync::mpsc::unbounded_channel();
+    let (tx2, _rx2) = tokio::sync::mpsc::unbounded_channel();
+    let (spectator_tx, mut spectator_rx) = tokio::sync::mpsc::unbounded_channel();
+
+    let first = MockSocket::new(tx1, vec![]);
+    let mut game: Game<_, MockSocket<Message>, MockSocket<Message>> = Game::new_with_rng(
+        uuid::Uuid::new_v4(),
+        vec![
+            GamePlayer::new("test".to_string(), first.clone().split()),
+            GamePlayer::new("test2".to_string(), MockSocket::new(tx2, vec![]).split()),
+        ],
+        MockRand::new(vec![0, 5]),
+    );
+    game.next_player = 0;
+
+    let mut distr = DumbDistr {};
+    let (rejointx, mut rejoinrx) = tokio::sync::mpsc::unbounded_channel();
+    rejointx
+        .send((
+            RejoinKey::Spectate,
+            MockSocket::new(spectator_tx, vec![]).split(),
+        ))
+        .unwrap();
+
+    // The Spectator is served while the Game is still waiting for the first Player to roll
+    let (n_state, initial) = tokio::join!(
+        server::statemachine::step(
+            GameState::StartTurn { attempt: 0 },
+            &mut game,
+            &mut rejoinrx,
+            &mut distr,
+        ),
+        async {
+            let initial = loop {
+                let initial = responses(&mut spectator_rx);
+                if !initial.is_empty() {
+                    break initial;
+                }
+                tokio::time::sleep(std::time::Duration::from_millis(5)).await;
+            };
+            first.push(Message::Text(
+                serde_json::to_string(&GameRequest::Roll).unwrap(),
+            ));
+            initial
+        }
+    );
+
+    assert_eq!(Some(GameState::StartTurn { attempt: 0 }), n_state);
+    assert_eq!(4, initial.len());
+    assert!(matches!(initial[0], GameResponse::State { .. }));
+    assert!(matches!(initial[1], GameResponse::Board { .. }));
+    assert_eq!(GameResponse::ActivePlayer { player: 0 }, initial[2]);
+    let code = match initial[3] {
+        GameResponse::RejoinCode { code, .. } => code,
+        ref other => panic!("Expected RejoinCode, got {:?}", other),
+    };
+    assert_eq!(&[code], game.spectator_codes());
+
+    let updates = responses(&mut spectator_rx);
+    assert!(updates.contains(&GameResponse::Rolled {
+        value: 6,
+        can_move: false,
+        attempts_left: Some(2),
+    }));
+
+    // Reconnecting with the Code goes through the same Channel
+    game.spectators.clear();
+    let (rejoin_tx, mut rejoin_rx) = tokio::sync::mpsc::unbounded_channel();
+    rejointx
+        .send((
+            RejoinKey::Code(code),
+            MockSocket::new(rejoin_tx, vec![]).split(),
+        ))
+        .unwrap();
+    first.push(Message::Text(
+        serde_json::to_string(&GameRequest::Roll).unwrap(),
+    ));
+    server::statemachine::step(
+        GameState::StartTurn { attempt: 0 },
+        &mut game,
+        &mut rejoinrx,
+        &mut distr,
+    )
+    .await
+    .expect("");
+
+    assert_eq!(1, game.spectators.len());
+    let received = responses(&mut rejoin_rx);
+    assert!(matches!(received[0], GameResponse::State { .. }));
+    assert!(matches!(received[1], GameResponse::Board { .. }));
+    assert_eq!(GameResponse::ActivePlayer { player: 0 }, received[2]);
+}
+
+#[tokio::test]
+#[traced_test]
+async fn subscriber_receives_updates() {
+    let (tx1, _rx1) = tokio::sync::mpsc::unbounded_channel();
+    let (tx2, _rx2) = tokio::sync::mpsc::unbounded_channel();
+
+    let (events, _) = tokio::sync::broadcast::channel(16);
+    let mut game: Game<_, MockSocket<Message>, MockSocket<Message>> = Game::new_with_rng(
+        uuid::Uuid::new_v4(),
+        vec![
+            GamePlayer::new(
+                "test".to_string(),
+                MockSocket::new(
+                    tx1,
+                    vec![Message::Text(
+                        serde_json::to_string(&GameRequest::Roll).unwrap(),
+                    )],
+                )
+                .split(),
+            ),
+            GamePlayer::new("test2".to_string(), MockSocket::new(tx2, vec![]).split()),
+        ],
+        MockRand::new(vec![0, 5]),
+    )
+    .with_events(events.clone());
+    let mut subscriber = events.subscribe();
+    let mut other = game.subscribe();
+
+    let mut distr = DumbDistr {};
+    let (_rejointx, mut rejoinrx) = tokio::sync::mpsc::unbounded_channel();
+
+    server::statemachine::step(
+        GameState::StartTurn { attempt: 0 },
+        &mut game,
+        &mut rejoinrx,
+        &mut distr,
+    )
+    .await
+    .expect("");
+
+    let mut updates = Vec::new();
+    while let Ok(resp) = subscriber.try_recv() {
+        updates.push(resp);
+    }
+    assert_eq!(4, updates.len());
+    assert_eq!(GameResponse::ActivePlayer { player: 0 }, updates[0]);
+    assert_eq!(
+        GameResponse::Rolled {
+            value: 6,
+            can_move: false,
+            attempts_left: Some(2),
+        },
+        updates[1]
+    );
+    assert!(matches!(updates[2], GameResponse::State { .. }));
+    assert!(matches!(updates[3], GameResponse::Board { .. }));
+
+    assert_eq!(Ok(updates[0].clone()), other.try_recv());
+}
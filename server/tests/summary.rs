@@ -0,0 +1,150 @@
+use server::{Figure, TurnSummary};
+
+use tracing_test::traced_test;
+
+mod common;
+use common::two_player_game;
+
+#[test]
+#[traced_test]
+fn before_rolling() {
+    let (mut game, _rx) = two_player_game(vec![], vec![0]);
+    game.players[0].figures = [
+        Figure::InStart,
+        Figure::InStart,
+        Figure::InStart,
+        Figure::InStart,
+    ];
+
+    assert_eq!(
+        TurnSummary {
+            value: None,
+            legal_moves: Vec::new(),
+            forced_figure: None,
+            must_leave_start: false,
+            no_moves: false,
+        },
+        game.turn_summary(None)
+    );
+}
+
+#[test]
+#[traced_test]
+fn all_in_start() {
+    let (mut game, _rx) = two_player_game(vec![], vec![0]);
+    game.players[0].figures = [
+        Figure::InStart,
+        Figure::InStart,
+        Figure::InStart,
+        Figure::InStart,
+    ];
+
+    assert_eq!(
+        TurnSummary {
+            value: Some(3),
+            legal_moves: Vec::new(),
+            forced_figure: None,
+            must_leave_start: false,
+            no_moves: true,
+        },
+        game.turn_summary(Some(3))
+    );
+    assert_eq!(
+        TurnSummary {
+            value: Some(6),
+            legal_moves: vec![0, 1, 2, 3],
+            forced_figure: Some(0),
+            must_leave_start: true,
+            no_moves: false,
+        },
+        game.turn_summary(Some(6))
+    );
+}
+
+#[test]
+#[traced_test]
+fn start_field_has_to_be_vacated() {
+    let (mut game, _rx) = two_player_game(vec![], vec![0]);
+    game.players[0].figures = [
+        Figure::OnField { moved: 0 },
+        Figure::OnField { moved: 12 },
+        Figure::InStart,
+        Figure::InStart,
+    ];
+
+    assert_eq!(
+        TurnSummary {
+            value: Some(4),
+            legal_moves: vec![0, 1],
+            forced_figure: Some(0),
+            must_leave_start: false,
+            no_moves: false,
+        },
+        game.turn_summary(Some(4))
+    );
+}
+
+#[test]
+#[traced_test]
+fn free_choice() {
+    let (mut game, _rx) = two_player_game(vec![], vec![0]);
+    game.players[0].figures = [
+        Figure::OnField { moved: 5 },
+        Figure::OnField { moved: 12 },
+        Figure::InStart,
+        Figure::InHouse { pos: 3 },
+    ];
+
+    assert_eq!(
+        TurnSummary {
+            value: Some(6),
+            legal_moves: vec![0, 1, 2],
+            forced_figure: None,
+            must_leave_start: false,
+            no_moves: false,
+        },
+        game.turn_summary(Some(6))
+    );
+}
+
+#[test]
+#[traced_test]
+fn start_field_vacating_disabled() {
+    let (mut game, _rx) = two_player_game(vec![], vec![0]);
+    game.players[0].figures = [
+        Figure::OnField { moved: 0 },
+        Figure::OnField { moved: 12 },
+        Figure::InStart,
+        Figure::InStart,
+    ];
+    assert_eq!(Some(0), game.must_vacate_start(0));
+
+    game.vacate_start = false;
+    assert_eq!(None, game.must_vacate_start(0));
+
+    assert_eq!(
+        TurnSummary {
+            value: Some(4),
+            legal_moves: vec![0, 1],
+            forced_figure: None,
+            must_leave_start: false,
+            no_moves: false,
+        },
+        game.turn_summary(Some(4))
+    );
+}
+
+#[test]
+#[traced_test]
+fn start_field_without_figures_in_start() {
+    let (mut game, _rx) = two_player_game(vec![], vec![0]);
+    game.players[0].figures = [
+        Figure::OnField { moved: 0 },
+        Figure::OnField { moved: 12 },
+        Figure::InHouse { pos: 2 },
+        Figure::InHouse { pos: 3 },
+    ];
+
+    assert_eq!(None, game.must_vacate_start(0));
+    assert_eq!(None, game.turn_summary(Some(4)).forced_figure);
+}
@@ -0,0 +1,162 @@
+use axum::extract::ws::Message;
+use futures::StreamExt;
+use server::{
+    statemachine::GameState, Figure, Game, GameEndReason, GamePlayer, GameRequest, GameResponse,
+};
+
+use mocks::{MockRand, MockSocket, SequenceDistr};
+use tracing_test::traced_test;
+
+mod common;
+use common::responses;
+
+#[tokio::test]
+#[traced_test]
+async fn teammates_share_field_and_win_together() {
+    let (tx0, mut rx0) = tokio::sync::mpsc::unbounded_channel();
+    let (tx1, _rx1) = tokio::sync::mpsc::unbounded_channel();
+    let (tx2, _rx2) = tokio::sync::mpsc::unbounded_channel();
+    let (tx3, _rx3) = tokio::sync::mpsc::unbounded_channel();
+
+    let in_house = [
+        Figure::InHouse { pos: 0 },
+        Figure::InHouse { pos: 1 },
+        Figure::InHouse { pos: 2 },
+        Figure::InHouse { pos: 3 },
+    ];
+    let mut game: Game<_, MockSocket<Message>, MockSocket<Message>> = Game::from_state(
+        uuid::Uuid::new_v4(),
+        vec![
+            (
+                GamePlayer::new(
+                    "test0".to_string(),
+                    MockSocket::new(
+                        tx0,
+                        vec![Message::Text(
+                            serde_json::to_string(&GameRequest::Move { figure: 3 }).unwrap(),
+                        )],
+                    )
+                    .split(),
+                ),
+                [
+                    Figure::InHouse { pos: 1 },
+                    Figure::InHouse { pos: 2 },
+                    Figure::InHouse { pos: 3 },
+                    Figure::OnField { moved: 17 },
+                ],
+            ),
+            (
+                GamePlayer::new("test1".to_string(), MockSocket::new(tx1, vec![]).split()),
+                [
+                    Figure::OnField { moved: 5 },
+                    Figure::InStart,
+                    Figure::InStart,
+                    Figure::InStart,
+                ],
+            ),
+            (
+                GamePlayer::new("test2".to_string(), MockSocket::new(tx2, vec![]).split()),
+                in_house.clone(),
+            ),
+            (
+                GamePlayer::new("test3".to_string(), MockSocket::new(tx3, vec![]).split()),
+                [
+                    Figure::OnField { moved: 2 },
+                    Figure::InStart,
+                    Figure::InStart,
+                    Figure::InStart,
+                ],
+            ),
+        ],
+        0,
+        vec![2],
+        MockRand::new(vec![]),
+    );
+    game.teams = Some(vec![0, 1, 0, 1]);
+
+    // Figure 3 of Player 0 and a Figure of their Teammate share a Field without a Capture
+    game.players[2].figures[0] = Figure::OnField { moved: 37 };
+    assert!(game.check_move(0, 3).is_empty());
+    assert_eq!(Figure::OnField { moved: 37 }, game.players[2].figures[0]);
+    game.players[2].figures = in_house;
+
+    let mut distr = SequenceDistr::new(vec![]);
+    let (_rejointx, mut rejoinrx) = tokio::sync::mpsc::unbounded_channel();
+
+    let n_state = server::statemachine::step(
+        GameState::Rolled { value: 23 },
+        &mut game,
+        &mut rejoinrx,
+        &mut distr,
+    )
+    .await
+    .expect("");
+    assert_eq!(GameState::MoveToNextTurn, n_state);
+    assert_eq!(Figure::InHouse { pos: 0 }, game.players[0].figures[3]);
+
+    let n_state = server::statemachine::step(n_state, &mut game, &mut rejoinrx, &mut distr)
+        .await
+        .expect("");
+    assert_eq!(GameState::Done, n_state);
+    assert_eq!(vec![2, 0], game.ranking[..2]);
+
+    assert!(responses(&mut rx0).iter().any(|r| matches!(
+        r,
+        GameResponse::GameDone {
+            reason: GameEndReason::TeamFinished,
+            ..
+        }
+    )));
+}
+
+#[test]
+#[traced_test]
+fn teammates_are_not_captured() {
+    let players: Vec<_> = (0..4)
+        .map(|i| {
+            let (tx, _rx) = tokio::sync::mpsc::unbounded_channel();
+            GamePlayer::new(format!("test{}", i), MockSocket::new(tx, vec![]).split())
+        })
+        .collect();
+    let mut game: Game<_, MockSocket<Message>, MockSocket<Message>> =
+        Game::new_with_rng(uuid::Uuid::new_v4(), players, MockRand::new(vec![0]));
+    game.teams = Some(vec![0, 1, 0, 1]);
+
+    // All Figures stand on the absolute Field 25
+    game.players[0].figures[0] = Figure::OnField { moved: 25 };
+    game.players[1].figures[0] = Figure::OnField { moved: 15 };
+    game.players[2].figures[0] = Figure::OnField { moved: 5 };
+
+    assert_eq!(vec![(1, 0)], game.check_move(0, 0));
+    assert_eq!(Figure::OnField { moved: 5 }, game.players[2].figures[0]);
+    assert!(game.are_teammates(0, 2));
+    assert!(!game.are_teammates(0, 1));
+    assert_eq!(Some(1), game.team_of(3));
+}
+
+#[test]
+#[traced_test]
+fn short_teams_do_not_panic() {
+    let players: Vec<_> = (0..4)
+        .map(|i| {
+            let (tx, _rx) = tokio::sync::mpsc::unbounded_channel();
+            GamePlayer::new(format!("test{}", i), MockSocket::new(tx, vec![]).split())
+        })
+        .collect();
+    let mut game: Game<_, MockSocket<Message>, MockSocket<Message>> =
+        Game::new_with_rng(uuid::Uuid::new_v4(), players, MockRand::new(vec![0]));
+    game.teams = Some(vec![0, 1]);
+
+    // The Player without a Team finishing does not finish any Team
+    game.players[3].figures = [
+        Figure::InHouse { pos: 0 },
+        Figure::InHouse { pos: 1 },
+        Figure::InHouse { pos: 2 },
+        Figure::InHouse { pos: 3 },
+    ];
+    game.players[3].check_done();
+    game.ranking.push(3);
+
+    assert_eq!(None, game.team_of(3));
+    assert_eq!(None, game.finished_team());
+}
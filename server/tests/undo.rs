@@ -0,0 +1,120 @@
+use std::time::Duration;
+
+use axum::extract::ws::Message;
+use futures::StreamExt;
+use server::{statemachine::GameState, Figure, Game, GamePlayer, GameRequest};
+
+use mocks::{DumbDistr, MockRand, MockSocket};
+use tracing_test::traced_test;
+
+fn capturing_game(
+    requests: Vec<GameRequest>,
+) -> Game<MockRand, MockSocket<Message>, MockSocket<Message>> {
+    let (tx1, _rx1) = tokio::sync::mpsc::unbounded_channel();
+    let (tx2, _rx2) = tokio::sync::mpsc::unbounded_channel();
+
+    let msgs = requests
+        .iter()
+        .map(|r| Message::Text(serde_json::to_string(r).unwrap()))
+        .collect();
+
+    let mut game = Game::new_with_rng(
+        uuid::Uuid::new_v4(),
+        vec![
+            GamePlayer::new("test".to_string(), MockSocket::new(tx1, msgs).split()),
+            GamePlayer::new("test2".to_string(), MockSocket::new(tx2, vec![]).split()),
+        ],
+        MockRand::new(vec![0]),
+    );
+
+    // Moving the first Figure by 5 captures the Figure of the second Player
+    game.next_player = 0;
+    game.players[0].figures[0] = Figure::OnField { moved: 25 };
+    game.players[0].figures[1] = Figure::OnField { moved: 2 };
+    game.players[1].figures[0] = Figure::OnField { moved: 10 };
+
+    game
+}
+
+#[tokio::test]
+#[traced_test]
+async fn undo_restores_captured_figure() {
+    let mut game = capturing_game(vec![GameRequest::Move { figure: 0 }, GameRequest::Undo]);
+    game.undo_window = Some(Duration::from_secs(60));
+
+    let mut distr = DumbDistr {};
+    let (_rejointx, mut rejoinrx) = tokio::sync::mpsc::unbounded_channel();
+
+    let state = server::statemachine::step(
+        GameState::Rolled { value: 5 },
+        &mut game,
+        &mut rejoinrx,
+        &mut distr,
+    )
+    .await
+    .expect("");
+
+    assert_eq!(
+        GameState::UndoWindow {
+            value: 5,
+            next: Box::new(GameState::MoveToNextTurn)
+        },
+        state
+    );
+    assert_eq!(Figure::OnField { moved: 30 }, game.players[0].figures[0]);
+    assert_eq!(Figure::InStart, game.players[1].figures[0]);
+    assert_eq!(2, game.history().len());
+
+    let state = server::statemachine::step(state, &mut game, &mut rejoinrx, &mut distr)
+        .await
+        .expect("");
+
+    assert_eq!(GameState::Rolled { value: 5 }, state);
+    assert_eq!(0, game.next_player);
+    assert_eq!(Figure::OnField { moved: 25 }, game.players[0].figures[0]);
+    assert_eq!(Figure::OnField { moved: 10 }, game.players[1].figures[0]);
+    assert!(game.history().is_empty());
+}
+
+#[tokio::test]
+#[traced_test]
+async fn move_is_committed_after_window() {
+    let mut game = capturing_game(vec![GameRequest::Move { figure: 0 }]);
+    game.undo_window = Some(Duration::from_millis(50));
+
+    let mut distr = DumbDistr {};
+    let (_rejointx, mut rejoinrx) = tokio::sync::mpsc::unbounded_channel();
+
+    let mut state = GameState::Rolled { value: 5 };
+    for _ in 0..2 {
+        state = server::statemachine::step(state, &mut game, &mut rejoinrx, &mut distr)
+            .await
+            .expect("");
+    }
+
+    assert_eq!(GameState::MoveToNextTurn, state);
+    assert_eq!(Figure::OnField { moved: 30 }, game.players[0].figures[0]);
+    assert_eq!(Figure::InStart, game.players[1].figures[0]);
+    assert_eq!(2, game.history().len());
+}
+
+#[tokio::test]
+#[traced_test]
+async fn no_window_by_default() {
+    let mut game = capturing_game(vec![GameRequest::Move { figure: 0 }]);
+
+    let mut distr = DumbDistr {};
+    let (_rejointx, mut rejoinrx) = tokio::sync::mpsc::unbounded_channel();
+
+    let state = server::statemachine::step(
+        GameState::Rolled { value: 5 },
+        &mut game,
+        &mut rejoinrx,
+        &mut distr,
+    )
+    .await
+    .expect("");
+
+    assert_eq!(GameState::MoveToNextTurn, state);
+    assert_eq!(Figure::InStart, game.players[1].figures[0]);
+}